@@ -1,3 +1,8 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
 #[derive(Clone, Debug)]
 pub struct Margin {
     pub top: f64,
@@ -7,7 +12,8 @@ pub struct Margin {
 }
 
 impl Margin {
-    pub fn same(value: f64) -> Self {
+    #[must_use]
+    pub const fn same(value: f64) -> Self {
         Self {
             top: value,
             right: value,
@@ -15,7 +21,8 @@ impl Margin {
             left: value,
         }
     }
-    pub fn sides(left: f64, right: f64) -> Self {
+    #[must_use]
+    pub const fn sides(left: f64, right: f64) -> Self {
         Self {
             top: 0.0,
             right,
@@ -23,7 +30,8 @@ impl Margin {
             left,
         }
     }
-    pub fn vertical(top: f64, bottom: f64) -> Self {
+    #[must_use]
+    pub const fn vertical(top: f64, bottom: f64) -> Self {
         Self {
             top,
             right: 0.0,
@@ -31,7 +39,8 @@ impl Margin {
             left: 0.0,
         }
     }
-    pub fn top(top: f64) -> Self {
+    #[must_use]
+    pub const fn top(top: f64) -> Self {
         Self {
             top,
             right: 0.0,
@@ -39,7 +48,8 @@ impl Margin {
             left: 0.0,
         }
     }
-    pub fn right(right: f64) -> Self {
+    #[must_use]
+    pub const fn right(right: f64) -> Self {
         Self {
             top: 0.0,
             right,
@@ -47,7 +57,8 @@ impl Margin {
             left: 0.0,
         }
     }
-    pub fn bottom(bottom: f64) -> Self {
+    #[must_use]
+    pub const fn bottom(bottom: f64) -> Self {
         Self {
             top: 0.0,
             right: 0.0,
@@ -55,7 +66,8 @@ impl Margin {
             left: 0.0,
         }
     }
-    pub fn left(left: f64) -> Self {
+    #[must_use]
+    pub const fn left(left: f64) -> Self {
         Self {
             top: 0.0,
             right: 0.0,
@@ -71,6 +83,28 @@ impl Default for Margin {
     }
 }
 
+/// Written like CSS: `"1"`, `"1 2"`, `"1 2 3"` or `"1 2 3 4"`, going
+/// top, right, bottom, left.
+impl Display for Margin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_sides(f, [self.top, self.right, self.bottom, self.left])
+    }
+}
+
+impl FromStr for Margin {
+    type Err = InvalidSpacing;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [top, right, bottom, left] = parse_sides(s)?;
+        Ok(Self {
+            top,
+            right,
+            bottom,
+            left,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Padding {
     pub top: f64,
@@ -79,7 +113,8 @@ pub struct Padding {
     pub left: f64,
 }
 impl Padding {
-    pub fn same(value: f64) -> Self {
+    #[must_use]
+    pub const fn same(value: f64) -> Self {
         Self {
             top: value,
             right: value,
@@ -87,7 +122,8 @@ impl Padding {
             left: value,
         }
     }
-    pub fn sides(left: f64, right: f64) -> Self {
+    #[must_use]
+    pub const fn sides(left: f64, right: f64) -> Self {
         Self {
             top: 0.0,
             right,
@@ -95,7 +131,8 @@ impl Padding {
             left,
         }
     }
-    pub fn vertical(top: f64, bottom: f64) -> Self {
+    #[must_use]
+    pub const fn vertical(top: f64, bottom: f64) -> Self {
         Self {
             top,
             right: 0.0,
@@ -103,7 +140,8 @@ impl Padding {
             left: 0.0,
         }
     }
-    pub fn top(top: f64) -> Self {
+    #[must_use]
+    pub const fn top(top: f64) -> Self {
         Self {
             top,
             right: 0.0,
@@ -111,7 +149,8 @@ impl Padding {
             left: 0.0,
         }
     }
-    pub fn right(right: f64) -> Self {
+    #[must_use]
+    pub const fn right(right: f64) -> Self {
         Self {
             top: 0.0,
             right,
@@ -119,7 +158,8 @@ impl Padding {
             left: 0.0,
         }
     }
-    pub fn bottom(bottom: f64) -> Self {
+    #[must_use]
+    pub const fn bottom(bottom: f64) -> Self {
         Self {
             top: 0.0,
             right: 0.0,
@@ -127,7 +167,8 @@ impl Padding {
             left: 0.0,
         }
     }
-    pub fn left(left: f64) -> Self {
+    #[must_use]
+    pub const fn left(left: f64) -> Self {
         Self {
             top: 0.0,
             right: 0.0,
@@ -142,3 +183,69 @@ impl Default for Padding {
         Self::same(0.0)
     }
 }
+
+/// Written like CSS, the same as [`Margin`].
+impl Display for Padding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_sides(f, [self.top, self.right, self.bottom, self.left])
+    }
+}
+
+impl FromStr for Padding {
+    type Err = InvalidSpacing;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [top, right, bottom, left] = parse_sides(s)?;
+        Ok(Self {
+            top,
+            right,
+            bottom,
+            left,
+        })
+    }
+}
+
+// Shorthand only applies when the sides are exactly equal.
+#[allow(clippy::float_cmp)]
+fn write_sides(f: &mut fmt::Formatter<'_>, sides: [f64; 4]) -> fmt::Result {
+    let [top, right, bottom, left] = sides;
+    if left != right {
+        write!(f, "{top} {right} {bottom} {left}")
+    } else if top != bottom {
+        write!(f, "{top} {right} {bottom}")
+    } else if top != right {
+        write!(f, "{top} {right}")
+    } else {
+        write!(f, "{top}")
+    }
+}
+
+fn parse_sides(s: &str) -> Result<[f64; 4], InvalidSpacing> {
+    let values: Option<Vec<f64>> = s
+        .split_whitespace()
+        .map(|value| {
+            value
+                .parse()
+                .ok()
+                .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+        })
+        .collect();
+    match values.as_deref() {
+        Some(&[all]) => Ok([all; 4]),
+        Some(&[vertical, horizontal]) => Ok([vertical, horizontal, vertical, horizontal]),
+        Some(&[top, horizontal, bottom]) => Ok([top, horizontal, bottom, horizontal]),
+        Some(&[top, right, bottom, left]) => Ok([top, right, bottom, left]),
+        _ => Err(InvalidSpacing(s.to_string())),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSpacing(pub String);
+
+impl Display for InvalidSpacing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid spacing `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidSpacing {}
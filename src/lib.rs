@@ -1,17 +1,22 @@
 #![warn(clippy::nursery, clippy::pedantic)]
 #![allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+#![cfg_attr(test, allow(clippy::float_cmp))]
 
 mod common;
 mod rect;
+#[cfg(feature = "serde")]
+mod serial;
+
+pub use common::{InvalidSpacing, Margin, Padding};
+pub use rect::{InvalidRect, Rect};
 
 #[cfg(feature = "tui")]
 pub mod tui;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test() {
         assert!(2 == 2);
     }
@@ -1,3 +1,8 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
 use glam::Vec2;
 use log::warn;
 
@@ -11,6 +16,10 @@ pub struct Rect {
 }
 
 impl Rect {
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is negative.
+    #[must_use]
     pub fn new(width: f64, height: f64) -> Self {
         assert!(width >= 0.0);
         assert!(height >= 0.0);
@@ -24,15 +33,18 @@ impl Rect {
         rect
     }
 
+    #[must_use]
     pub const fn with_position(self, position: Vec2) -> Self {
         Self { position, ..self }
     }
 
-    fn aspect_ratio(&self) -> f64 {
-        self.width / self.height
+    #[must_use]
+    pub const fn aspect_ratio(&self) -> f64 {
+        self.aspect_ratio
     }
 
-    pub fn pos_width_height(&self) -> (Vec2, f64, f64) {
+    #[must_use]
+    pub const fn pos_width_height(&self) -> (Vec2, f64, f64) {
         let Self {
             position,
             width,
@@ -42,10 +54,25 @@ impl Rect {
         (*position, *width, *height)
     }
 
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        let offset = point - self.position;
+        offset.x >= 0.0
+            && offset.y >= 0.0
+            && f64::from(offset.x) < self.width
+            && f64::from(offset.y) < self.height
+    }
+
     fn update_aspect_ratio(&mut self) {
         self.aspect_ratio = self.width / self.height;
     }
 
+    /// Shrinks the rect by `amount` from the top and returns the part cut off.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `amount` is negative or larger than the height.
+    #[must_use]
     pub fn cut_top(&mut self, amount: f64) -> Self {
         assert!(amount >= 0.0);
         assert!(amount <= self.height);
@@ -56,6 +83,12 @@ impl Rect {
         rect
     }
 
+    /// Shrinks the rect by `amount` from the bottom and returns the part cut off.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `amount` is negative or larger than the height.
+    #[must_use]
     pub fn cut_bottom(&mut self, amount: f64) -> Self {
         assert!(amount >= 0.0);
         assert!(amount <= self.height);
@@ -67,6 +100,12 @@ impl Rect {
         Self::new(self.width, amount).with_position(position)
     }
 
+    /// Shrinks the rect by `amount` from the left and returns the part cut off.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `amount` is negative or larger than the width.
+    #[must_use]
     pub fn cut_left(&mut self, amount: f64) -> Self {
         assert!(amount >= 0.0);
         assert!(amount <= self.width);
@@ -77,6 +116,12 @@ impl Rect {
         rect
     }
 
+    /// Shrinks the rect by `amount` from the right and returns the part cut off.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `amount` is negative or larger than the width.
+    #[must_use]
     pub fn cut_right(&mut self, amount: f64) -> Self {
         assert!(amount >= 0.0);
         assert!(amount <= self.width);
@@ -88,6 +133,12 @@ impl Rect {
         Self::new(amount, self.height).with_position(position)
     }
 
+    /// Splits the rect into `into_parts` columns of equal size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `into_parts` is zero.
+    #[must_use]
     pub fn divide_horizontally(mut self, into_parts: usize) -> Vec<Self> {
         assert!(into_parts >= 1);
         if into_parts == 1 {
@@ -102,6 +153,12 @@ impl Rect {
         result
     }
 
+    /// Splits the rect into `into_parts` rows of equal size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `into_parts` is zero.
+    #[must_use]
     pub fn divide_vertically(mut self, into_parts: usize) -> Vec<Self> {
         assert!(into_parts >= 1);
         if into_parts == 1 {
@@ -117,6 +174,68 @@ impl Rect {
     }
 }
 
+/// Written as `WIDTHxHEIGHT`, followed by `+X+Y` when the rect isn't at
+/// the origin, e.g. `40x10+2+3`.
+impl Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)?;
+        if self.position != Vec2::ZERO {
+            write!(f, "{:+}{:+}", self.position.x, self.position.y)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rect {
+    type Err = InvalidRect;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidRect(s.to_string());
+        let (width, rest) = s.trim().split_once('x').ok_or_else(invalid)?;
+        let (height, position) = match split_offset(rest) {
+            None => (rest, Vec2::ZERO),
+            Some((rest, y)) => {
+                let (height, x) = split_offset(rest).ok_or_else(invalid)?;
+                let coordinate = |c: &str| {
+                    c.parse::<f32>()
+                        .ok()
+                        .filter(|c| c.is_finite())
+                        .ok_or_else(invalid)
+                };
+                (height, Vec2::new(coordinate(x)?, coordinate(y)?))
+            }
+        };
+        let size = |d: &str| {
+            d.parse::<f64>()
+                .ok()
+                .filter(|d| d.is_finite() && *d >= 0.0)
+                .ok_or_else(invalid)
+        };
+        Ok(Self::new(size(width)?, size(height)?).with_position(position))
+    }
+}
+
+/// Splits the last signed offset off the end of `s`. Signs that follow an
+/// exponent's `e` belong to a number, and so does a sign at the very start.
+fn split_offset(s: &str) -> Option<(&str, &str)> {
+    let bytes = s.as_bytes();
+    let sign = (1..bytes.len())
+        .rev()
+        .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'))?;
+    Some(s.split_at(sign))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRect(pub String);
+
+impl Display for InvalidRect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rect `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidRect {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,7 +244,17 @@ mod tests {
     fn aspect_ratio() {
         let rect = Rect::new(640.0, 480.0);
         let Rect { aspect_ratio, .. } = rect;
-        assert_eq!(aspect_ratio, (4.0 / 3.0) as f64);
+        assert_eq!(aspect_ratio, 4.0 / 3.0);
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rect::new(10.0, 5.0).with_position(Vec2::new(2.0, 3.0));
+        assert!(rect.contains(Vec2::new(2.0, 3.0)));
+        assert!(rect.contains(Vec2::new(11.5, 7.9)));
+        assert!(!rect.contains(Vec2::new(12.0, 4.0)));
+        assert!(!rect.contains(Vec2::new(1.9, 4.0)));
+        assert!(!rect.contains(Vec2::new(4.0, 8.0)));
     }
 
     #[test]
@@ -204,4 +333,18 @@ mod tests {
         assert_eq!(top_third, expected_top);
         assert!(division.is_empty());
     }
+
+    #[test]
+    fn from_str() {
+        let rect: Rect = "2x1e-3+0-1.5".parse().unwrap();
+        let expected = Rect::new(2.0, 0.001).with_position(Vec2::new(0.0, -1.5));
+        assert_eq!(rect, expected);
+        let rect: Rect = "1e+2x4-1e-1+3".parse().unwrap();
+        let expected = Rect::new(100.0, 4.0).with_position(Vec2::new(-0.1, 3.0));
+        assert_eq!(rect, expected);
+        for invalid in ["infx1", "1xNaN", "1x1+inf+0", "1x+1+2"] {
+            assert!(invalid.parse::<Rect>().is_err(), "{invalid}");
+        }
+        assert_eq!("1x1e+2".parse::<Rect>(), Ok(Rect::new(1.0, 100.0)));
+    }
 }
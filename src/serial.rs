@@ -0,0 +1,216 @@
+//! Serde support behind the `serde` feature.
+//!
+//! Every type is stored as its text form, so config files hold the same
+//! strings `Display` prints and `FromStr` reads, e.g. `margin = "1 2"`.
+
+use std::{
+    fmt::{self, Display},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Margin, Padding, Rect};
+
+struct TextVisitor<T> {
+    expecting: &'static str,
+    target: PhantomData<T>,
+}
+
+impl<T> de::Visitor<'_> for TextVisitor<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    // A bare number is accepted wherever its text would be, as in
+    // `padding = 1`.
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<T, E> {
+        self.visit_str(&v.to_string())
+    }
+}
+
+macro_rules! as_text {
+    ($($ty:ty => $deserialize:ident, $expecting:literal;)*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.$deserialize(TextVisitor {
+                    expecting: $expecting,
+                    target: PhantomData,
+                })
+            }
+        }
+    )*};
+}
+
+as_text! {
+    Margin => deserialize_any, "a number or CSS-like sides such as \"1 2\"";
+    Padding => deserialize_any, "a number or CSS-like sides such as \"1 2\"";
+    Rect => deserialize_str, "a rect such as \"40x10+2+3\"";
+}
+
+#[cfg(feature = "tui")]
+as_text! {
+    crate::tui::Role => deserialize_str, "a style role such as \"border.focused\"";
+    crate::tui::TuiBorder => deserialize_str, "a border such as \"smooth-corner\"";
+    crate::tui::SplitState => deserialize_str, "a split state such as \"0.3 second\"";
+    crate::tui::Layout => deserialize_str, "a tiling layout such as \"(h 0.5 0 1)\"";
+}
+
+/// A theme is stored like a theme file, with every role's style as a spec
+/// string under `styles`. Reading accepts anything a theme file may hold,
+/// including a `base` theme.
+#[cfg(feature = "tui")]
+impl Serialize for crate::tui::Theme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        use crate::tui::{style_spec, Role};
+
+        struct Styles<'a>(&'a crate::tui::Theme);
+
+        impl Serialize for Styles<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_map(Role::ALL.map(|role| (role, style_spec(self.0.style(role)))))
+            }
+        }
+
+        let mut theme = serializer.serialize_struct("Theme", 1)?;
+        theme.serialize_field("styles", &Styles(self))?;
+        theme.end()
+    }
+}
+
+#[cfg(feature = "tui")]
+impl<'de> Deserialize<'de> for crate::tui::Theme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let table = toml::Table::deserialize(deserializer)?;
+        Self::from_table(&table).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(all(test, feature = "tui"))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use glam::Vec2;
+    use toml::Value;
+
+    use super::*;
+    use crate::tui::{parse_style, Layout, Role, SplitState, Theme, TuiBorder};
+
+    fn round_trip<T>(value: &T) -> (Value, T)
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        let stored = Value::try_from(value).unwrap();
+        let restored = T::deserialize(stored.clone()).unwrap();
+        (stored, restored)
+    }
+
+    #[test]
+    fn spacing_uses_css_shorthand() {
+        let cases = [
+            (Margin::same(1.0), "1"),
+            (Margin::vertical(1.0, 1.0), "1 0"),
+            (Margin::top(2.0), "2 0 0"),
+            (Margin::left(0.5), "0 0 0 0.5"),
+        ];
+        for (margin, text) in cases {
+            let (stored, restored) = round_trip(&margin);
+            assert_eq!(stored, Value::String(text.to_string()));
+            assert_eq!(restored.to_string(), text);
+        }
+
+        let config: BTreeMap<String, Padding> =
+            toml::from_str("a = \"1 2\"\nb = 3\nc = \"1 2 3 4\"").unwrap();
+        assert_eq!(config["a"].to_string(), "1 2");
+        assert_eq!(config["b"].to_string(), "3");
+        assert_eq!(config["c"].left, 4.0);
+        for invalid in [
+            "a = \"\"",
+            "a = \"1 2 3 4 5\"",
+            "a = -1",
+            "a = true",
+            "a = \"inf\"",
+            "a = \"1 NaN\"",
+            "a = inf",
+        ] {
+            assert!(toml::from_str::<BTreeMap<String, Padding>>(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn rects_store_size_and_position() {
+        let rect = Rect::new(40.0, 10.5).with_position(Vec2::new(2.0, -3.0));
+        let (stored, restored) = round_trip(&rect);
+        assert_eq!(stored, Value::String("40x10.5+2-3".to_string()));
+        assert_eq!(restored, rect);
+        let (stored, _) = round_trip(&Rect::new(8.0, 4.0));
+        assert_eq!(stored, Value::String("8x4".to_string()));
+        for invalid in ["8", "8x", "x4", "-8x4", "8x4+1", "8x4+1+2+3", "8x4+a+1"] {
+            assert!(invalid.parse::<Rect>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn layout_types_round_trip() {
+        for border in [TuiBorder::None, TuiBorder::SmoothCorner] {
+            assert_eq!(round_trip(&border).1, border);
+        }
+        assert_eq!(
+            Value::try_from(TuiBorder::SmoothCorner).unwrap(),
+            Value::String("smooth-corner".to_string())
+        );
+        let state: SplitState = "0.3 second".parse().unwrap();
+        assert_eq!(round_trip(&state).1, state);
+        let layout: Layout = "(h 0.25 3 (v 0.5 first 1 7))".parse().unwrap();
+        assert_eq!(round_trip(&layout).1, layout);
+        assert!(TuiBorder::deserialize(Value::String("dotted".to_string())).is_err());
+    }
+
+    #[test]
+    fn themes_store_style_specs() {
+        let theme = Theme::light()
+            .with_style(Role::Selection, parse_style("bold #87afff on 236").unwrap())
+            .with_style(Role::Text, ansi_term::Style::new());
+        let (stored, restored) = round_trip(&theme);
+        assert_eq!(restored, theme);
+        let styles = stored["styles"].as_table().unwrap();
+        assert_eq!(styles.len(), Role::ALL.len());
+        assert_eq!(styles["selection"].as_str(), Some("bold #87afff on 236"));
+        assert_eq!(styles["border.focused"].as_str(), Some("bold blue"));
+        assert_eq!(styles["text"].as_str(), Some(""));
+        assert_eq!(round_trip(&Role::TextMuted).1, Role::TextMuted);
+
+        let config: BTreeMap<String, Theme> =
+            toml::from_str("[ui]\nbase = \"high-contrast\"\nstyles = { text = \"red\" }").unwrap();
+        let expected = Theme::high_contrast().with_style(Role::Text, parse_style("red").unwrap());
+        assert_eq!(config["ui"], expected);
+        assert!(toml::from_str::<BTreeMap<String, Theme>>("[ui]\nbase = \"neon\"").is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use std::{iter::Peekable, str::Chars};
+
+use ansi_term::{Colour, Style};
+
+use super::text::{Span, Text, TextLine};
+
+const TAB_WIDTH: usize = 8;
+
+/// The line being written, with a cursor that `\r` and backspace move back
+/// so that progress output overwrites itself like it does in a terminal.
+#[derive(Debug, Default)]
+struct Row {
+    cells: Vec<(char, Style)>,
+    cursor: usize,
+}
+
+impl Row {
+    fn put(&mut self, symbol: char, style: Style) {
+        match self.cells.get_mut(self.cursor) {
+            Some(cell) => *cell = (symbol, style),
+            None => self.cells.push((symbol, style)),
+        }
+        self.cursor += 1;
+    }
+
+    fn erase(&mut self, mode: u16) {
+        let blank = (' ', Style::new());
+        match mode {
+            0 => self.cells.truncate(self.cursor),
+            1 => {
+                let end = self.cursor.min(self.cells.len());
+                self.cells[..end].fill(blank);
+            }
+            _ => self.cells.clear(),
+        }
+    }
+
+    fn finish(&mut self) -> TextLine {
+        let line = self
+            .cells
+            .drain(..)
+            .fold(TextLine::new(), |line, (symbol, style)| {
+                let content = symbol.to_string();
+                line.with_span(if style == Style::new() {
+                    Span::raw(content)
+                } else {
+                    Span::styled(content, style)
+                })
+            });
+        self.cursor = 0;
+        line
+    }
+}
+
+/// Converts text containing ANSI escape codes, such as the coloured output
+/// of `cargo` or `git`, into styled text.
+///
+/// SGR colours and modifiers become span styles. A lone `\r` returns to the
+/// start of the line and erase-in-line is honoured, so progress bars end up
+/// showing their last state. Every other escape or control sequence is
+/// dropped.
+#[must_use]
+pub fn parse_ansi(input: &str) -> Text {
+    let mut text = Text::new();
+    let mut row = Row::default();
+    let mut style = Style::new();
+    let mut chars = input.chars().peekable();
+    while let Some(symbol) = chars.next() {
+        match symbol {
+            '\x1B' => match chars.next() {
+                Some('[') => {
+                    let (params, end) = control_sequence(&mut chars);
+                    match end {
+                        Some('m') => style = select_graphic_rendition(style, &params),
+                        Some('K') => row.erase(params.first().copied().unwrap_or(0)),
+                        _ => {}
+                    }
+                }
+                Some(']' | 'P' | '_' | '^') => skip_string(&mut chars),
+                Some(' '..='/') => {
+                    while chars.next_if(|c| matches!(c, ' '..='/')).is_some() {}
+                    chars.next();
+                }
+                _ => {}
+            },
+            '\n' => text.push_line(row.finish()),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' => row.cursor = 0,
+            '\x08' => row.cursor = row.cursor.saturating_sub(1),
+            '\t' => {
+                for _ in 0..TAB_WIDTH - row.cursor % TAB_WIDTH {
+                    row.put(' ', style);
+                }
+            }
+            _ if symbol.is_control() => {}
+            _ => row.put(symbol, style),
+        }
+    }
+    if !row.cells.is_empty() || text.lines().is_empty() {
+        text.push_line(row.finish());
+    }
+    text
+}
+
+/// Reads the parameters and final byte of a CSI sequence. Parameters that
+/// do not fit or are missing read as zero.
+fn control_sequence(chars: &mut Peekable<Chars<'_>>) -> (Vec<u16>, Option<char>) {
+    let mut raw = String::new();
+    while let Some(c) = chars.next_if(|c| matches!(c, '0'..='?' | ' '..='/')) {
+        raw.push(c);
+    }
+    let params = raw
+        .split([';', ':'])
+        .map(|param| param.parse().unwrap_or(0))
+        .collect();
+    (params, chars.next_if(|c| matches!(c, '@'..='~')))
+}
+
+/// Skips an OSC or similar string up to its BEL or ST terminator.
+fn skip_string(chars: &mut Peekable<Chars<'_>>) {
+    while let Some(c) = chars.next() {
+        if c == '\x07' || (c == '\x1B' && chars.next_if_eq(&'\\').is_some()) {
+            break;
+        }
+    }
+}
+
+fn select_graphic_rendition(mut style: Style, params: &[u16]) -> Style {
+    let mut params = params.iter().copied();
+    while let Some(param) = params.next() {
+        style = match param {
+            0 => Style::new(),
+            1 => style.bold(),
+            2 => style.dimmed(),
+            3 => style.italic(),
+            4 => style.underline(),
+            5 | 6 => style.blink(),
+            7 => style.reverse(),
+            8 => style.hidden(),
+            9 => style.strikethrough(),
+            21 | 22 => Style {
+                is_bold: false,
+                is_dimmed: false,
+                ..style
+            },
+            23 => Style {
+                is_italic: false,
+                ..style
+            },
+            24 => Style {
+                is_underline: false,
+                ..style
+            },
+            25 => Style {
+                is_blink: false,
+                ..style
+            },
+            27 => Style {
+                is_reverse: false,
+                ..style
+            },
+            28 => Style {
+                is_hidden: false,
+                ..style
+            },
+            29 => Style {
+                is_strikethrough: false,
+                ..style
+            },
+            30..=37 => style.fg(basic(param - 30)),
+            38 => Style {
+                foreground: extended(&mut params),
+                ..style
+            },
+            39 => Style {
+                foreground: None,
+                ..style
+            },
+            40..=47 => style.on(basic(param - 40)),
+            48 => Style {
+                background: extended(&mut params),
+                ..style
+            },
+            49 => Style {
+                background: None,
+                ..style
+            },
+            90..=97 => style.fg(Colour::Fixed((param - 90 + 8) as u8)),
+            100..=107 => style.on(Colour::Fixed((param - 100 + 8) as u8)),
+            _ => style,
+        };
+    }
+    style
+}
+
+const fn basic(index: u16) -> Colour {
+    match index {
+        0 => Colour::Black,
+        1 => Colour::Red,
+        2 => Colour::Green,
+        3 => Colour::Yellow,
+        4 => Colour::Blue,
+        5 => Colour::Purple,
+        6 => Colour::Cyan,
+        _ => Colour::White,
+    }
+}
+
+/// The `5;n` and `2;r;g;b` forms following a 38 or 48.
+fn extended(params: &mut impl Iterator<Item = u16>) -> Option<Colour> {
+    let mut channel = || params.next().and_then(|value| u8::try_from(value).ok());
+    match channel()? {
+        5 => channel().map(Colour::Fixed),
+        2 => Some(Colour::RGB(channel()?, channel()?, channel()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colours_become_spans() {
+        let text =
+            parse_ansi("\x1B[1;32m   Compiling\x1B[0m yui\n\x1B[38;5;208mwarn\x1B[39;4mx\x1B[m");
+        let green = Colour::Green.bold();
+        let expected = Text::new()
+            .with_line(
+                TextLine::new()
+                    .with_span(Span::styled("   Compiling", green))
+                    .with_span(" yui"),
+            )
+            .with_line(
+                TextLine::new()
+                    .with_span(Span::styled("warn", Colour::Fixed(208).normal()))
+                    .with_span(Span::styled("x", Style::new().underline())),
+            );
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn strips_other_sequences() {
+        let input = "\x1B]8;;https://x.rs\x1B\\link\x1B]8;;\x07 \x1B[2Jok\x1B(B\x07\x1B[?25l!";
+        assert_eq!(parse_ansi(input).to_string(), "link ok!");
+        assert_eq!(parse_ansi("cut\x1B[").to_string(), "cut");
+        assert_eq!(parse_ansi("a\r\nb\tc").to_string(), "a\nb       c");
+    }
+
+    #[test]
+    fn carriage_return_overwrites() {
+        let progress = "Building [==  ] 1/4\rBuilding [=== ] 3/4\r\x1B[Kdone\n";
+        assert_eq!(parse_ansi(progress).to_string(), "done");
+        assert_eq!(parse_ansi("12345\rab").to_string(), "ab345");
+    }
+}
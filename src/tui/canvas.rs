@@ -0,0 +1,552 @@
+use std::fmt::Debug;
+
+use ansi_term::{Colour, Style};
+use glam::Vec2;
+
+use super::{
+    cell::Cell,
+    container::Container,
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Marker {
+    /// 2×4 dots per cell.
+    #[default]
+    Braille,
+    /// 1×2 dots per cell.
+    HalfBlock,
+}
+
+impl Marker {
+    #[must_use]
+    pub const fn resolution(self) -> (usize, usize) {
+        match self {
+            Self::Braille => (2, 4),
+            Self::HalfBlock => (1, 2),
+        }
+    }
+
+    const fn bit(self, x: usize, y: usize) -> u8 {
+        match self {
+            Self::Braille => match (x, y) {
+                (0, 0) => 0x01,
+                (0, 1) => 0x02,
+                (0, 2) => 0x04,
+                (1, 0) => 0x08,
+                (1, 1) => 0x10,
+                (1, 2) => 0x20,
+                (0, _) => 0x40,
+                _ => 0x80,
+            },
+            Self::HalfBlock => 1 << y,
+        }
+    }
+
+    fn symbol(self, mask: u8) -> char {
+        match self {
+            Self::Braille => char::from_u32(0x2800 + u32::from(mask)).unwrap_or(' '),
+            Self::HalfBlock => match mask {
+                0 => ' ',
+                1 => '▀',
+                2 => '▄',
+                _ => '█',
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Dots {
+    mask: u8,
+    colour: Option<Colour>,
+}
+
+/// A cell grid that shapes are rasterized into at sub-cell resolution.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    marker: Marker,
+    cells: Vec<Dots>,
+}
+
+impl Grid {
+    #[must_use]
+    pub fn new(width: usize, height: usize, marker: Marker) -> Self {
+        Self {
+            width,
+            height,
+            marker,
+            cells: vec![Dots::default(); width * height],
+        }
+    }
+
+    #[must_use]
+    pub const fn resolution(&self) -> (usize, usize) {
+        let (x, y) = self.marker.resolution();
+        (self.width * x, self.height * y)
+    }
+
+    pub fn set_dot(&mut self, x: usize, y: usize, colour: Colour) {
+        let (resolution_x, resolution_y) = self.marker.resolution();
+        let (column, row) = (x / resolution_x, y / resolution_y);
+        if column >= self.width || row >= self.height {
+            return;
+        }
+        let dots = &mut self.cells[row * self.width + column];
+        dots.mask |= self.marker.bit(x % resolution_x, y % resolution_y);
+        dots.colour = Some(colour);
+    }
+
+    /// The symbol and colour of a cell, or `None` when nothing was drawn there.
+    #[must_use]
+    pub fn get(&self, column: usize, row: usize) -> Option<(char, Option<Colour>)> {
+        if column >= self.width || row >= self.height {
+            return None;
+        }
+        let dots = self.cells[row * self.width + column];
+        (dots.mask != 0).then(|| (self.marker.symbol(dots.mask), dots.colour))
+    }
+
+    /// Draws a line between two dots, clipped to the grid.
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64), colour: Colour) {
+        let as_f64 = |(x, y): (i64, i64)| (x as f64, y as f64);
+        let Some(((mut x, mut y), to)) = clip_to_dots(as_f64(from), as_f64(to), self.resolution())
+        else {
+            return;
+        };
+        let (dx, dy) = ((to.0 - x).abs(), -(to.1 - y).abs());
+        let (step_x, step_y) = ((to.0 - x).signum(), (to.1 - y).signum());
+        let mut error = dx + dy;
+        loop {
+            self.set_signed_dot(x, y, colour);
+            if (x, y) == to {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    fn set_signed_dot(&mut self, x: i64, y: i64, colour: Colour) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+            self.set_dot(x, y, colour);
+        }
+    }
+}
+
+/// The part of the segment inside the box from `min` to `max`, found with
+/// Liang–Barsky clipping.
+pub(super) fn clip_segment(
+    from: (f64, f64),
+    to: (f64, f64),
+    min: (f64, f64),
+    max: (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let (mut enter, mut leave) = (0.0_f64, 1.0_f64);
+    let edges = [
+        (-dx, from.0 - min.0),
+        (dx, max.0 - from.0),
+        (-dy, from.1 - min.1),
+        (dy, max.1 - from.1),
+    ];
+    for (direction, distance) in edges {
+        if direction == 0.0 {
+            if distance < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let t = distance / direction;
+        if direction < 0.0 {
+            enter = enter.max(t);
+        } else {
+            leave = leave.min(t);
+        }
+        if enter > leave {
+            return None;
+        }
+    }
+    let at = |t: f64| (t.mul_add(dx, from.0), t.mul_add(dy, from.1));
+    Some((at(enter), at(leave)))
+}
+
+const fn round_dot((x, y): (f64, f64)) -> (i64, i64) {
+    (x.round() as i64, y.round() as i64)
+}
+
+/// Clips a segment to the positions that round onto a grid's dots.
+fn clip_to_dots(
+    from: (f64, f64),
+    to: (f64, f64),
+    (width, height): (usize, usize),
+) -> Option<((i64, i64), (i64, i64))> {
+    let max = (width as f64 - 0.5, height as f64 - 0.5);
+    let (from, to) = clip_segment(from, to, (-0.5, -0.5), max)?;
+    Some((round_dot(from), round_dot(to)))
+}
+
+/// Maps canvas coordinates onto the dots of a [`Grid`]. The y axis points up.
+#[derive(Debug)]
+pub struct Painter<'a> {
+    grid: &'a mut Grid,
+    min: Vec2,
+    max: Vec2,
+}
+
+impl<'a> Painter<'a> {
+    pub const fn new(grid: &'a mut Grid, min: Vec2, max: Vec2) -> Self {
+        Self { grid, min, max }
+    }
+
+    /// The dot under a point, or `None` for points that aren't finite.
+    pub fn dot_of(&self, point: Vec2) -> Option<(i64, i64)> {
+        self.position_of(point).map(round_dot)
+    }
+
+    fn position_of(&self, point: Vec2) -> Option<(f64, f64)> {
+        if !point.is_finite() {
+            return None;
+        }
+        let (width, height) = self.grid.resolution();
+        let size = (self.max - self.min).max(Vec2::splat(f32::EPSILON));
+        let relative = (point - self.min) / size;
+        let x = f64::from(relative.x) * (width.saturating_sub(1)) as f64;
+        let y = (1.0 - f64::from(relative.y)) * (height.saturating_sub(1)) as f64;
+        (x.is_finite() && y.is_finite()).then_some((x, y))
+    }
+
+    /// Size of one dot in canvas units.
+    #[must_use]
+    pub fn dot_size(&self) -> Vec2 {
+        let (width, height) = self.grid.resolution();
+        (self.max - self.min) / Vec2::new(width.max(1) as f32, height.max(1) as f32)
+    }
+
+    pub fn point(&mut self, point: Vec2, colour: Colour) {
+        if let Some((x, y)) = self.dot_of(point) {
+            self.grid.set_signed_dot(x, y, colour);
+        }
+    }
+
+    /// Draws a line, skipping it when either end isn't finite.
+    pub fn line(&mut self, from: Vec2, to: Vec2, colour: Colour) {
+        let (Some(from), Some(to)) = (self.position_of(from), self.position_of(to)) else {
+            return;
+        };
+        // Clip before rounding, so far away ends keep the line's slope.
+        if let Some((from, to)) = clip_to_dots(from, to, self.grid.resolution()) {
+            self.grid.line(from, to, colour);
+        }
+    }
+}
+
+pub trait Shape: Debug {
+    fn draw(&self, painter: &mut Painter);
+}
+
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub colour: Colour,
+}
+
+impl Shape for Line {
+    fn draw(&self, painter: &mut Painter) {
+        painter.line(self.from, self.to, self.colour);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rectangle {
+    pub min: Vec2,
+    pub max: Vec2,
+    pub colour: Colour,
+}
+
+impl Shape for Rectangle {
+    fn draw(&self, painter: &mut Painter) {
+        let corners = [
+            self.min,
+            Vec2::new(self.max.x, self.min.y),
+            self.max,
+            Vec2::new(self.min.x, self.max.y),
+        ];
+        for (i, &corner) in corners.iter().enumerate() {
+            painter.line(corner, corners[(i + 1) % corners.len()], self.colour);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f32,
+    pub colour: Colour,
+}
+
+impl Shape for Circle {
+    #[allow(clippy::cast_sign_loss)]
+    fn draw(&self, painter: &mut Painter) {
+        let dot = painter.dot_size().min_element().max(f32::EPSILON);
+        let steps = ((std::f32::consts::TAU * self.radius / dot).ceil() as usize).clamp(8, 4096);
+        let at = |step: usize| {
+            let angle = std::f32::consts::TAU * step as f32 / steps as f32;
+            self.center + self.radius * Vec2::new(angle.cos(), angle.sin())
+        };
+        for step in 0..steps {
+            painter.line(at(step), at(step + 1), self.colour);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Points {
+    pub coords: Vec<Vec2>,
+    pub colour: Colour,
+}
+
+impl Shape for Points {
+    fn draw(&self, painter: &mut Painter) {
+        for &point in &self.coords {
+            painter.point(point, self.colour);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Canvas {
+    frame: Container,
+    marker: Marker,
+    min: Vec2,
+    max: Vec2,
+    layers: Vec<Vec<Box<dyn Shape>>>,
+    /// The finished layers flattened into cells.
+    cells: Vec<Option<(char, Option<Colour>)>>,
+    /// The dots of the layer that shapes are being drawn into.
+    top: Grid,
+}
+
+impl Canvas {
+    #[must_use]
+    pub fn new(frame: Container) -> Self {
+        let mut canvas = Self {
+            frame,
+            marker: Marker::default(),
+            min: Vec2::ZERO,
+            max: Vec2::ONE,
+            layers: vec![Vec::new()],
+            cells: Vec::new(),
+            top: Grid::new(0, 0, Marker::default()),
+        };
+        canvas.rasterize();
+        canvas
+    }
+
+    #[must_use]
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self.rasterize();
+        self
+    }
+
+    #[must_use]
+    pub fn with_bounds(mut self, min: Vec2, max: Vec2) -> Self {
+        self.min = min;
+        self.max = max;
+        self.rasterize();
+        self
+    }
+
+    /// Rasterizes the shape into the current layer and keeps it, so that it
+    /// can be drawn again when the bounds or the marker change.
+    pub fn draw(&mut self, shape: impl Shape + 'static) {
+        shape.draw(&mut Painter::new(&mut self.top, self.min, self.max));
+        if let Some(layer) = self.layers.last_mut() {
+            layer.push(Box::new(shape));
+        }
+    }
+
+    /// Shapes drawn after this cover cells drawn by earlier layers instead
+    /// of merging their dots into them.
+    pub fn layer(&mut self) {
+        self.flatten_top();
+        self.layers.push(Vec::new());
+    }
+
+    pub fn clear(&mut self) {
+        self.layers = vec![Vec::new()];
+        self.rasterize();
+    }
+
+    /// Moves the cells drawn in the current layer below a new, empty one.
+    fn flatten_top(&mut self) {
+        let Grid { width, height, .. } = self.top;
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            if let Some(drawn) = self.top.get(i % width, i / width) {
+                *cell = Some(drawn);
+            }
+        }
+        self.top = Grid::new(width, height, self.marker);
+    }
+
+    /// Draws every shape again from scratch.
+    fn rasterize(&mut self) {
+        let (width, height) = self.frame.content_size();
+        self.cells = vec![None; width * height];
+        self.top = Grid::new(width, height, self.marker);
+        let layers = std::mem::take(&mut self.layers);
+        for (i, layer) in layers.iter().enumerate() {
+            if i > 0 {
+                self.flatten_top();
+            }
+            let mut painter = Painter::new(&mut self.top, self.min, self.max);
+            for shape in layer {
+                shape.draw(&mut painter);
+            }
+        }
+        self.layers = layers;
+    }
+}
+
+impl Widget for Canvas {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let (width, _) = self.frame.content_size();
+        let drawn = self.top.get(x, y);
+        Some(
+            match drawn.or_else(|| self.cells.get(y * width + x).copied().flatten()) {
+                Some((symbol, colour)) => {
+                    let style =
+                        colour.map_or_else(|| theme.style(Role::Text), |c| Style::new().fg(c));
+                    Cell::new(symbol, style)
+                }
+                None => Cell::blank(theme.style(Role::Text)),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rect::Rect;
+
+    fn symbols(canvas: &Canvas, width: usize, height: usize) -> Vec<String> {
+        let theme = Theme::default();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| canvas.contents_of(x, y, &theme).unwrap().symbol)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn braille_line() {
+        let mut canvas = Canvas::new(Container::new(Rect::new(2.0, 1.0)));
+        canvas.draw(Line {
+            from: Vec2::new(0.0, 1.0),
+            to: Vec2::new(1.0, 0.0),
+            colour: Colour::Red,
+        });
+        // Dots (0,0) (1,1) on the left cell, (2,2) (3,3) on the right one.
+        assert_eq!(symbols(&canvas, 2, 1), vec!["⠑⢄"]);
+    }
+
+    #[test]
+    fn half_block_rectangle() {
+        let mut canvas = Canvas::new(Container::new(Rect::new(3.0, 2.0)))
+            .with_marker(Marker::HalfBlock)
+            .with_bounds(Vec2::ZERO, Vec2::new(2.0, 3.0));
+        canvas.draw(Rectangle {
+            min: Vec2::ZERO,
+            max: Vec2::new(2.0, 3.0),
+            colour: Colour::Green,
+        });
+        assert_eq!(symbols(&canvas, 3, 2), vec!["█▀█", "█▄█"]);
+    }
+
+    #[test]
+    fn later_layers_cover_earlier_ones() {
+        let mut canvas = Canvas::new(Container::new(Rect::new(1.0, 1.0)));
+        canvas.draw(Points {
+            coords: vec![Vec2::new(0.0, 1.0)],
+            colour: Colour::Red,
+        });
+        canvas.layer();
+        canvas.draw(Points {
+            coords: vec![Vec2::new(1.0, 0.0)],
+            colour: Colour::Blue,
+        });
+        let cell = canvas.contents_of(0, 0, &Theme::default()).unwrap();
+        assert_eq!(cell, Cell::new('⢀', Colour::Blue.normal()));
+    }
+
+    #[test]
+    fn new_bounds_draw_every_layer_again() {
+        let mut canvas = Canvas::new(Container::new(Rect::new(1.0, 1.0)));
+        canvas.draw(Points {
+            coords: vec![Vec2::new(0.0, 1.0)],
+            colour: Colour::Red,
+        });
+        canvas.layer();
+        canvas.draw(Points {
+            coords: vec![Vec2::new(1.0, 0.0)],
+            colour: Colour::Blue,
+        });
+        canvas.draw(Points {
+            coords: vec![Vec2::new(1.0, 1.0)],
+            colour: Colour::Blue,
+        });
+        let canvas = canvas.with_bounds(Vec2::ZERO, Vec2::splat(2.0));
+        let cell = canvas.contents_of(0, 0, &Theme::default()).unwrap();
+        assert_eq!(cell, Cell::new('⢠', Colour::Blue.normal()));
+    }
+
+    #[test]
+    fn lines_are_clipped_to_the_grid() {
+        let mut grid = Grid::new(2, 1, Marker::Braille);
+        grid.line((0, 0), (1_000_000_000, 3), Colour::Red);
+        grid.line((-5, 3), (-1, 3), Colour::Red);
+        assert_eq!(grid.get(0, 0), Some(('⠉', Some(Colour::Red))));
+        assert_eq!(grid.get(1, 0), Some(('⠉', Some(Colour::Red))));
+
+        let mut grid = Grid::new(2, 1, Marker::Braille);
+        let mut painter = Painter::new(&mut grid, Vec2::ZERO, Vec2::ONE);
+        painter.line(Vec2::ZERO, Vec2::new(f32::INFINITY, 0.5), Colour::Red);
+        painter.line(Vec2::ZERO, Vec2::new(f32::NAN, 0.5), Colour::Red);
+        painter.point(Vec2::new(f32::NEG_INFINITY, 0.0), Colour::Red);
+        assert_eq!(painter.dot_of(Vec2::new(0.0, f32::INFINITY)), None);
+        painter.line(Vec2::new(0.0, 1.0), Vec2::new(1e30, 1.0), Colour::Red);
+        assert_eq!(grid.get(0, 0), Some(('⠉', Some(Colour::Red))));
+        assert_eq!(grid.get(1, 0), Some(('⠉', Some(Colour::Red))));
+    }
+
+    #[test]
+    fn circle_is_closed() {
+        let mut canvas = Canvas::new(Container::new(Rect::new(4.0, 2.0)))
+            .with_bounds(Vec2::splat(-1.0), Vec2::splat(1.0));
+        canvas.draw(Circle {
+            center: Vec2::ZERO,
+            radius: 1.0,
+            colour: Colour::White,
+        });
+        let rows = symbols(&canvas, 4, 2);
+        assert!(rows.iter().all(|row| !row.contains(' ')));
+    }
+}
@@ -0,0 +1,53 @@
+use ansi_term::{ANSIString, ANSIStrings, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub style: Style,
+}
+
+impl Cell {
+    #[must_use]
+    pub const fn new(symbol: char, style: Style) -> Self {
+        Self { symbol, style }
+    }
+
+    #[must_use]
+    pub const fn blank(style: Style) -> Self {
+        Self::new(' ', style)
+    }
+
+    /// Stands in for the second column of a double width symbol and is
+    /// skipped when painting.
+    #[must_use]
+    pub const fn continuation(style: Style) -> Self {
+        Self::new('\0', style)
+    }
+
+    #[must_use]
+    pub const fn is_continuation(&self) -> bool {
+        self.symbol == '\0'
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::blank(Style::new())
+    }
+}
+
+/// Renders cells as a string, emitting escape codes only where the style changes.
+pub fn paint(cells: impl IntoIterator<Item = Cell>) -> String {
+    let mut runs: Vec<ANSIString> = Vec::new();
+    let mut run = String::new();
+    let mut run_style = Style::new();
+    for cell in cells.into_iter().filter(|cell| !cell.is_continuation()) {
+        if cell.style != run_style && !run.is_empty() {
+            runs.push(run_style.paint(std::mem::take(&mut run)));
+        }
+        run_style = cell.style;
+        run.push(cell.symbol);
+    }
+    runs.push(run_style.paint(run));
+    ANSIStrings(&runs).to_string()
+}
@@ -0,0 +1,797 @@
+use std::fmt::{self, Display};
+
+use ansi_term::{Colour, Style};
+use glam::Vec2;
+
+use super::{
+    canvas::{clip_segment, Grid, Marker, Painter},
+    cell::Cell,
+    container::Container,
+    table::Alignment,
+    theme::{Role, Theme},
+    widget::{Direction, Widget},
+};
+
+const VERTICAL_EIGHTHS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const HORIZONTAL_EIGHTHS: [char; 9] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Ink {
+    Role(Role),
+    Colour(Colour),
+}
+
+impl Ink {
+    fn style(self, theme: &Theme) -> Style {
+        match self {
+            Self::Role(role) => theme.style(role),
+            Self::Colour(colour) => Style::new().fg(colour),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Axis {
+    title: Option<String>,
+    bounds: Option<(f64, f64)>,
+    ticks: usize,
+}
+
+impl Default for Axis {
+    fn default() -> Self {
+        Self {
+            title: None,
+            bounds: None,
+            ticks: 3,
+        }
+    }
+}
+
+impl Axis {
+    #[must_use]
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Overrides the bounds that would otherwise be fitted to the data.
+    #[must_use]
+    pub fn with_bounds(self, min: f64, max: f64) -> Self {
+        Self {
+            bounds: Some((min, max)),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_ticks(self, ticks: usize) -> Self {
+        Self { ticks, ..self }
+    }
+
+    fn resolve(&self, values: impl Iterator<Item = f64>) -> (f64, f64) {
+        if let Some(bounds) = self.bounds {
+            return bounds;
+        }
+        let (min, max) = values
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        if min > max {
+            (0.0, 1.0)
+        } else if (max - min).abs() < f64::EPSILON {
+            (min - 1.0, max + 1.0)
+        } else {
+            (min, max)
+        }
+    }
+
+    fn tick_labels(&self, (min, max): (f64, f64)) -> Vec<(f64, String)> {
+        if self.ticks == 0 {
+            return Vec::new();
+        }
+        if self.ticks == 1 {
+            return vec![(min, format_tick(min, max - min))];
+        }
+        let step = (max - min) / (self.ticks - 1) as f64;
+        (0..self.ticks)
+            .map(|i| {
+                let value = (i as f64).mul_add(step, min);
+                (value, format_tick(value, step))
+            })
+            .collect()
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn format_tick(value: f64, step: f64) -> String {
+    let decimals = if step.abs() >= 1.0 || step == 0.0 {
+        0
+    } else {
+        (-step.abs().log10()).ceil() as usize
+    };
+    format!("{value:.decimals$}")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphType {
+    #[default]
+    Line,
+    Scatter,
+}
+
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    name: String,
+    points: Vec<(f64, f64)>,
+    colour: Colour,
+    graph_type: GraphType,
+}
+
+impl Dataset {
+    pub fn new(name: impl Into<String>, points: Vec<(f64, f64)>) -> Self {
+        Self {
+            name: name.into(),
+            points,
+            colour: Colour::Cyan,
+            graph_type: GraphType::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_colour(self, colour: Colour) -> Self {
+        Self { colour, ..self }
+    }
+
+    #[must_use]
+    pub fn with_graph_type(self, graph_type: GraphType) -> Self {
+        Self { graph_type, ..self }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Chart {
+    frame: Container,
+    datasets: Vec<Dataset>,
+    x_axis: Axis,
+    y_axis: Axis,
+    marker: Marker,
+    legend: bool,
+    cells: Vec<Option<(char, Ink)>>,
+}
+
+impl Chart {
+    #[must_use]
+    pub fn new(frame: Container) -> Self {
+        let mut chart = Self {
+            frame,
+            datasets: Vec::new(),
+            x_axis: Axis::default(),
+            y_axis: Axis::default(),
+            marker: Marker::default(),
+            legend: true,
+            cells: Vec::new(),
+        };
+        chart.layout();
+        chart
+    }
+
+    #[must_use]
+    pub fn with_datasets(mut self, datasets: Vec<Dataset>) -> Self {
+        self.datasets = datasets;
+        self.layout();
+        self
+    }
+
+    #[must_use]
+    pub fn with_x_axis(mut self, axis: Axis) -> Self {
+        self.x_axis = axis;
+        self.layout();
+        self
+    }
+
+    #[must_use]
+    pub fn with_y_axis(mut self, axis: Axis) -> Self {
+        self.y_axis = axis;
+        self.layout();
+        self
+    }
+
+    #[must_use]
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.marker = marker;
+        self.layout();
+        self
+    }
+
+    #[must_use]
+    pub fn with_legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self.layout();
+        self
+    }
+
+    pub fn set_datasets(&mut self, datasets: Vec<Dataset>) {
+        self.datasets = datasets;
+        self.layout();
+    }
+
+    #[must_use]
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_axis.resolve(
+            self.datasets
+                .iter()
+                .flat_map(|d| d.points.iter().map(|p| p.0)),
+        )
+    }
+
+    #[must_use]
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_axis.resolve(
+            self.datasets
+                .iter()
+                .flat_map(|d| d.points.iter().map(|p| p.1)),
+        )
+    }
+
+    fn layout(&mut self) {
+        let (width, height) = self.frame.content_size();
+        let mut canvas = Sheet::new(width, height);
+        let (x_bounds, y_bounds) = (self.x_bounds(), self.y_bounds());
+        let x_ticks = self.x_axis.tick_labels(x_bounds);
+        let y_ticks = self.y_axis.tick_labels(y_bounds);
+
+        let label_width = y_ticks
+            .iter()
+            .map(|(_, l)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        let top = usize::from(self.y_axis.title.is_some());
+        let bottom = 2 + usize::from(self.x_axis.title.is_some());
+        let plot_left = label_width + 1;
+        if height <= top + bottom || width <= plot_left + 1 {
+            self.cells = canvas.cells;
+            return;
+        }
+        let (plot_width, plot_height) = (width - plot_left, height - top - bottom);
+        let axis_row = top + plot_height;
+
+        if let Some(title) = &self.y_axis.title {
+            canvas.text(0, 0, title, Ink::Role(Role::TextMuted));
+        }
+        for row in top..axis_row {
+            canvas.set(label_width, row, '│', Ink::Role(Role::Border));
+        }
+        canvas.set(label_width, axis_row, '└', Ink::Role(Role::Border));
+        for column in plot_left..width {
+            canvas.set(column, axis_row, '─', Ink::Role(Role::Border));
+        }
+        for (value, label) in &y_ticks {
+            let row = top + scale(*value, y_bounds, plot_height, true);
+            let start = label_width - label.chars().count();
+            canvas.text(start, row, label, Ink::Role(Role::TextMuted));
+            if row < axis_row {
+                canvas.set(label_width, row, '┤', Ink::Role(Role::Border));
+            }
+        }
+        for (value, label) in &x_ticks {
+            let column = plot_left + scale(*value, x_bounds, plot_width, false);
+            let length = label.chars().count();
+            let start = column
+                .saturating_sub(length / 2)
+                .clamp(plot_left, width.saturating_sub(length).max(plot_left));
+            canvas.text(start, axis_row + 1, label, Ink::Role(Role::TextMuted));
+            canvas.set(column, axis_row, '┬', Ink::Role(Role::Border));
+        }
+        if let Some(title) = &self.x_axis.title {
+            let start = width.saturating_sub(title.chars().count());
+            canvas.text(start, axis_row + 2, title, Ink::Role(Role::TextMuted));
+        }
+
+        let plot = (plot_left, top, plot_width, plot_height);
+        self.draw_datasets(&mut canvas, plot, x_bounds, y_bounds);
+        if self.legend {
+            self.draw_legend(&mut canvas, plot);
+        }
+        self.cells = canvas.cells;
+    }
+
+    /// Rasterizes every dataset into the plot area `(left, top, width, height)`.
+    fn draw_datasets(
+        &self,
+        canvas: &mut Sheet,
+        (plot_left, top, plot_width, plot_height): (usize, usize, usize, usize),
+        x_bounds: (f64, f64),
+        y_bounds: (f64, f64),
+    ) {
+        let (min, max) = ((x_bounds.0, y_bounds.0), (x_bounds.1, y_bounds.1));
+        let to_canvas = |(x, y): (f64, f64)| Vec2::new(x as f32, y as f32);
+        let inside =
+            |&(x, y): &(f64, f64)| (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y);
+        for dataset in &self.datasets {
+            let mut grid = Grid::new(plot_width, plot_height, self.marker);
+            let mut painter = Painter::new(&mut grid, to_canvas(min), to_canvas(max));
+            let points: Vec<(f64, f64)> = dataset
+                .points
+                .iter()
+                .copied()
+                .filter(|(x, y)| x.is_finite() && y.is_finite())
+                .collect();
+            match dataset.graph_type {
+                GraphType::Line => {
+                    // Clipped here, as far away points don't fit in an f32.
+                    for pair in points.windows(2) {
+                        if let Some((from, to)) = clip_segment(pair[0], pair[1], min, max) {
+                            painter.line(to_canvas(from), to_canvas(to), dataset.colour);
+                        }
+                    }
+                    if let [point] = points.as_slice() {
+                        if inside(point) {
+                            painter.point(to_canvas(*point), dataset.colour);
+                        }
+                    }
+                }
+                GraphType::Scatter => {
+                    for point in points.iter().filter(|point| inside(point)) {
+                        painter.point(to_canvas(*point), dataset.colour);
+                    }
+                }
+            }
+            for row in 0..plot_height {
+                for column in 0..plot_width {
+                    if let Some((symbol, _)) = grid.get(column, row) {
+                        let ink = Ink::Colour(dataset.colour);
+                        canvas.set(plot_left + column, top + row, symbol, ink);
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_legend(
+        &self,
+        canvas: &mut Sheet,
+        (plot_left, top, _, plot_height): (usize, usize, usize, usize),
+    ) {
+        let width = canvas.width;
+        if !self.datasets.is_empty() {
+            let legend_width = self
+                .datasets
+                .iter()
+                .map(|d| d.name.chars().count() + 2)
+                .max()
+                .unwrap_or(0);
+            let start = width.saturating_sub(legend_width).max(plot_left);
+            for (row, dataset) in self.datasets.iter().enumerate().take(plot_height) {
+                for column in start..width {
+                    canvas.set(column, top + row, ' ', Ink::Role(Role::Text));
+                }
+                canvas.set(start, top + row, '■', Ink::Colour(dataset.colour));
+                canvas.text(start + 2, top + row, &dataset.name, Ink::Role(Role::Text));
+            }
+        }
+    }
+}
+
+/// Position of `value` along an axis `length` cells long.
+#[allow(clippy::cast_sign_loss)]
+fn scale(value: f64, (min, max): (f64, f64), length: usize, flip: bool) -> usize {
+    let relative = ((value - min) / (max - min)).clamp(0.0, 1.0);
+    let relative = if flip { 1.0 - relative } else { relative };
+    (relative * length.saturating_sub(1) as f64).round() as usize
+}
+
+struct Sheet {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<(char, Ink)>>,
+}
+
+impl Sheet {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![None; width * height],
+        }
+    }
+
+    fn set(&mut self, column: usize, row: usize, symbol: char, ink: Ink) {
+        if column < self.width && row < self.height {
+            self.cells[row * self.width + column] = Some((symbol, ink));
+        }
+    }
+
+    fn text(&mut self, column: usize, row: usize, text: &str, ink: Ink) {
+        for (i, symbol) in text.chars().enumerate() {
+            self.set(column + i, row, symbol, ink);
+        }
+    }
+}
+
+impl Widget for Chart {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let (width, _) = self.frame.content_size();
+        Some(match self.cells.get(y * width + x).copied().flatten() {
+            Some((symbol, ink)) => Cell::new(symbol, ink.style(theme)),
+            None => Cell::blank(theme.style(Role::Text)),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bar {
+    value: f64,
+    colour: Option<Colour>,
+}
+
+impl Bar {
+    #[must_use]
+    pub const fn new(value: f64) -> Self {
+        Self {
+            value,
+            colour: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_colour(self, colour: Colour) -> Self {
+        Self {
+            colour: Some(colour),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BarGroup {
+    label: String,
+    bars: Vec<Bar>,
+}
+
+impl BarGroup {
+    pub fn new(label: impl Into<String>, bars: Vec<Bar>) -> Self {
+        Self {
+            label: label.into(),
+            bars,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BarChart {
+    frame: Container,
+    groups: Vec<BarGroup>,
+    direction: Direction,
+    bar_width: usize,
+    bar_gap: usize,
+    group_gap: usize,
+    max: Option<f64>,
+}
+
+enum BarSlot<'a> {
+    Bar(&'a BarGroup, &'a Bar, usize),
+    Label(&'a BarGroup, usize),
+    Gap,
+}
+
+impl BarChart {
+    #[must_use]
+    pub const fn new(frame: Container) -> Self {
+        Self {
+            frame,
+            groups: Vec::new(),
+            direction: Direction::Vertical,
+            bar_width: 1,
+            bar_gap: 0,
+            group_gap: 1,
+            max: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_groups(self, groups: Vec<BarGroup>) -> Self {
+        Self { groups, ..self }
+    }
+
+    /// `Vertical` bars grow upwards, `Horizontal` ones grow to the right.
+    #[must_use]
+    pub fn with_direction(self, direction: Direction) -> Self {
+        Self { direction, ..self }
+    }
+
+    #[must_use]
+    pub fn with_bar_width(self, bar_width: usize) -> Self {
+        Self {
+            bar_width: bar_width.max(1),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_gaps(self, bar_gap: usize, group_gap: usize) -> Self {
+        Self {
+            bar_gap,
+            group_gap,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_max(self, max: f64) -> Self {
+        Self {
+            max: Some(max),
+            ..self
+        }
+    }
+
+    pub fn set_groups(&mut self, groups: Vec<BarGroup>) {
+        self.groups = groups;
+    }
+
+    fn max(&self) -> f64 {
+        let max = self.max.unwrap_or_else(|| {
+            self.groups
+                .iter()
+                .flat_map(|g| g.bars.iter().map(|b| b.value))
+                .fold(0.0, f64::max)
+        });
+        if max > 0.0 {
+            max
+        } else {
+            1.0
+        }
+    }
+
+    const fn group_span(&self, group: &BarGroup) -> usize {
+        let bars = group.bars.len();
+        bars * self.bar_width + bars.saturating_sub(1) * self.bar_gap
+    }
+
+    /// Finds what lies at `offset` cells along the axis the bars are laid out on.
+    fn slot_at(&self, mut offset: usize) -> BarSlot<'_> {
+        for group in &self.groups {
+            let span = self.group_span(group);
+            if offset < span {
+                let stride = self.bar_width + self.bar_gap;
+                let (bar, within) = (offset / stride, offset % stride);
+                return match group.bars.get(bar) {
+                    Some(value) if within < self.bar_width => BarSlot::Bar(group, value, offset),
+                    _ => BarSlot::Label(group, offset),
+                };
+            }
+            offset -= span;
+            if offset < self.group_gap {
+                return BarSlot::Gap;
+            }
+            offset -= self.group_gap;
+        }
+        BarSlot::Gap
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn bar_cell(&self, bar: &Bar, filled: usize, length: usize, theme: &Theme) -> Cell {
+        let total = (bar.value.max(0.0) / self.max() * (length * 8) as f64).round() as usize;
+        let eighths = total.saturating_sub(filled * 8).min(8);
+        let glyphs = match self.direction {
+            Direction::Vertical => VERTICAL_EIGHTHS,
+            Direction::Horizontal => HORIZONTAL_EIGHTHS,
+        };
+        let style = bar
+            .colour
+            .map_or_else(|| theme.style(Role::Progress), |c| Style::new().fg(c));
+        Cell::new(glyphs[eighths], style)
+    }
+
+    fn label_cell(group: &BarGroup, offset: usize, span: usize, theme: &Theme) -> Cell {
+        let start = Alignment::Center.offset(group.label.chars().count(), span);
+        let symbol = offset
+            .checked_sub(start)
+            .and_then(|i| group.label.chars().nth(i))
+            .unwrap_or(' ');
+        Cell::new(symbol, theme.style(Role::TextMuted))
+    }
+}
+
+impl Widget for BarChart {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let (width, height) = self.frame.content_size();
+        let blank = Cell::blank(theme.style(Role::Text));
+        Some(match self.direction {
+            Direction::Vertical => {
+                let bars_height = height.saturating_sub(1);
+                match self.slot_at(x) {
+                    BarSlot::Bar(group, _, offset) | BarSlot::Label(group, offset)
+                        if y == bars_height =>
+                    {
+                        Self::label_cell(group, offset, self.group_span(group), theme)
+                    }
+                    BarSlot::Bar(_, bar, _) => {
+                        self.bar_cell(bar, bars_height - 1 - y, bars_height, theme)
+                    }
+                    _ => blank,
+                }
+            }
+            Direction::Horizontal => {
+                let label_width = self
+                    .groups
+                    .iter()
+                    .map(|g| g.label.chars().count() + 1)
+                    .max()
+                    .unwrap_or(0);
+                let bars_width = width.saturating_sub(label_width);
+                match (self.slot_at(y), x.checked_sub(label_width)) {
+                    (BarSlot::Bar(group, _, 0), None) => {
+                        let symbol = group.label.chars().nth(x).unwrap_or(' ');
+                        Cell::new(symbol, theme.style(Role::TextMuted))
+                    }
+                    (BarSlot::Bar(_, bar, _), Some(column)) => {
+                        self.bar_cell(bar, column, bars_width, theme)
+                    }
+                    _ => blank,
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Sparkline {
+    data: Vec<f64>,
+    max: Option<f64>,
+}
+
+impl Sparkline {
+    #[must_use]
+    pub const fn new(data: Vec<f64>) -> Self {
+        Self { data, max: None }
+    }
+
+    #[must_use]
+    pub fn with_max(self, max: f64) -> Self {
+        Self {
+            max: Some(max),
+            ..self
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.data.push(value);
+    }
+
+    /// The most recent `width` values as a plain string, ready to be used in
+    /// a table cell or a container title.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn render(&self, width: usize) -> String {
+        let data = &self.data[self.data.len().saturating_sub(width)..];
+        let max = self
+            .max
+            .unwrap_or_else(|| data.iter().copied().fold(0.0, f64::max));
+        data.iter()
+            .map(|&value| {
+                if max <= 0.0 {
+                    return VERTICAL_EIGHTHS[1];
+                }
+                let eighths = (value.max(0.0) / max * 7.0).round() as usize + 1;
+                VERTICAL_EIGHTHS[eighths.min(8)]
+            })
+            .collect()
+    }
+}
+
+impl Display for Sparkline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(self.data.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rect::Rect;
+
+    fn rows(widget: &impl Widget, width: usize, height: usize) -> Vec<String> {
+        let theme = Theme::default();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| widget.contents_of(x, y, &theme).unwrap().symbol)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn axis_bounds() {
+        let axis = Axis::default();
+        assert_eq!(axis.resolve([3.0, -1.0, 2.0].into_iter()), (-1.0, 3.0));
+        assert_eq!(axis.resolve(std::iter::once(2.0)), (1.0, 3.0));
+        assert_eq!(axis.resolve(std::iter::empty()), (0.0, 1.0));
+        let axis = axis.with_bounds(0.0, 10.0);
+        assert_eq!(axis.resolve([3.0, 20.0].into_iter()), (0.0, 10.0));
+    }
+
+    #[test]
+    fn tick_labels() {
+        let axis = Axis::default().with_ticks(3);
+        let labels: Vec<String> = axis
+            .tick_labels((0.0, 1.0))
+            .into_iter()
+            .map(|t| t.1)
+            .collect();
+        assert_eq!(labels, vec!["0.0", "0.5", "1.0"]);
+        let labels: Vec<String> = axis
+            .tick_labels((0.0, 100.0))
+            .into_iter()
+            .map(|t| t.1)
+            .collect();
+        assert_eq!(labels, vec!["0", "50", "100"]);
+    }
+
+    #[test]
+    fn line_chart_layout() {
+        let chart = Chart::new(Container::new(Rect::new(12.0, 6.0)))
+            .with_legend(false)
+            .with_y_axis(Axis::default().with_ticks(2))
+            .with_x_axis(Axis::default().with_ticks(2))
+            .with_datasets(vec![Dataset::new("cpu", vec![(0.0, 0.0), (9.0, 9.0)])]);
+        let rows = rows(&chart, 12, 6);
+        assert_eq!(rows[0].chars().next(), Some('9'));
+        assert_eq!(rows[3].chars().next(), Some('0'));
+        assert_eq!(rows[4], " └┬────────┬");
+        assert_eq!(rows[5], "  0        9");
+        assert!(rows[0].ends_with('⠊'));
+        assert!(rows[3].contains('⡠'));
+    }
+
+    #[test]
+    fn skips_non_finite_and_clips_far_points() {
+        let chart = Chart::new(Container::new(Rect::new(12.0, 6.0)))
+            .with_legend(false)
+            .with_x_axis(Axis::default().with_ticks(0).with_bounds(0.0, 1.0))
+            .with_y_axis(Axis::default().with_ticks(0).with_bounds(0.0, 1.0))
+            .with_datasets(vec![
+                Dataset::new("inf", vec![(0.0, 0.0), (1.0, f64::INFINITY), (1.0, 1.0)]),
+                Dataset::new("far", vec![(0.0, 0.0), (1e30, 0.0)]),
+            ]);
+        let rows = rows(&chart, 12, 6);
+        assert_eq!(rows[0], "│        ⢀⠔⠊");
+        assert_eq!(rows[3], "│⣀⣀⣀⣀⣀⣀⣀⣀⣀⣀⣀");
+    }
+
+    #[test]
+    fn vertical_bar_chart() {
+        let chart = BarChart::new(Container::new(Rect::new(5.0, 3.0))).with_groups(vec![
+            BarGroup::new("a", vec![Bar::new(4.0), Bar::new(1.0)]),
+            BarGroup::new("b", vec![Bar::new(2.0)]),
+        ]);
+        assert_eq!(rows(&chart, 5, 3), vec!["█    ", "█▄ █ ", "a  b "]);
+    }
+
+    #[test]
+    fn horizontal_bar_chart() {
+        let chart = BarChart::new(Container::new(Rect::new(6.0, 3.0)))
+            .with_direction(Direction::Horizontal)
+            .with_groups(vec![
+                BarGroup::new("a", vec![Bar::new(4.0)]),
+                BarGroup::new("b", vec![Bar::new(1.0)]),
+            ]);
+        assert_eq!(rows(&chart, 6, 3), vec!["a ████", "      ", "b █   "]);
+    }
+
+    #[test]
+    fn sparkline() {
+        let sparkline = Sparkline::new(vec![0.0, 1.0, 2.0, 3.0, 7.0]);
+        assert_eq!(sparkline.to_string(), "▁▂▃▄█");
+        assert_eq!(sparkline.render(2), "▄█");
+    }
+}
@@ -0,0 +1,635 @@
+use std::{
+    fmt::{self, Debug},
+    ops::Range,
+};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::Container,
+    event::{Key, KeyEvent, MouseEvent, MouseEventKind},
+    text::{Span, Text, TextLine},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+const TAB: &str = "    ";
+const SCROLL_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    Type,
+    Function,
+    String,
+    Number,
+    Comment,
+}
+
+impl TokenKind {
+    /// The theme role the token is drawn with.
+    #[must_use]
+    pub const fn role(self) -> Role {
+        match self {
+            Self::Keyword => Role::SyntaxKeyword,
+            Self::Type => Role::SyntaxType,
+            Self::Function => Role::SyntaxFunction,
+            Self::String => Role::SyntaxString,
+            Self::Number => Role::SyntaxNumber,
+            Self::Comment => Role::SyntaxComment,
+        }
+    }
+}
+
+/// A highlighted byte range of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub range: Range<usize>,
+    pub kind: TokenKind,
+}
+
+/// Splits source code into highlighted tokens. Text outside any token is
+/// drawn plain.
+pub trait Tokenizer {
+    fn tokenize(&self, source: &str) -> Vec<Token>;
+}
+
+/// A keyword based tokenizer that is good enough for most languages.
+#[derive(Debug, Clone, Default)]
+pub struct Syntax {
+    keywords: &'static [&'static str],
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+impl Syntax {
+    #[must_use]
+    pub const fn rust() -> Self {
+        Self {
+            keywords: &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+                "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"'],
+        }
+    }
+
+    #[must_use]
+    pub const fn python() -> Self {
+        Self {
+            keywords: &[
+                "and", "as", "assert", "async", "await", "break", "class", "continue", "def",
+                "del", "elif", "else", "except", "False", "finally", "for", "from", "global", "if",
+                "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise",
+                "return", "True", "try", "while", "with", "yield",
+            ],
+            line_comment: Some("#"),
+            block_comment: None,
+            quotes: &['"', '\''],
+        }
+    }
+
+    #[must_use]
+    pub const fn shell() -> Self {
+        Self {
+            keywords: &[
+                "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function",
+                "if", "in", "local", "return", "then", "until", "while",
+            ],
+            line_comment: Some("#"),
+            block_comment: None,
+            quotes: &['"', '\''],
+        }
+    }
+
+    #[must_use]
+    pub const fn toml() -> Self {
+        Self {
+            keywords: &["true", "false"],
+            line_comment: Some("#"),
+            block_comment: None,
+            quotes: &['"', '\''],
+        }
+    }
+
+    #[must_use]
+    pub const fn json() -> Self {
+        Self {
+            keywords: &["true", "false", "null"],
+            line_comment: None,
+            block_comment: None,
+            quotes: &['"'],
+        }
+    }
+
+    /// Picks a syntax from a file extension such as `rs` or `toml`.
+    #[must_use]
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        Some(match extension {
+            "rs" => Self::rust(),
+            "py" => Self::python(),
+            "sh" | "bash" | "zsh" => Self::shell(),
+            "toml" | "ini" | "cfg" => Self::toml(),
+            "json" => Self::json(),
+            _ => return None,
+        })
+    }
+}
+
+const fn is_word(symbol: char) -> bool {
+    symbol.is_ascii_alphanumeric() || symbol == '_'
+}
+
+impl Syntax {
+    /// Length and kind of the token at the start of `rest`.
+    fn token_at(&self, rest: &str, symbol: char) -> (usize, Option<TokenKind>) {
+        if let Some(start) = self.line_comment.filter(|c| rest.starts_with(c)) {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            return (end.max(start.len()), Some(TokenKind::Comment));
+        }
+        if let Some((start, end)) = self.block_comment.filter(|(s, _)| rest.starts_with(s)) {
+            let close = rest[start.len()..]
+                .find(end)
+                .map_or(rest.len(), |at| start.len() + at + end.len());
+            return (close, Some(TokenKind::Comment));
+        }
+        if self.quotes.contains(&symbol) {
+            let mut escaped = false;
+            let close = rest.char_indices().skip(1).find(|&(_, c)| {
+                let done = c == symbol && !escaped;
+                escaped = c == '\\' && !escaped;
+                done
+            });
+            let end = close.map_or(rest.len(), |(at, c)| at + c.len_utf8());
+            return (end, Some(TokenKind::String));
+        }
+        if !is_word(symbol) {
+            return (symbol.len_utf8(), None);
+        }
+        let end = rest.find(|c| !is_word(c)).unwrap_or(rest.len());
+        let kind = if symbol.is_ascii_digit() {
+            Some(TokenKind::Number)
+        } else if self.keywords.contains(&&rest[..end]) {
+            Some(TokenKind::Keyword)
+        } else if symbol.is_uppercase() {
+            Some(TokenKind::Type)
+        } else if rest[end..].starts_with('(') {
+            Some(TokenKind::Function)
+        } else {
+            None
+        };
+        (end, kind)
+    }
+}
+
+impl Tokenizer for Syntax {
+    fn tokenize(&self, source: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while let Some(symbol) = source[i..].chars().next() {
+            let (length, kind) = self.token_at(&source[i..], symbol);
+            if let Some(kind) = kind {
+                tokens.push(Token {
+                    range: i..i + length,
+                    kind,
+                });
+            }
+            i += length;
+        }
+        tokens
+    }
+}
+
+/// Source code with a line number gutter, drawn without wrapping and
+/// scrolled sideways instead. The line under the cursor and search matches
+/// are highlighted.
+pub struct Code {
+    frame: Container,
+    tokenizer: Option<Box<dyn Tokenizer>>,
+    lines: Vec<TextLine>,
+    cursor: usize,
+    column_offset: usize,
+    query: String,
+    matches: Vec<(usize, Range<usize>)>,
+    current_match: Option<usize>,
+}
+
+impl Code {
+    #[must_use]
+    pub fn new(frame: Container) -> Self {
+        let mut code = Self {
+            frame,
+            tokenizer: None,
+            lines: Vec::new(),
+            cursor: 0,
+            column_offset: 0,
+            query: String::new(),
+            matches: Vec::new(),
+            current_match: None,
+        };
+        code.layout();
+        code
+    }
+
+    #[must_use]
+    pub fn with_tokenizer(self, tokenizer: impl Tokenizer + 'static) -> Self {
+        Self {
+            tokenizer: Some(Box::new(tokenizer)),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.set_source(source);
+        self
+    }
+
+    /// Replaces the source, highlighting it with the tokenizer if there is
+    /// one.
+    pub fn set_source(&mut self, source: &str) {
+        let tokens = self
+            .tokenizer
+            .as_ref()
+            .map(|tokenizer| tokenizer.tokenize(source))
+            .unwrap_or_default();
+        let mut kinds = vec![None; source.len()];
+        for token in tokens {
+            let end = token.range.end.min(source.len());
+            kinds[token.range.start.min(end)..end].fill(Some(token.kind));
+        }
+        let mut start = 0;
+        self.lines = source
+            .split('\n')
+            .map(|line| {
+                let mut highlighted = TextLine::new();
+                for (i, symbol) in line.trim_end_matches('\r').char_indices() {
+                    let content = match symbol {
+                        '\t' => TAB.to_string(),
+                        _ => symbol.to_string(),
+                    };
+                    highlighted.push(match kinds[start + i] {
+                        Some(kind) => Span::themed(content, kind.role()),
+                        None => Span::raw(content),
+                    });
+                }
+                start += line.len() + 1;
+                highlighted
+            })
+            .collect();
+        self.cursor = self.cursor.min(self.lines.len().saturating_sub(1));
+        self.search(&self.query.clone());
+    }
+
+    #[must_use]
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    /// The frame, for styling. Resize it with [`Self::set_area`] instead, so
+    /// that the visible columns are cut to the new width.
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    /// Moves or resizes the frame and cuts the rows to it again, keeping the
+    /// cursor in view.
+    pub fn set_area(&mut self, area: Rect) {
+        self.frame.set_area(area);
+        self.scroll_columns(0);
+        self.go_to_line(self.cursor);
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    #[must_use]
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[must_use]
+    pub const fn column_offset(&self) -> usize {
+        self.column_offset
+    }
+
+    /// Moves the cursor to a line and scrolls it into view.
+    pub fn go_to_line(&mut self, line: usize) {
+        self.cursor = line.min(self.lines.len().saturating_sub(1));
+        let (_, height) = self.frame.content_size();
+        let offset = self.frame.scroll_offset();
+        if self.cursor < offset {
+            self.frame.scroll_to(self.cursor);
+        } else if self.cursor >= offset + height {
+            self.frame.scroll_to(self.cursor + 1 - height.max(1));
+        }
+    }
+
+    pub fn scroll_columns(&mut self, columns: isize) {
+        let widest = self.lines.iter().map(TextLine::width).max().unwrap_or(0);
+        let room = self
+            .frame
+            .content_size()
+            .0
+            .saturating_sub(self.gutter_width());
+        let limit = widest.saturating_sub(room);
+        self.column_offset = self.column_offset.saturating_add_signed(columns).min(limit);
+        self.layout();
+    }
+
+    /// Highlights every occurrence of `query` and jumps to the first one at
+    /// or after the cursor. An empty query clears the search.
+    pub fn search(&mut self, query: &str) {
+        query.clone_into(&mut self.query);
+        self.matches.clear();
+        self.current_match = None;
+        if !query.is_empty() {
+            for (row, line) in self.lines.iter().enumerate() {
+                let line = line.to_string();
+                for (at, _) in line.match_indices(query) {
+                    let start = line[..at].width();
+                    self.matches.push((row, start..start + query.width()));
+                }
+            }
+        }
+        match self.matches.iter().position(|(row, _)| *row >= self.cursor) {
+            Some(index) => self.show_match(index),
+            None if !self.matches.is_empty() => self.show_match(0),
+            None => self.layout(),
+        }
+    }
+
+    #[must_use]
+    pub const fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    pub fn next_match(&mut self) {
+        if let Some(current) = self.current_match {
+            self.show_match((current + 1) % self.matches.len());
+        }
+    }
+
+    pub fn previous_match(&mut self) {
+        if let Some(current) = self.current_match {
+            self.show_match((current + self.matches.len() - 1) % self.matches.len());
+        }
+    }
+
+    fn show_match(&mut self, index: usize) {
+        let (row, columns) = self.matches[index].clone();
+        self.current_match = Some(index);
+        self.go_to_line(row);
+        let room = self
+            .frame
+            .content_size()
+            .0
+            .saturating_sub(self.gutter_width());
+        if columns.start < self.column_offset {
+            self.column_offset = columns.start;
+        } else if columns.end > self.column_offset + room {
+            self.column_offset = columns.end.saturating_sub(room);
+        }
+        self.layout();
+    }
+
+    fn gutter_width(&self) -> usize {
+        self.lines.len().max(1).to_string().len() + 3
+    }
+
+    /// Cuts the visible columns out of every line and hands them to the
+    /// frame, which takes care of the vertical scrolling.
+    fn layout(&mut self) {
+        let (width, _) = self.frame.content_size();
+        let digits = self.gutter_width() - 3;
+        let columns = self.column_offset..self.column_offset + width.saturating_sub(digits + 3);
+        let mut text = Text::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            let mut row = TextLine::new().with_span(format!("{:>digits$} │ ", i + 1));
+            for span in line.columns(columns.clone()).spans() {
+                row.push(span.clone());
+            }
+            text.push_line(row);
+        }
+        self.frame.set_content(text);
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let (_, height) = self.frame.content_size();
+        let page = height.max(1);
+        match event.key {
+            Key::Up => self.go_to_line(self.cursor.saturating_sub(1)),
+            Key::Down => self.go_to_line(self.cursor + 1),
+            Key::PageUp => self.go_to_line(self.cursor.saturating_sub(page)),
+            Key::PageDown => self.go_to_line(self.cursor + page),
+            Key::Home if event.modifiers.ctrl => self.go_to_line(0),
+            Key::End if event.modifiers.ctrl => self.go_to_line(usize::MAX),
+            Key::Home => self.scroll_columns(isize::MIN),
+            Key::Left => self.scroll_columns(-1),
+            Key::Right => self.scroll_columns(1),
+            Key::Char('n') => self.next_match(),
+            Key::Char('N') => self.previous_match(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Debug for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Code")
+            .field("frame", &self.frame)
+            .field("lines", &self.lines.len())
+            .field("cursor", &self.cursor)
+            .field("column_offset", &self.column_offset)
+            .field("query", &self.query)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for Code {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let mut cell = self.frame.contents_of(x, y, theme)?;
+        let Some((column, row)) = self.frame.local_position(x, y) else {
+            return Some(cell);
+        };
+        let line = row + self.frame.scroll_offset();
+        let gutter = self.gutter_width();
+        if line >= self.lines.len() {
+            return Some(cell);
+        }
+        if column < gutter {
+            let role = if line == self.cursor {
+                Role::Text
+            } else {
+                Role::TextMuted
+            };
+            return Some(Cell::new(cell.symbol, theme.style(role)));
+        }
+        let column = column - gutter + self.column_offset;
+        let found = self
+            .matches
+            .iter()
+            .position(|(r, columns)| *r == line && columns.contains(&column));
+        if let Some(index) = found {
+            cell.style = if Some(index) == self.current_match {
+                theme.style(Role::Selection)
+            } else {
+                theme.style(Role::Warning).reverse()
+            };
+        } else if line == self.cursor {
+            cell.style.background = theme.style(Role::Selection).background;
+        }
+        Some(cell)
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let Some((_, row)) = self.frame.local_position(event.x, event.y) else {
+            return false;
+        };
+        match event.kind {
+            MouseEventKind::Down(_) => self.go_to_line(row + self.frame.scroll_offset()),
+            MouseEventKind::ScrollUp => {
+                let offset = self.frame.scroll_offset();
+                self.frame.scroll_to(offset.saturating_sub(SCROLL_LINES));
+            }
+            MouseEventKind::ScrollDown => {
+                let offset = self.frame.scroll_offset();
+                self.frame.scroll_to(offset + SCROLL_LINES);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "// demo\nfn main() {\n    let s = \"hi\"; // x\n    Vec::new(42);\n}";
+
+    fn rows(code: &Code, width: usize, height: usize) -> Vec<String> {
+        let theme = Theme::default();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| code.contents_of(x, y, &theme).unwrap().symbol)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tokenizes_rust() {
+        let kinds: Vec<_> = Syntax::rust()
+            .tokenize(SOURCE)
+            .into_iter()
+            .map(|token| (&SOURCE[token.range], token.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("// demo", TokenKind::Comment),
+                ("fn", TokenKind::Keyword),
+                ("main", TokenKind::Function),
+                ("let", TokenKind::Keyword),
+                ("\"hi\"", TokenKind::String),
+                ("// x", TokenKind::Comment),
+                ("Vec", TokenKind::Type),
+                ("new", TokenKind::Function),
+                ("42", TokenKind::Number),
+            ]
+        );
+    }
+
+    #[test]
+    fn gutter_and_horizontal_scroll() {
+        let mut code = Code::new(Container::new(Rect::new(12.0, 3.0)))
+            .with_tokenizer(Syntax::rust())
+            .with_source(SOURCE);
+        assert_eq!(
+            rows(&code, 12, 3),
+            vec!["1 │ // demo", "2 │ fn main(", "3 │     let"]
+        );
+        code.handle_key(Key::Right.into());
+        code.handle_key(Key::Right.into());
+        code.handle_key(Key::Down.into());
+        code.handle_key(Key::Down.into());
+        code.handle_key(Key::Down.into());
+        assert_eq!(
+            rows(&code, 12, 3),
+            vec!["2 │  main()", "3 │   let s", "4 │   Vec::n"]
+        );
+
+        let theme = Theme::default();
+        let keyword = code.contents_of(6, 1, &theme).unwrap();
+        assert_eq!(keyword.style, theme.style(Role::SyntaxKeyword));
+        let light = Theme::light();
+        let keyword = code.contents_of(6, 1, &light).unwrap();
+        assert_eq!(keyword.style, light.style(Role::SyntaxKeyword));
+        let current = code.contents_of(6, 2, &theme).unwrap();
+        assert_eq!(
+            current.style.foreground,
+            theme.style(Role::SyntaxType).foreground
+        );
+        assert_eq!(
+            current.style.background,
+            theme.style(Role::Selection).background
+        );
+    }
+
+    #[test]
+    fn resizing_cuts_the_rows_again() {
+        let mut code = Code::new(Container::new(Rect::new(12.0, 3.0))).with_source(SOURCE);
+        code.go_to_line(3);
+        code.set_area(Rect::new(16.0, 2.0));
+        assert_eq!(
+            rows(&code, 16, 2),
+            vec!["3 │     let s =", "4 │     Vec::new"]
+        );
+    }
+
+    #[test]
+    fn search_highlights_and_jumps() {
+        let source = (1..=30)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut code = Code::new(Container::new(Rect::new(20.0, 4.0))).with_source(&source);
+        code.search("line 2");
+        assert_eq!((code.match_count(), code.cursor()), (11, 1));
+        code.previous_match();
+        assert_eq!(code.cursor(), 28);
+        assert_eq!(code.frame().scroll_offset(), 25);
+
+        let theme = Theme::default();
+        let current = code.contents_of(5, 3, &theme).unwrap();
+        assert_eq!(current, Cell::new('l', theme.style(Role::Selection)));
+        let other = code.contents_of(5, 2, &theme).unwrap();
+        assert_eq!(other.style, theme.style(Role::Warning).reverse());
+        let gutter = code.contents_of(0, 2, &theme).unwrap();
+        assert_eq!(gutter, Cell::new('2', theme.style(Role::TextMuted)));
+    }
+}
@@ -1,227 +1,281 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
 
 use crate::{
     common::{Margin, Padding},
     rect::Rect,
 };
 
+use super::{
+    cell::Cell,
+    text::{Text, TextLine},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
 #[derive(Debug, Clone)]
 pub struct Container {
     domain: Rect,
     margin: Margin,
     padding: Padding,
     border: TuiBorder,
+    title: Option<String>,
+    focused: bool,
 
-    content: String,
+    source: Text,
+    content: Text,
     lines_scrolled: usize,
 }
 
 impl Container {
+    #[must_use]
     pub fn new(rect: Rect) -> Self {
         Self {
             domain: rect,
             margin: Margin::default(),
             padding: Padding::default(),
             border: TuiBorder::default(),
+            title: None,
+            focused: false,
 
-            content: String::new(),
+            source: Text::new(),
+            content: Text::new(),
             lines_scrolled: 0,
         }
     }
 
-    pub fn with_margin(mut self, margin: Margin) -> Self {
+    #[must_use]
+    pub fn with_margin(self, margin: Margin) -> Self {
         Self { margin, ..self }
     }
 
-    pub fn with_padding(mut self, padding: Padding) -> Self {
+    #[must_use]
+    pub fn with_padding(self, padding: Padding) -> Self {
         Self { padding, ..self }
     }
 
-    pub fn with_border(mut self, border: TuiBorder) -> Self {
+    #[must_use]
+    pub fn with_border(self, border: TuiBorder) -> Self {
         Self { border, ..self }
     }
 
-    fn what_side_is(&self, x: usize, y: usize, offset_x: usize, offset_y: usize) -> Side {
-        let (pos, width, height) = self.domain.pos_width_height();
-        let (width, height) = (
-            width as i32 - offset_x as i32,
-            height as i32 - offset_y as i32,
-        );
-        let (x, y) = (x as i32 - pos.x as i32, y as i32 - pos.y as i32);
-        let (middle_x, middle_y) = (width as i32 / 2, height as i32 / 2);
-        if x < middle_x {
-            if y < middle_y {
-                if x == y {
-                    return Side::TopLeftCorner;
-                } else if x > y {
-                    return Side::Top;
-                } else {
-                    return Side::Left;
-                }
-            } else {
-                if height - x == y {
-                    return Side::BottomLeftCorner;
-                } else if height - x > y {
-                    return Side::Left;
-                } else {
-                    return Side::Bottom;
-                }
-            }
-        } else {
-            if y < middle_y {
-                if width - y == x {
-                    return Side::TopRightCorner;
-                } else if width - y > x {
-                    return Side::Top;
-                } else {
-                    return Side::Right;
-                }
-            } else {
-                if height + x - width == y {
-                    return Side::BottomRightCorner;
-                } else if height + x - width > y {
-                    return Side::Right;
-                } else {
-                    return Side::Bottom;
-                }
-            }
+    #[must_use]
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
         }
     }
 
-    fn draw_margin(&self, x: usize, y: usize) -> char {
-        ' '
-    }
-    fn draw_padding(&self, x: usize, y: usize) -> char {
-        ' '
-    }
-    fn draw_border(&self, x: usize, y: usize) -> char {
-        let side = self.what_side_is(
-            x - self.margin.left as usize,
-            y - self.margin.top as usize,
-            self.margin.right as usize + self.margin.left as usize,
-            self.margin.bottom as usize + self.margin.top as usize,
-        );
-        match self.border {
-            TuiBorder::None => ' ',
-            TuiBorder::SmoothCorner => match side {
-                Side::TopLeftCorner => '╭',
-                Side::Top | Side::Bottom => '─',
-                Side::TopRightCorner => '╮',
-                Side::Right | Side::Left => '│',
-                Side::BottomRightCorner => '╯',
-                Side::BottomLeftCorner => '╰',
-            },
-        }
+    pub const fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
     }
 
-    pub fn set_content(&mut self, mut content: String) {
-        let (_pos, width, height) = self.domain.pos_width_height();
-        let width = (width
-            - (self.margin.left
-                + self.margin.right
-                + (self.border.size() as f64) * 2.0
-                + self.padding.left
-                + self.padding.right)) as usize;
-        let height = (height
-            - (self.margin.top
-                + self.margin.bottom
-                + (self.border.size() as f64) * 2.0
-                + self.padding.top
-                + self.padding.bottom)) as usize;
-
-        textwrap::fill_inplace(&mut content, width);
-        self.content = content;
-    }
-
-    fn draw_contents(&self, x: usize, y: usize) -> char {
-        let (pos, ..) = self.domain.pos_width_height();
-        let x = x
-            - (self.margin.left + self.border.size() as f64 + self.padding.left + pos.x as f64)
-                as usize;
-        let y = y
-            - (self.margin.top + self.border.size() as f64 + self.padding.top + pos.y as f64)
-                as usize
-            + self.lines_scrolled;
-        if let Some(line) = self.content.lines().nth(y) {
-            return line.chars().nth(x).unwrap_or(' ');
-        }
-        ' '
+    #[must_use]
+    pub const fn is_focused(&self) -> bool {
+        self.focused
     }
 
-    pub fn contents_of(&self, x: usize, y: usize) -> Option<char> {
-        let (pos, width, height) = self.domain.pos_width_height();
-        let (offset_x, offset_y, width, height) = (
-            pos.x as usize,
-            pos.y as usize,
-            width as usize,
-            height as usize,
-        );
-        let (max_x, max_y) = (offset_x + width, offset_y + height);
+    #[must_use]
+    pub const fn border(&self) -> TuiBorder {
+        self.border
+    }
 
-        if x <= offset_x || x >= max_x || y <= offset_y || y >= max_y {
+    /// Whether the position is inside the border box, i.e. not in the
+    /// margin.
+    #[must_use]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        matches!(self.area_of(x, y), Some(area) if !matches!(area, Area::Margin))
+    }
+
+    fn area_of(&self, x: usize, y: usize) -> Option<Area> {
+        let outer = Bounds::of(&self.domain);
+        if !outer.contains(x, y) {
             return None;
         }
-        let (offset_margin_left, offset_margin_top, offset_margin_right, offset_margin_bottom) = (
-            offset_x + self.margin.left as usize,
-            offset_y + self.margin.top as usize,
-            max_x - self.margin.right as usize,
-            max_y - self.margin.bottom as usize,
-        );
-        if x <= offset_margin_left
-            || x >= offset_margin_right
-            || y <= offset_margin_top
-            || y >= offset_margin_bottom
-        {
-            return Some(self.draw_margin(x, y));
+        let border_box = outer.shrink(&self.margin_insets());
+        if !border_box.contains(x, y) {
+            return Some(Area::Margin);
+        }
+        let size = self.border.size();
+        let padding_box = border_box.shrink(&[size; 4]);
+        if !padding_box.contains(x, y) {
+            return Some(Area::Border(border_box.side_of(x, y)));
         }
-        let border_size = self.border.size();
-        let (offset_border_left, offset_border_top, offset_border_right, offset_border_bottom) = (
-            offset_margin_left + border_size,
-            offset_margin_top + border_size,
-            offset_margin_right - border_size,
-            offset_margin_bottom - border_size,
-        );
-        if x <= offset_border_left
-            || x >= offset_border_right
-            || y <= offset_border_top
-            || y >= offset_border_bottom
-        {
-            return Some(self.draw_border(x, y));
+        let content_box = padding_box.shrink(&self.padding_insets());
+        if !content_box.contains(x, y) {
+            return Some(Area::Padding);
+        }
+        Some(Area::Content(x - content_box.left, y - content_box.top))
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    const fn margin_insets(&self) -> [usize; 4] {
+        let Margin {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.margin;
+        [top as usize, right as usize, bottom as usize, left as usize]
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    const fn padding_insets(&self) -> [usize; 4] {
+        let Padding {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.padding;
+        [top as usize, right as usize, bottom as usize, left as usize]
+    }
+
+    #[must_use]
+    pub fn content_size(&self) -> (usize, usize) {
+        let bounds = self.content_bounds();
+        (bounds.width(), bounds.height())
+    }
+
+    /// Screen position of the top left corner of the content area.
+    #[must_use]
+    pub fn content_origin(&self) -> (usize, usize) {
+        let bounds = self.content_bounds();
+        (bounds.left, bounds.top)
+    }
+
+    #[must_use]
+    pub fn local_position(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        match self.area_of(x, y)? {
+            Area::Content(x, y) => Some((x, y)),
+            _ => None,
+        }
+    }
+
+    fn content_bounds(&self) -> Bounds {
+        let size = self.border.size();
+        Bounds::of(&self.domain)
+            .shrink(&self.margin_insets())
+            .shrink(&[size; 4])
+            .shrink(&self.padding_insets())
+    }
+
+    fn draw_border(&self, side: Side, x: usize, theme: &Theme) -> Cell {
+        let role = if self.focused {
+            Role::BorderFocused
+        } else {
+            Role::Border
         };
-        let (offset_padding_left, offset_padding_top, offset_padding_right, offset_padding_bottom) = (
-            offset_border_left + self.padding.left as usize,
-            offset_border_top + self.padding.top as usize,
-            offset_border_right - self.padding.right as usize,
-            offset_border_bottom - self.padding.bottom as usize,
-        );
-        if x <= offset_padding_left
-            || x >= offset_padding_right
-            || y <= offset_padding_top
-            || y >= offset_padding_bottom
-        {
-            return Some(self.draw_padding(x, y));
+        if let (Side::Top, Some(title)) = (side, &self.title) {
+            let border_box = Bounds::of(&self.domain).shrink(&self.margin_insets());
+            let offset = x - border_box.left;
+            let room = border_box.width().saturating_sub(4);
+            if let Some(symbol) = offset
+                .checked_sub(2)
+                .filter(|&i| i < room)
+                .and_then(|i| title.chars().nth(i))
+            {
+                return Cell::new(symbol, theme.style(Role::Title));
+            }
+        }
+        Cell::new(self.border.symbol(side), theme.style(role))
+    }
+
+    /// Sets plain or styled content, wrapped to the width of the content area.
+    pub fn set_content(&mut self, content: impl Into<Text>) {
+        self.source = content.into();
+        self.rewrap();
+    }
+
+    /// Adds lines after the content, wrapping only the new ones.
+    pub fn extend_content(&mut self, lines: impl IntoIterator<Item = TextLine>) {
+        let added = lines.into_iter().fold(Text::new(), Text::with_line);
+        let wrapped = added.wrapped(self.content_bounds().width());
+        for line in wrapped.lines() {
+            self.content.push_line(line.clone());
         }
-        return Some(self.draw_contents(x, y));
+        for line in added.lines() {
+            self.source.push_line(line.clone());
+        }
+    }
+
+    fn rewrap(&mut self) {
+        self.content = self.source.wrapped(self.content_bounds().width());
+        self.scroll_to(self.lines_scrolled);
+    }
+
+    #[must_use]
+    pub const fn area(&self) -> &Rect {
+        &self.domain
+    }
+
+    /// Moves or resizes the container, wrapping the content again.
+    pub fn set_area(&mut self, area: Rect) {
+        self.domain = area;
+        self.rewrap();
+    }
+
+    /// Number of content lines after wrapping.
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.content.lines().len()
+    }
+
+    #[must_use]
+    pub const fn scroll_offset(&self) -> usize {
+        self.lines_scrolled
+    }
+
+    /// Scrolls so that `line` is at the top, stopping once the last line is
+    /// at the bottom.
+    pub fn scroll_to(&mut self, line: usize) {
+        let height = self.content_bounds().height();
+        self.lines_scrolled = line.min(self.line_count().saturating_sub(height));
+    }
+
+    pub fn scroll_by(&mut self, lines: isize) {
+        self.scroll_to(self.lines_scrolled.saturating_add_signed(lines));
+    }
+
+    fn draw_contents(&self, x: usize, y: usize, theme: &Theme) -> Cell {
+        let (symbol, style) = self
+            .content
+            .lines()
+            .get(y + self.lines_scrolled)
+            .and_then(|line| line.cell_at(x, theme))
+            .unwrap_or((' ', None));
+        Cell::new(symbol, style.unwrap_or_else(|| theme.style(Role::Text)))
+    }
+
+    #[must_use]
+    pub fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        Some(match self.area_of(x, y)? {
+            Area::Margin => Cell::default(),
+            Area::Border(side) => self.draw_border(side, x, theme),
+            Area::Padding => Cell::blank(theme.style(Role::Text)),
+            Area::Content(x, y) => self.draw_contents(x, y, theme),
+        })
+    }
+}
+
+impl Widget for Container {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        self.contents_of(x, y, theme)
     }
 }
 
 impl Display for Container {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (pos, width, height) = self.domain.pos_width_height();
-
-        let capacity =
-            (f64::from(pos.x) + width + 1.0) as usize * (f64::from(pos.y) + height) as usize;
-        let mut buffer = String::with_capacity(capacity);
-        for y in 0..(f64::from(pos.y) + height) as usize {
-            if y < pos.y as usize {
-                buffer.push(' ');
-            } else {
-                for x in 0..(f64::from(pos.x) + width) as usize {
-                    if x < pos.x as usize {
-                        buffer.push(' ');
-                    } else {
-                        buffer.push(self.contents_of(x, y).unwrap_or(' '));
-                    }
+        let theme = Theme::default();
+        let Bounds { right, bottom, .. } = Bounds::of(&self.domain);
+        let mut buffer = String::with_capacity((right + 1) * bottom);
+        for y in 0..bottom {
+            for x in 0..right {
+                let cell = self.contents_of(x, y, &theme).unwrap_or_default();
+                if !cell.is_continuation() {
+                    buffer.push(cell.symbol);
                 }
             }
             buffer.push('\n');
@@ -230,6 +284,72 @@ impl Display for Container {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Area {
+    Margin,
+    Border(Side),
+    Padding,
+    Content(usize, usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+}
+
+impl Bounds {
+    #[allow(clippy::cast_sign_loss)]
+    const fn of(rect: &Rect) -> Self {
+        let (pos, width, height) = rect.pos_width_height();
+        let (left, top) = (pos.x.max(0.0) as usize, pos.y.max(0.0) as usize);
+        Self {
+            left,
+            top,
+            right: left + width as usize,
+            bottom: top + height as usize,
+        }
+    }
+
+    const fn width(&self) -> usize {
+        self.right.saturating_sub(self.left)
+    }
+
+    const fn height(&self) -> usize {
+        self.bottom.saturating_sub(self.top)
+    }
+
+    const fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+
+    fn shrink(&self, &[top, right, bottom, left]: &[usize; 4]) -> Self {
+        let (left, top) = (self.left + left, self.top + top);
+        Self {
+            left,
+            top,
+            right: self.right.saturating_sub(right).max(left),
+            bottom: self.bottom.saturating_sub(bottom).max(top),
+        }
+    }
+
+    const fn side_of(&self, x: usize, y: usize) -> Side {
+        let (left, right) = (x == self.left, x + 1 == self.right);
+        let (top, bottom) = (y == self.top, y + 1 == self.bottom);
+        match (top, right, bottom, left) {
+            (true, _, _, true) => Side::TopLeftCorner,
+            (true, true, _, _) => Side::TopRightCorner,
+            (_, _, true, true) => Side::BottomLeftCorner,
+            (_, true, true, _) => Side::BottomRightCorner,
+            (true, ..) => Side::Top,
+            (_, _, true, _) => Side::Bottom,
+            (_, _, _, true) => Side::Left,
+            _ => Side::Right,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 enum Side {
@@ -243,23 +363,188 @@ enum Side {
     Left,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TuiBorder {
+    #[default]
     None,
     SmoothCorner,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderSymbols {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+    pub tee_down: char,
+    pub tee_up: char,
+    pub tee_right: char,
+    pub tee_left: char,
+    pub cross: char,
+}
+
+/// Written in kebab case, e.g. `smooth-corner`.
+impl Display for TuiBorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::SmoothCorner => "smooth-corner",
+        })
+    }
+}
+
+impl FromStr for TuiBorder {
+    type Err = InvalidBorder;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "none" => Ok(Self::None),
+            "smooth-corner" => Ok(Self::SmoothCorner),
+            _ => Err(InvalidBorder(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidBorder(pub String);
+
+impl Display for InvalidBorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid border `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidBorder {}
+
 impl TuiBorder {
-    pub const fn size(&self) -> usize {
+    #[must_use]
+    pub const fn size(self) -> usize {
         match self {
-            TuiBorder::None => 0,
-            TuiBorder::SmoothCorner => 1,
+            Self::None => 0,
+            Self::SmoothCorner => 1,
+        }
+    }
+
+    #[must_use]
+    pub const fn symbols(self) -> BorderSymbols {
+        match self {
+            Self::None => BorderSymbols {
+                top_left: ' ',
+                top_right: ' ',
+                bottom_left: ' ',
+                bottom_right: ' ',
+                horizontal: ' ',
+                vertical: ' ',
+                tee_down: ' ',
+                tee_up: ' ',
+                tee_right: ' ',
+                tee_left: ' ',
+                cross: ' ',
+            },
+            Self::SmoothCorner => BorderSymbols {
+                top_left: '╭',
+                top_right: '╮',
+                bottom_left: '╰',
+                bottom_right: '╯',
+                horizontal: '─',
+                vertical: '│',
+                tee_down: '┬',
+                tee_up: '┴',
+                tee_right: '├',
+                tee_left: '┤',
+                cross: '┼',
+            },
+        }
+    }
+
+    const fn symbol(self, side: Side) -> char {
+        let symbols = self.symbols();
+        match side {
+            Side::TopLeftCorner => symbols.top_left,
+            Side::Top | Side::Bottom => symbols.horizontal,
+            Side::TopRightCorner => symbols.top_right,
+            Side::Right | Side::Left => symbols.vertical,
+            Side::BottomRightCorner => symbols.bottom_right,
+            Side::BottomLeftCorner => symbols.bottom_left,
         }
     }
 }
 
-impl Default for TuiBorder {
-    fn default() -> Self {
-        Self::None
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
+
+    use super::*;
+
+    #[test]
+    fn box_model() {
+        let mut container = Container::new(Rect::new(12.0, 5.0))
+            .with_border(TuiBorder::SmoothCorner)
+            .with_padding(Padding::sides(1.0, 1.0))
+            .with_title("Logs");
+        container.set_content("Some more content.".to_string());
+        let expected = "\
+╭─Logs─────╮
+│ Some     │
+│ more     │
+│ content. │
+╰──────────╯
+";
+        assert_eq!(container.to_string(), expected);
+    }
+
+    #[test]
+    fn content_starts_in_the_first_row_and_column() {
+        let mut container = Container::new(Rect::new(4.0, 2.0));
+        container.set_content("ab cd".to_string());
+        assert_eq!(container.to_string(), "ab  \ncd  \n");
+    }
+
+    #[test]
+    fn positioned_containers_keep_their_offset() {
+        let container = Container::new(Rect::new(6.0, 3.0).with_position(Vec2::new(2.0, 1.0)))
+            .with_border(TuiBorder::SmoothCorner);
+        let expected = "        \n  ╭────╮\n  │    │\n  ╰────╯\n";
+        assert_eq!(container.to_string(), expected);
+    }
+
+    #[test]
+    fn border_roles() {
+        let theme = Theme::default();
+        let mut container = Container::new(Rect::new(6.0, 3.0))
+            .with_border(TuiBorder::SmoothCorner)
+            .with_title("T");
+        let border = container.contents_of(0, 0, &theme).unwrap();
+        assert_eq!(border.style, theme.style(Role::Border));
+        let title = container.contents_of(2, 0, &theme).unwrap();
+        assert_eq!(title, Cell::new('T', theme.style(Role::Title)));
+        container.set_focused(true);
+        let border = container.contents_of(5, 2, &theme).unwrap();
+        assert_eq!(border, Cell::new('╯', theme.style(Role::BorderFocused)));
+        assert!(container.contents_of(6, 0, &theme).is_none());
+    }
+
+    #[test]
+    fn styled_content() {
+        let theme = Theme::default();
+        let mut container = Container::new(Rect::new(8.0, 3.0));
+        let markup = "[bold]warning[/] disk full";
+        container.set_content(Text::parse_markup(markup).unwrap());
+        assert_eq!(container.to_string(), "warning \ndisk    \nfull    \n");
+        let bold = container.contents_of(6, 0, &theme).unwrap();
+        assert_eq!(bold, Cell::new('g', ansi_term::Style::new().bold()));
+        let plain = container.contents_of(0, 1, &theme).unwrap();
+        assert_eq!(plain, Cell::new('d', theme.style(Role::Text)));
+    }
+
+    #[test]
+    fn resizing_rewraps_content() {
+        let mut container = Container::new(Rect::new(8.0, 3.0));
+        container.set_content("warning disk full");
+        assert_eq!(container.line_count(), 3);
+        container.set_area(Rect::new(20.0, 1.0));
+        assert_eq!(container.to_string(), "warning disk full   \n");
     }
 }
@@ -0,0 +1,548 @@
+use std::fmt::{self, Debug};
+
+use ansi_term::Style;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    table::Alignment,
+    theme::{Role, Theme},
+    widget::{local_position, Widget},
+};
+
+/// The area and interaction state every control holds, read and changed
+/// through [`Control`].
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlState {
+    area: Rect,
+    focused: bool,
+    hovered: bool,
+    pressed: bool,
+    disabled: bool,
+}
+
+impl ControlState {
+    const fn new(area: Rect) -> Self {
+        Self {
+            area,
+            focused: false,
+            hovered: false,
+            pressed: false,
+            disabled: false,
+        }
+    }
+
+    fn style(&self, theme: &Theme) -> Style {
+        if self.disabled {
+            theme.style(Role::TextMuted)
+        } else if self.pressed {
+            theme.style(Role::Selection).reverse()
+        } else if self.focused {
+            theme.style(Role::Selection)
+        } else if self.hovered {
+            theme.style(Role::Text).underline()
+        } else {
+            theme.style(Role::Text)
+        }
+    }
+
+    /// Keys only reach enabled, focused controls.
+    const fn activated_by(&self, event: KeyEvent) -> bool {
+        self.focused && !self.disabled && matches!(event.key, Key::Enter | Key::Char(' '))
+    }
+
+    /// Updates hover and press state and returns where a click ended, i.e.
+    /// where the left button was released after being pressed on the area.
+    fn track(&mut self, event: MouseEvent) -> Option<(usize, usize)> {
+        let inside = local_position(&self.area, event.x, event.y);
+        self.hovered = inside.is_some();
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.pressed = inside.is_some() && !self.disabled;
+                None
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                let clicked = self.pressed;
+                self.pressed = false;
+                inside.filter(|_| clicked)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Accessors shared by [`Button`], [`Checkbox`] and [`RadioGroup`].
+pub trait Control {
+    fn state(&self) -> &ControlState;
+
+    fn state_mut(&mut self) -> &mut ControlState;
+
+    #[must_use]
+    fn with_enabled(mut self, enabled: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_enabled(enabled);
+        self
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.state_mut().disabled = !enabled;
+    }
+
+    #[must_use]
+    fn is_enabled(&self) -> bool {
+        !self.state().disabled
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.state_mut().focused = focused;
+    }
+
+    #[must_use]
+    fn is_focused(&self) -> bool {
+        self.state().focused
+    }
+
+    #[must_use]
+    fn is_hovered(&self) -> bool {
+        self.state().hovered
+    }
+
+    #[must_use]
+    fn is_pressed(&self) -> bool {
+        self.state().pressed
+    }
+
+    #[must_use]
+    fn area(&self) -> &Rect {
+        &self.state().area
+    }
+}
+
+fn text_cell(text: &str, column: usize, style: Style) -> Cell {
+    Cell::new(text.chars().nth(column).unwrap_or(' '), style)
+}
+
+pub struct Button {
+    label: String,
+    state: ControlState,
+    on_press: Option<Box<dyn FnMut()>>,
+}
+
+impl Button {
+    pub fn new(area: Rect, label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            state: ControlState::new(area),
+            on_press: None,
+        }
+    }
+
+    #[must_use]
+    pub fn on_press(self, on_press: impl FnMut() + 'static) -> Self {
+        Self {
+            on_press: Some(Box::new(on_press)),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn press(&mut self) {
+        if let (false, Some(on_press)) = (self.state.disabled, &mut self.on_press) {
+            on_press();
+        }
+    }
+}
+
+impl Control for Button {
+    fn state(&self) -> &ControlState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut ControlState {
+        &mut self.state
+    }
+}
+
+impl Widget for Button {
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, row) = local_position(&self.state.area, x, y)?;
+        let (_, width, height) = self.state.area.pos_width_height();
+        let text = format!("[ {} ]", self.label);
+        let start = Alignment::Center.offset(text.chars().count(), width as usize);
+        let style = self.state.style(theme);
+        if row != height as usize / 2 {
+            return Some(Cell::blank(style));
+        }
+        let symbol = column
+            .checked_sub(start)
+            .and_then(|i| text.chars().nth(i))
+            .unwrap_or(' ');
+        Some(Cell::new(symbol, style))
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let activated = self.state.activated_by(event);
+        if activated {
+            self.press();
+        }
+        activated
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if self.state.track(event).is_some() {
+            self.press();
+        }
+        self.state.hovered
+    }
+}
+
+impl Debug for Button {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Button")
+            .field("label", &self.label)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A checkbox, or a toggle switch when built with [`Checkbox::toggle`].
+pub struct Checkbox {
+    label: String,
+    checked: bool,
+    switch: bool,
+    state: ControlState,
+    on_change: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Checkbox {
+    pub fn new(area: Rect, label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            checked: false,
+            switch: false,
+            state: ControlState::new(area),
+            on_change: None,
+        }
+    }
+
+    pub fn toggle(area: Rect, label: impl Into<String>) -> Self {
+        Self {
+            switch: true,
+            ..Self::new(area, label)
+        }
+    }
+
+    #[must_use]
+    pub fn with_checked(self, checked: bool) -> Self {
+        Self { checked, ..self }
+    }
+
+    #[must_use]
+    pub fn on_change(self, on_change: impl FnMut(bool) + 'static) -> Self {
+        Self {
+            on_change: Some(Box::new(on_change)),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Changes the value without notifying the change callback.
+    pub const fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    pub fn flip(&mut self) {
+        if self.state.disabled {
+            return;
+        }
+        self.checked = !self.checked;
+        if let Some(on_change) = &mut self.on_change {
+            on_change(self.checked);
+        }
+    }
+
+    fn text(&self) -> String {
+        let mark = match (self.switch, self.checked) {
+            (false, true) => "[x]",
+            (false, false) => "[ ]",
+            (true, true) => "━━●",
+            (true, false) => "●━━",
+        };
+        format!("{mark} {}", self.label)
+    }
+}
+
+impl Control for Checkbox {
+    fn state(&self) -> &ControlState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut ControlState {
+        &mut self.state
+    }
+}
+
+impl Widget for Checkbox {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, row) = local_position(&self.state.area, x, y)?;
+        let style = self.state.style(theme);
+        Some(match row {
+            0 => text_cell(&self.text(), column, style),
+            _ => Cell::blank(style),
+        })
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let activated = self.state.activated_by(event);
+        if activated {
+            self.flip();
+        }
+        activated
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if self.state.track(event).is_some() {
+            self.flip();
+        }
+        self.state.hovered
+    }
+}
+
+impl Debug for Checkbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checkbox")
+            .field("label", &self.label)
+            .field("checked", &self.checked)
+            .field("switch", &self.switch)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+/// One option per row, exactly one of which is selected.
+pub struct RadioGroup {
+    options: Vec<String>,
+    selected: usize,
+    cursor: usize,
+    state: ControlState,
+    on_change: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl RadioGroup {
+    pub fn new<T: Into<String>>(area: Rect, options: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: 0,
+            cursor: 0,
+            state: ControlState::new(area),
+            on_change: None,
+        }
+    }
+
+    #[must_use]
+    pub fn on_change(self, on_change: impl FnMut(usize) + 'static) -> Self {
+        Self {
+            on_change: Some(Box::new(on_change)),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_option(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if self.state.disabled || index >= self.options.len() {
+            return;
+        }
+        self.cursor = index;
+        if index != self.selected {
+            self.selected = index;
+            if let Some(on_change) = &mut self.on_change {
+                on_change(index);
+            }
+        }
+    }
+}
+
+impl Control for RadioGroup {
+    fn state(&self) -> &ControlState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut ControlState {
+        &mut self.state
+    }
+}
+
+impl Widget for RadioGroup {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, row) = local_position(&self.state.area, x, y)?;
+        let Some(option) = self.options.get(row) else {
+            return Some(Cell::blank(theme.style(Role::Text)));
+        };
+        let state = ControlState {
+            focused: self.state.focused && row == self.cursor,
+            hovered: self.state.hovered && row == self.cursor,
+            ..self.state.clone()
+        };
+        let mark = if row == self.selected { "(•)" } else { "( )" };
+        Some(text_cell(
+            &format!("{mark} {option}"),
+            column,
+            state.style(theme),
+        ))
+    }
+
+    /// Up and down move the selection, like most native radio groups.
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        if !self.state.focused || self.state.disabled {
+            return false;
+        }
+        match event.key {
+            Key::Up => self.select(self.cursor.saturating_sub(1)),
+            Key::Down => self.select(self.cursor + 1),
+            Key::Enter | Key::Char(' ') => self.select(self.cursor),
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if let Some((_, row)) = local_position(&self.state.area, event.x, event.y) {
+            self.cursor = row.min(self.options.len().saturating_sub(1));
+        }
+        if let Some((_, row)) = self.state.track(event) {
+            self.select(row);
+        }
+        self.state.hovered
+    }
+}
+
+impl Debug for RadioGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RadioGroup")
+            .field("options", &self.options)
+            .field("selected", &self.selected)
+            .field("cursor", &self.cursor)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    fn click(widget: &mut impl Widget, x: usize, y: usize) {
+        for kind in [
+            MouseEventKind::Down(MouseButton::Left),
+            MouseEventKind::Up(MouseButton::Left),
+        ] {
+            widget.handle_mouse(MouseEvent::new(kind, x, y));
+        }
+    }
+
+    #[test]
+    fn button_states() {
+        let presses = Rc::new(RefCell::new(0));
+        let counter = Rc::clone(&presses);
+        let mut button = Button::new(Rect::new(10.0, 3.0), "OK").on_press(move || {
+            *counter.borrow_mut() += 1;
+        });
+        let theme = Theme::default();
+        assert_eq!(button.contents_of(2, 1, &theme).unwrap().symbol, '[');
+
+        button.handle_mouse(MouseEvent::new(MouseEventKind::Moved, 4, 1));
+        assert!(button.is_hovered());
+        button.handle_mouse(MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            4,
+            1,
+        ));
+        assert!(button.is_pressed());
+        let cell = button.contents_of(0, 0, &theme).unwrap();
+        assert_eq!(cell.style, theme.style(Role::Selection).reverse());
+        button.handle_mouse(MouseEvent::new(
+            MouseEventKind::Up(MouseButton::Left),
+            20,
+            1,
+        ));
+        assert_eq!(*presses.borrow(), 0);
+
+        click(&mut button, 4, 1);
+        assert_eq!(*presses.borrow(), 1);
+        assert!(!button.handle_key(Key::Enter.into()));
+        button.set_focused(true);
+        assert!(button.handle_key(Key::Enter.into()));
+        assert_eq!(*presses.borrow(), 2);
+    }
+
+    #[test]
+    fn checkbox_and_toggle() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&changes);
+        let mut checkbox = Checkbox::new(Rect::new(10.0, 1.0), "Wrap")
+            .on_change(move |checked| log.borrow_mut().push(checked));
+        checkbox.set_focused(true);
+        checkbox.handle_key(Key::Char(' ').into());
+        click(&mut checkbox, 0, 0);
+        assert_eq!(*changes.borrow(), vec![true, false]);
+
+        let toggle = Checkbox::toggle(Rect::new(10.0, 1.0), "Dark").with_checked(true);
+        let theme = Theme::default();
+        let text: String = (0..8)
+            .map(|x| toggle.contents_of(x, 0, &theme).unwrap().symbol)
+            .collect();
+        assert_eq!(text, "━━● Dark");
+    }
+
+    #[test]
+    fn radio_group_selection() {
+        let selected = Rc::new(RefCell::new(None));
+        let last = Rc::clone(&selected);
+        let mut group = RadioGroup::new(Rect::new(10.0, 3.0), ["a", "b", "c"])
+            .on_change(move |index| *last.borrow_mut() = Some(index));
+        group.set_focused(true);
+        group.handle_key(Key::Down.into());
+        assert_eq!(*selected.borrow(), Some(1));
+        click(&mut group, 0, 2);
+        assert_eq!(group.selected_option(), Some("c"));
+        let theme = Theme::default();
+        assert_eq!(group.contents_of(1, 2, &theme).unwrap().symbol, '•');
+        assert_eq!(group.contents_of(1, 1, &theme).unwrap().symbol, ' ');
+    }
+
+    #[test]
+    fn disabled_controls_ignore_input() {
+        let mut checkbox = Checkbox::new(Rect::new(10.0, 1.0), "Wrap").with_enabled(false);
+        checkbox.set_focused(true);
+        assert!(!checkbox.handle_key(Key::Enter.into()));
+        click(&mut checkbox, 0, 0);
+        assert!(!checkbox.is_checked());
+        let theme = Theme::default();
+        let cell = checkbox.contents_of(0, 0, &theme).unwrap();
+        assert_eq!(cell.style, theme.style(Role::TextMuted));
+    }
+}
@@ -0,0 +1,758 @@
+use std::ops::Range;
+
+use ansi_term::Style;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    text::{layered, Span, TextLine},
+    theme::{Role, Theme},
+    widget::{local_position, Widget},
+};
+
+const SCROLL_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Shortest edit script from `old` to `new` using Myers' algorithm. Only the
+/// part of each round that can have been reached is kept for backtracking.
+fn edit_script<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let (old_len, new_len) = (old.len().cast_signed(), new.len().cast_signed());
+    let offset = old_len + new_len + 1;
+    let mut furthest = vec![0; 2 * offset.cast_unsigned() + 1];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let at = |k: isize| (k + offset).cast_unsigned();
+    let same = |x: isize, y: isize| old[x.cast_unsigned()] == new[y.cast_unsigned()];
+    'search: for d in 0..=old_len + new_len {
+        trace.push(furthest[at(-d)..=at(d)].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let down = k == -d || (k != d && furthest[at(k - 1)] < furthest[at(k + 1)]);
+            let mut x = if down {
+                furthest[at(k + 1)]
+            } else {
+                furthest[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < old_len && y < new_len && same(x, y) {
+                x += 1;
+                y += 1;
+            }
+            furthest[at(k)] = x;
+            if x >= old_len && y >= new_len {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (old_len, new_len);
+    for (d, reached) in trace.iter().enumerate().rev() {
+        let d = d.cast_signed();
+        let k = x - y;
+        let round = |k: isize| reached[(k + d).cast_unsigned()];
+        let previous = if k == -d || (k != d && round(k - 1) < round(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = if d == 0 { 0 } else { round(previous) };
+        let previous_y = if d == 0 { 0 } else { previous_x - previous };
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x.cast_unsigned(), y.cast_unsigned()));
+        }
+        if d > 0 {
+            if x == previous_x {
+                edits.push(Edit::Insert((y - 1).cast_unsigned()));
+            } else {
+                edits.push(Edit::Delete((x - 1).cast_unsigned()));
+            }
+        }
+        (x, y) = (previous_x, previous_y);
+    }
+    edits.reverse();
+    edits
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Context,
+    Removed,
+    Added,
+}
+
+/// A line of a hunk with its 1-based numbers in the old and new text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: LineKind,
+    pub old: Option<usize>,
+    pub new: Option<usize>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    old_start: usize,
+    new_start: usize,
+    lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    #[must_use]
+    pub fn lines(&self) -> &[DiffLine] {
+        &self.lines
+    }
+
+    fn count(&self, kind: LineKind) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.kind == LineKind::Context || line.kind == kind)
+            .count()
+    }
+
+    /// The `@@ -1,3 +1,4 @@` line of a unified diff.
+    #[must_use]
+    pub fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start,
+            self.count(LineKind::Removed),
+            self.new_start,
+            self.count(LineKind::Added)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diff {
+    hunks: Vec<Hunk>,
+}
+
+impl Diff {
+    /// Compares two texts line by line, keeping `context` unchanged lines
+    /// around every change.
+    #[must_use]
+    pub fn between(old: &str, new: &str, context: usize) -> Self {
+        let old: Vec<_> = old.lines().collect();
+        let new: Vec<_> = new.lines().collect();
+        let lines: Vec<_> = edit_script(&old, &new)
+            .into_iter()
+            .map(|edit| match edit {
+                Edit::Equal(i, j) => DiffLine {
+                    kind: LineKind::Context,
+                    old: Some(i + 1),
+                    new: Some(j + 1),
+                    text: old[i].to_string(),
+                },
+                Edit::Delete(i) => DiffLine {
+                    kind: LineKind::Removed,
+                    old: Some(i + 1),
+                    new: None,
+                    text: old[i].to_string(),
+                },
+                Edit::Insert(j) => DiffLine {
+                    kind: LineKind::Added,
+                    old: None,
+                    new: Some(j + 1),
+                    text: new[j].to_string(),
+                },
+            })
+            .collect();
+
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if line.kind == LineKind::Context {
+                continue;
+            }
+            let range = i.saturating_sub(context)..(i + context + 1).min(lines.len());
+            match ranges.last_mut() {
+                Some(last) if last.end >= range.start => last.end = range.end,
+                _ => ranges.push(range),
+            }
+        }
+        let hunks = ranges
+            .into_iter()
+            .map(|range| {
+                let before = &lines[..range.start];
+                let old_start = before.iter().filter(|l| l.kind != LineKind::Added).count();
+                let new_start = before
+                    .iter()
+                    .filter(|l| l.kind != LineKind::Removed)
+                    .count();
+                let lines = lines[range].to_vec();
+                let first = |number: fn(&DiffLine) -> Option<usize>, fallback: usize| {
+                    lines.iter().find_map(number).unwrap_or(fallback)
+                };
+                Hunk {
+                    old_start: first(|l| l.old, old_start),
+                    new_start: first(|l| l.new, new_start),
+                    lines,
+                }
+            })
+            .collect();
+        Self { hunks }
+    }
+
+    /// Reads the hunks of a unified diff such as `git diff` prints. File
+    /// headers and anything else outside a hunk are skipped.
+    #[must_use]
+    pub fn parse_unified(diff: &str) -> Self {
+        let mut hunks: Vec<Hunk> = Vec::new();
+        let (mut old, mut new) = (0, 0);
+        let (mut old_left, mut new_left) = (0, 0);
+        for line in diff.lines() {
+            if old_left == 0 && new_left == 0 {
+                if let Some((old_range, new_range)) = hunk_ranges(line) {
+                    (old, old_left) = old_range;
+                    (new, new_left) = new_range;
+                    hunks.push(Hunk {
+                        old_start: old,
+                        new_start: new,
+                        lines: Vec::new(),
+                    });
+                }
+                continue;
+            }
+            let Some(hunk) = hunks.last_mut() else {
+                continue;
+            };
+            let (kind, text) = match line.split_at_checked(1) {
+                Some(("-", text)) => (LineKind::Removed, text),
+                Some(("+", text)) => (LineKind::Added, text),
+                Some(("\\", _)) => continue,
+                Some((_, text)) => (LineKind::Context, text),
+                None => (LineKind::Context, ""),
+            };
+            let removes = kind != LineKind::Added;
+            let adds = kind != LineKind::Removed;
+            hunk.lines.push(DiffLine {
+                kind,
+                old: removes.then_some(old),
+                new: adds.then_some(new),
+                text: text.to_string(),
+            });
+            if removes {
+                old += 1;
+                old_left = old_left.saturating_sub(1);
+            }
+            if adds {
+                new += 1;
+                new_left = new_left.saturating_sub(1);
+            }
+        }
+        Self { hunks }
+    }
+
+    #[must_use]
+    pub fn hunks(&self) -> &[Hunk] {
+        &self.hunks
+    }
+}
+
+/// Start and length of both sides from a `@@ -a,b +c,d @@` header.
+fn hunk_ranges(line: &str) -> Option<((usize, usize), (usize, usize))> {
+    let mut parts = line.strip_prefix("@@ ")?.split_whitespace();
+    let range = |part: Option<&str>, sign: char| {
+        let part = part?.strip_prefix(sign)?;
+        let (start, length) = part.split_once(',').unwrap_or((part, "1"));
+        Some((start.parse().ok()?, length.parse().ok()?))
+    };
+    Some((range(parts.next(), '-')?, range(parts.next(), '+')?))
+}
+
+/// Words, runs of spaces and single punctuation marks.
+fn words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let class = |c: char| (c.is_alphanumeric() || c == '_', c.is_whitespace());
+    for (i, c) in line.char_indices().skip(1) {
+        let previous = line[..i].chars().next_back().map(class);
+        let punctuation = class(c) == (false, false);
+        if punctuation || previous != Some(class(c)) {
+            words.push(&line[start..i]);
+            start = i;
+        }
+    }
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+    words
+}
+
+/// The two lines with the words that differ between them highlighted.
+fn word_diff(old: &str, new: &str) -> (TextLine, TextLine) {
+    let changed = Style::new().reverse();
+    let (old_words, new_words) = (words(old), words(new));
+    let (mut old_line, mut new_line) = (TextLine::new(), TextLine::new());
+    for edit in edit_script(&old_words, &new_words) {
+        match edit {
+            Edit::Equal(i, j) => {
+                old_line.push(old_words[i]);
+                new_line.push(new_words[j]);
+            }
+            Edit::Delete(i) => old_line.push(Span::styled(old_words[i], changed)),
+            Edit::Insert(j) => new_line.push(Span::styled(new_words[j], changed)),
+        }
+    }
+    (old_line, new_line)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffLayout {
+    #[default]
+    Unified,
+    SideBySide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Header,
+    Line(LineKind),
+    Filler,
+}
+
+impl Kind {
+    fn style(self, theme: &Theme) -> Style {
+        match self {
+            Self::Header => theme.style(Role::TextMuted),
+            Self::Line(LineKind::Context) => theme.style(Role::Text),
+            Self::Line(LineKind::Removed) => theme.style(Role::DiffRemoved),
+            Self::Line(LineKind::Added) => theme.style(Role::DiffAdded),
+            Self::Filler => theme.style(Role::Scrollbar),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Row {
+    hunk: usize,
+    panes: Vec<(TextLine, Kind)>,
+}
+
+/// Shows a [`Diff`] either unified or as old and new side by side, where
+/// both sides scroll together. Enter folds the hunk under the cursor.
+#[derive(Debug)]
+pub struct DiffView {
+    area: Rect,
+    panes: Vec<Rect>,
+    diff: Diff,
+    layout: DiffLayout,
+    folded: Vec<bool>,
+    rows: Vec<Row>,
+    cursor: usize,
+    offset: usize,
+}
+
+impl DiffView {
+    #[must_use]
+    pub fn new(area: Rect, diff: Diff) -> Self {
+        let mut view = Self {
+            panes: Vec::new(),
+            folded: vec![false; diff.hunks.len()],
+            area,
+            diff,
+            layout: DiffLayout::default(),
+            rows: Vec::new(),
+            cursor: 0,
+            offset: 0,
+        };
+        view.layout();
+        view
+    }
+
+    #[must_use]
+    pub fn with_layout(mut self, layout: DiffLayout) -> Self {
+        self.set_layout(layout);
+        self
+    }
+
+    pub fn set_layout(&mut self, layout: DiffLayout) {
+        self.layout = layout;
+        self.layout();
+    }
+
+    #[must_use]
+    pub const fn diff(&self) -> &Diff {
+        &self.diff
+    }
+
+    #[must_use]
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[must_use]
+    pub const fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[must_use]
+    pub fn is_folded(&self, hunk: usize) -> bool {
+        self.folded.get(hunk).copied().unwrap_or(false)
+    }
+
+    /// Folds or unfolds a hunk down to its header, leaving the cursor on it.
+    pub fn toggle_fold(&mut self, hunk: usize) {
+        if let Some(folded) = self.folded.get_mut(hunk) {
+            *folded = !*folded;
+            self.layout();
+            let header = self.rows.iter().position(|row| row.hunk == hunk);
+            self.move_cursor(header.unwrap_or(0));
+        }
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn height(&self) -> usize {
+        let (_, _, height) = self.area.pos_width_height();
+        (height as usize).max(1)
+    }
+
+    fn move_cursor(&mut self, row: usize) {
+        self.cursor = row.min(self.rows.len().saturating_sub(1));
+        let height = self.height();
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+    }
+
+    fn scroll(&mut self, offset: usize) {
+        self.offset = offset.min(self.rows.len().saturating_sub(self.height()));
+    }
+
+    /// Moves to the next hunk header, or back to the start of the current
+    /// hunk before going to the previous one.
+    fn jump_to_hunk(&mut self, forward: bool) {
+        let current = self.rows.get(self.cursor).map_or(0, |row| row.hunk);
+        let header = |hunk: usize| self.rows.iter().position(|row| row.hunk == hunk);
+        let target = if forward {
+            header(current + 1)
+        } else if header(current) == Some(self.cursor) {
+            current.checked_sub(1).and_then(header)
+        } else {
+            header(current)
+        };
+        if let Some(row) = target {
+            self.move_cursor(row);
+        }
+    }
+
+    fn gutter_width(&self) -> usize {
+        let last = self.diff.hunks.iter().flat_map(|hunk| &hunk.lines);
+        let largest = last.filter_map(|line| line.old.max(line.new)).max();
+        largest.unwrap_or(1).to_string().len()
+    }
+
+    fn layout(&mut self) {
+        let panes = match self.layout {
+            DiffLayout::Unified => 1,
+            DiffLayout::SideBySide => 2,
+        };
+        self.panes = self.area.clone().divide_horizontally(panes);
+        let width = self.gutter_width();
+        let number =
+            |n: Option<usize>| n.map_or_else(|| " ".repeat(width), |n| format!("{n:>width$}"));
+        let with_gutter = |gutter: String, text: &TextLine| {
+            let line = TextLine::new().with_span(gutter);
+            text.spans().iter().cloned().fold(line, TextLine::with_span)
+        };
+        self.rows.clear();
+        for (h, hunk) in self.diff.hunks.iter().enumerate() {
+            let header = |text: String| (TextLine::from(text), Kind::Header);
+            let headers = match (self.layout, self.folded[h]) {
+                (_, true) => {
+                    let folded = format!("{} ({} lines folded)", hunk.header(), hunk.lines.len());
+                    vec![header(folded), header(String::new())]
+                }
+                (DiffLayout::Unified, false) => vec![header(hunk.header())],
+                (DiffLayout::SideBySide, false) => {
+                    let (old, new) = (hunk.count(LineKind::Removed), hunk.count(LineKind::Added));
+                    vec![
+                        header(format!("@@ -{},{old} @@", hunk.old_start)),
+                        header(format!("@@ +{},{new} @@", hunk.new_start)),
+                    ]
+                }
+            };
+            self.rows.push(Row {
+                hunk: h,
+                panes: headers,
+            });
+            if self.folded[h] {
+                continue;
+            }
+
+            let pairs = pairs(&hunk.lines);
+            let mut texts: Vec<_> = hunk
+                .lines
+                .iter()
+                .map(|l| TextLine::from(l.text.as_str()))
+                .collect();
+            for &(old, new) in &pairs {
+                if let (Some(old), Some(new)) = (old, new) {
+                    if old != new {
+                        (texts[old], texts[new]) =
+                            word_diff(&hunk.lines[old].text, &hunk.lines[new].text);
+                    }
+                }
+            }
+            let line_pane = |index: usize, field: fn(&DiffLine) -> Option<usize>| {
+                let line = &hunk.lines[index];
+                let gutter = format!("{} │ ", number(field(line)));
+                (with_gutter(gutter, &texts[index]), Kind::Line(line.kind))
+            };
+            match self.layout {
+                DiffLayout::Unified => {
+                    for (line, text) in hunk.lines.iter().zip(&texts) {
+                        let marker = match line.kind {
+                            LineKind::Context => ' ',
+                            LineKind::Removed => '-',
+                            LineKind::Added => '+',
+                        };
+                        let gutter = format!("{} {} │{marker}", number(line.old), number(line.new));
+                        self.rows.push(Row {
+                            hunk: h,
+                            panes: vec![(with_gutter(gutter, text), Kind::Line(line.kind))],
+                        });
+                    }
+                }
+                DiffLayout::SideBySide => {
+                    for (old, new) in pairs {
+                        let filler = || (TextLine::new(), Kind::Filler);
+                        self.rows.push(Row {
+                            hunk: h,
+                            panes: vec![
+                                old.map_or_else(filler, |i| line_pane(i, |l| l.old)),
+                                new.map_or_else(filler, |i| line_pane(i, |l| l.new)),
+                            ],
+                        });
+                    }
+                }
+            }
+        }
+        self.move_cursor(self.cursor);
+        self.scroll(self.offset);
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let page = self.height();
+        match event.key {
+            Key::Up => self.move_cursor(self.cursor.saturating_sub(1)),
+            Key::Down => self.move_cursor(self.cursor + 1),
+            Key::PageUp => self.move_cursor(self.cursor.saturating_sub(page)),
+            Key::PageDown => self.move_cursor(self.cursor + page),
+            Key::Home => self.move_cursor(0),
+            Key::End => self.move_cursor(usize::MAX),
+            Key::Char('n') => self.jump_to_hunk(true),
+            Key::Char('N') => self.jump_to_hunk(false),
+            Key::Enter | Key::Char(' ') => match self.rows.get(self.cursor) {
+                Some(row) => self.toggle_fold(row.hunk),
+                None => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Indices of the lines shown next to each other: context lines face
+/// themselves, and a run of removed lines faces the added lines after it.
+fn pairs(lines: &[DiffLine]) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut pairs = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].kind == LineKind::Context {
+            pairs.push((Some(i), Some(i)));
+            i += 1;
+            continue;
+        }
+        let removed = lines[i..]
+            .iter()
+            .take_while(|l| l.kind == LineKind::Removed)
+            .count();
+        let added = lines[i + removed..]
+            .iter()
+            .take_while(|l| l.kind == LineKind::Added)
+            .count();
+        for k in 0..removed.max(added) {
+            pairs.push((
+                (k < removed).then_some(i + k),
+                (k < added).then_some(i + removed + k),
+            ));
+        }
+        i += removed + added;
+    }
+    pairs
+}
+
+impl Widget for DiffView {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (pane, (column, row)) = self
+            .panes
+            .iter()
+            .enumerate()
+            .find_map(|(i, pane)| Some((i, local_position(pane, x, y)?)))?;
+        let index = row + self.offset;
+        let Some(row) = self.rows.get(index) else {
+            return Some(Cell::blank(theme.style(Role::Text)));
+        };
+        let (text, kind) = &row.panes[pane];
+        let base = kind.style(theme);
+        let mut cell = match text.cell_at(column, theme) {
+            Some((symbol, style)) => Cell::new(symbol, style.map_or(base, |s| layered(base, s))),
+            None if *kind == Kind::Filler => Cell::new('╱', base),
+            None => Cell::blank(base),
+        };
+        if index == self.cursor {
+            cell.style.background = theme.style(Role::Selection).background;
+        }
+        Some(cell)
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let Some((_, row)) = local_position(&self.area, event.x, event.y) else {
+            return false;
+        };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let index = row + self.offset;
+                if index < self.rows.len() {
+                    let header = self
+                        .rows
+                        .iter()
+                        .position(|r| r.hunk == self.rows[index].hunk);
+                    if index == self.cursor && header == Some(index) {
+                        self.toggle_fold(self.rows[index].hunk);
+                    } else {
+                        self.move_cursor(index);
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp => self.scroll(self.offset.saturating_sub(SCROLL_LINES)),
+            MouseEventKind::ScrollDown => self.scroll(self.offset + SCROLL_LINES),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ansi_term::Colour;
+
+    use super::*;
+
+    const OLD: &str = "a\nb\nc\nlet x = 1;\nd\ne\nf\ng\nh\ni\nj";
+    const NEW: &str = "a\nb\nc\nlet y = 1;\nd\ne\nf\ng\nh\nI\nj\nk";
+
+    fn render(view: &DiffView, width: usize, rows: usize) -> Vec<String> {
+        let theme = Theme::default();
+        (0..rows)
+            .map(|y| {
+                (0..width)
+                    .map(|x| view.contents_of(x, y, &theme).map_or(' ', |c| c.symbol))
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diff_between_texts() {
+        use LineKind::{Added, Context, Removed};
+        let diff = Diff::between(OLD, NEW, 1);
+        let headers: Vec<_> = diff.hunks().iter().map(Hunk::header).collect();
+        assert_eq!(headers, vec!["@@ -3,3 +3,3 @@", "@@ -9,3 +9,4 @@"]);
+        let kinds: Vec<_> = diff.hunks()[1].lines().iter().map(|l| l.kind).collect();
+        assert_eq!(kinds, vec![Context, Removed, Added, Context, Added]);
+        assert_eq!(Diff::between("same", "same", 3).hunks(), &[]);
+    }
+
+    #[test]
+    fn parses_unified_diff() {
+        let text = "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -3,3 +3,3 @@\n c\n-let x = 1;\n+let y = 1;\n d\n@@ -11 +11,2 @@\n j\n+k\n\\ No newline at end of file\n";
+        let parsed = Diff::parse_unified(text);
+        let computed = Diff::between(OLD, NEW, 1);
+        assert_eq!(parsed.hunks()[0], computed.hunks()[0]);
+        assert_eq!(parsed.hunks()[1].header(), "@@ -11,1 +11,2 @@");
+        assert_eq!(parsed.hunks()[1].lines()[1].new, Some(12));
+    }
+
+    #[test]
+    fn unified_view_highlights_words() {
+        let view = DiffView::new(Rect::new(30.0, 6.0), Diff::between(OLD, NEW, 1));
+        assert_eq!(
+            render(&view, 30, 6),
+            vec![
+                "@@ -3,3 +3,3 @@",
+                " 3  3 │ c",
+                " 4    │-let x = 1;",
+                "    4 │+let y = 1;",
+                " 5  5 │ d",
+                "@@ -9,3 +9,4 @@",
+            ]
+        );
+        let theme = Theme::default();
+        let changed = view.contents_of(12, 3, &theme).unwrap();
+        let added = theme.style(Role::DiffAdded);
+        assert_eq!(changed, Cell::new('y', added.reverse()));
+        let same = view.contents_of(8, 2, &theme).unwrap();
+        assert_eq!(same, Cell::new('l', theme.style(Role::DiffRemoved)));
+
+        let theme = theme.with_style(Role::DiffRemoved, Colour::Purple.normal());
+        let same = view.contents_of(8, 2, &theme).unwrap();
+        assert_eq!(same, Cell::new('l', Colour::Purple.normal()));
+    }
+
+    #[test]
+    fn side_by_side_folds_and_scrolls_together() {
+        let diff = Diff::between(OLD, NEW, 0);
+        let area = Rect::new(32.0, 3.0);
+        let mut view = DiffView::new(area, diff).with_layout(DiffLayout::SideBySide);
+        assert_eq!(
+            render(&view, 32, 3),
+            vec![
+                "@@ -4,1 @@      @@ +4,1 @@",
+                " 4 │ let x = 1;  4 │ let y = 1;",
+                "@@ -10,1 @@     @@ +10,1 @@",
+            ]
+        );
+        view.handle_key(Key::Enter.into());
+        view.handle_key(Key::End.into());
+        assert!(view.is_folded(0));
+        assert_eq!((view.cursor(), view.offset()), (4, 2));
+        assert_eq!(
+            render(&view, 32, 3),
+            vec![
+                "10 │ i          10 │ I",
+                "@@ -11,0 @@     @@ +12,1 @@",
+                "╱╱╱╱╱╱╱╱╱╱╱╱╱╱╱╱12 │ k",
+            ]
+        );
+        view.handle_key(Key::Home.into());
+        assert_eq!(render(&view, 32, 1), vec!["@@ -4,1 +4,1 @@"]);
+        view.handle_key(Key::Char('n').into());
+        view.handle_key(Key::Char('n').into());
+        assert_eq!(view.cursor(), 3);
+        view.handle_key(Key::Char('N').into());
+        assert_eq!(view.cursor(), 1);
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Backspace,
+    Delete,
+    Tab,
+    BackTab,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    F(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self {
+        ctrl: false,
+        alt: false,
+        shift: false,
+    };
+    pub const CTRL: Self = Self {
+        ctrl: true,
+        ..Self::NONE
+    };
+    pub const ALT: Self = Self {
+        alt: true,
+        ..Self::NONE
+    };
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ..Self::NONE
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl KeyEvent {
+    #[must_use]
+    pub const fn new(key: Key, modifiers: Modifiers) -> Self {
+        Self { key, modifiers }
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Char(' ') => f.write_str("Space"),
+            Self::Char(symbol) => write!(f, "{}", symbol.to_uppercase()),
+            Self::Enter => f.write_str("Enter"),
+            Self::Esc => f.write_str("Esc"),
+            Self::Backspace => f.write_str("Backspace"),
+            Self::Delete => f.write_str("Del"),
+            Self::Tab => f.write_str("Tab"),
+            Self::BackTab => f.write_str("Shift+Tab"),
+            Self::Up => f.write_str("Up"),
+            Self::Down => f.write_str("Down"),
+            Self::Left => f.write_str("Left"),
+            Self::Right => f.write_str("Right"),
+            Self::Home => f.write_str("Home"),
+            Self::End => f.write_str("End"),
+            Self::PageUp => f.write_str("PgUp"),
+            Self::PageDown => f.write_str("PgDn"),
+            Self::Insert => f.write_str("Ins"),
+            Self::F(n) => write!(f, "F{n}"),
+        }
+    }
+}
+
+/// Formats as e.g. `Ctrl+Shift+S`. Characters typed without a modifier
+/// keep their case, so that `g` and `G` stay distinct.
+impl Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Modifiers { ctrl, alt, shift } = self.modifiers;
+        for (held, name) in [(ctrl, "Ctrl+"), (alt, "Alt+"), (shift, "Shift+")] {
+            if held {
+                f.write_str(name)?;
+            }
+        }
+        match self.key {
+            Key::Char(symbol) if symbol != ' ' && self.modifiers == Modifiers::NONE => {
+                write!(f, "{symbol}")
+            }
+            key => write!(f, "{key}"),
+        }
+    }
+}
+
+/// Parses what [`Display`] writes, e.g. `Ctrl+Shift+S`, `PgDn` or `G`.
+/// Names are case-insensitive and letters held with a modifier are read in
+/// lower case, matching the events the terminal sends.
+impl FromStr for KeyEvent {
+    type Err = InvalidKey;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidKey(s.to_string());
+        let (prefix, name) = match s.strip_suffix('+') {
+            Some(prefix) if prefix.is_empty() || prefix.ends_with('+') => (prefix, "+"),
+            _ => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+        let mut modifiers = Modifiers::NONE;
+        for modifier in prefix.split('+').filter(|m| !m.is_empty()) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "alt" | "meta" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                _ => return Err(invalid()),
+            }
+        }
+        let mut chars = name.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(symbol), None) if modifiers == Modifiers::NONE => Key::Char(symbol),
+            (Some(symbol), None) => Key::Char(symbol.to_ascii_lowercase()),
+            _ => match name.to_ascii_lowercase().as_str() {
+                "space" => Key::Char(' '),
+                "enter" | "return" => Key::Enter,
+                "esc" | "escape" => Key::Esc,
+                "backspace" => Key::Backspace,
+                "del" | "delete" => Key::Delete,
+                "tab" if modifiers.shift => {
+                    modifiers.shift = false;
+                    Key::BackTab
+                }
+                "tab" => Key::Tab,
+                "backtab" => Key::BackTab,
+                "up" => Key::Up,
+                "down" => Key::Down,
+                "left" => Key::Left,
+                "right" => Key::Right,
+                "home" => Key::Home,
+                "end" => Key::End,
+                "pgup" | "pageup" => Key::PageUp,
+                "pgdn" | "pagedown" => Key::PageDown,
+                "ins" | "insert" => Key::Insert,
+                other => other
+                    .strip_prefix('f')
+                    .and_then(|n| n.parse().ok())
+                    .map(Key::F)
+                    .ok_or_else(invalid)?,
+            },
+        };
+        Ok(Self::new(key, modifiers))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidKey(pub String);
+
+impl Display for InvalidKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidKey {}
+
+impl From<Key> for KeyEvent {
+    fn from(key: Key) -> Self {
+        Self::new(key, Modifiers::NONE)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseEventKind {
+    Down(MouseButton),
+    Up(MouseButton),
+    Drag(MouseButton),
+    Moved,
+    ScrollUp,
+    ScrollDown,
+    /// A second press of the same button in quick succession, sent after
+    /// its `Down`.
+    DoubleClick(MouseButton),
+    /// The pointer moved onto the widget.
+    Enter,
+    /// The pointer moved off the widget.
+    Leave,
+}
+
+/// A mouse event at a screen position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    pub kind: MouseEventKind,
+    pub x: usize,
+    pub y: usize,
+    pub modifiers: Modifiers,
+}
+
+impl MouseEvent {
+    #[must_use]
+    pub const fn new(kind: MouseEventKind, x: usize, y: usize) -> Self {
+        Self {
+            kind,
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        }
+    }
+}
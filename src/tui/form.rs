@@ -0,0 +1,582 @@
+use std::fmt::{self, Debug};
+
+use glam::Vec2;
+use unicode_width::UnicodeWidthStr;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::Container,
+    controls::{Button, Checkbox, Control},
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    input::TextInput,
+    menu::Dropdown,
+    theme::{Role, Theme},
+    widget::{local_position, Widget},
+};
+
+type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+#[derive(Debug, Clone)]
+enum FieldKind {
+    Text { mask: Option<char> },
+    Checkbox,
+    Choice(Vec<String>),
+}
+
+/// Describes a form field. Checkbox values are `"true"` or `"false"`.
+pub struct Field {
+    name: String,
+    label: String,
+    kind: FieldKind,
+    value: String,
+    required: bool,
+    validators: Vec<Validator>,
+}
+
+impl Field {
+    fn new(name: impl Into<String>, label: impl Into<String>, kind: FieldKind) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            kind,
+            value: String::new(),
+            required: false,
+            validators: Vec::new(),
+        }
+    }
+
+    pub fn text(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label, FieldKind::Text { mask: None })
+    }
+
+    pub fn password(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label, FieldKind::Text { mask: Some('*') })
+    }
+
+    pub fn checkbox(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label, FieldKind::Checkbox)
+    }
+
+    pub fn choice<T: Into<String>>(
+        name: impl Into<String>,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let options = options.into_iter().map(Into::into).collect();
+        Self::new(name, label, FieldKind::Choice(options))
+    }
+
+    #[must_use]
+    pub fn with_value(self, value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            ..self
+        }
+    }
+
+    /// Text has to be non-empty and checkboxes checked.
+    #[must_use]
+    pub fn required(self) -> Self {
+        Self {
+            required: true,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+}
+
+impl Debug for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Field")
+            .field("name", &self.name)
+            .field("label", &self.label)
+            .field("kind", &self.kind)
+            .field("value", &self.value)
+            .field("required", &self.required)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+enum Input {
+    Text(TextInput),
+    Checkbox(Checkbox),
+    Choice(Dropdown),
+}
+
+impl Input {
+    fn new(field: &Field, area: Rect, screen: &Rect) -> Self {
+        match &field.kind {
+            FieldKind::Text { mask } => {
+                let input = TextInput::new(Container::new(area)).with_value(&field.value);
+                Self::Text(match mask {
+                    Some(mask) => input.with_mask(*mask),
+                    None => input,
+                })
+            }
+            FieldKind::Checkbox => {
+                Self::Checkbox(Checkbox::new(area, "").with_checked(field.value == "true"))
+            }
+            FieldKind::Choice(options) => {
+                let mut dropdown = Dropdown::new(area, screen.clone()).with_options(options);
+                if let Some(index) = options.iter().position(|o| *o == field.value) {
+                    dropdown.select(index);
+                }
+                Self::Choice(dropdown)
+            }
+        }
+    }
+
+    fn widget(&self) -> &dyn Widget {
+        match self {
+            Self::Text(input) => input,
+            Self::Checkbox(checkbox) => checkbox,
+            Self::Choice(dropdown) => dropdown,
+        }
+    }
+
+    fn widget_mut(&mut self) -> &mut dyn Widget {
+        match self {
+            Self::Text(input) => input,
+            Self::Checkbox(checkbox) => checkbox,
+            Self::Choice(dropdown) => dropdown,
+        }
+    }
+
+    fn value(&self) -> String {
+        match self {
+            Self::Text(input) => input.value(),
+            Self::Checkbox(checkbox) => checkbox.is_checked().to_string(),
+            Self::Choice(dropdown) => dropdown.selected_option().unwrap_or_default().to_string(),
+        }
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        match self {
+            Self::Text(input) => input.frame_mut().set_focused(focused),
+            Self::Checkbox(checkbox) => checkbox.set_focused(focused),
+            Self::Choice(dropdown) => {
+                dropdown.set_focused(focused);
+                if !focused {
+                    dropdown.close();
+                }
+            }
+        }
+    }
+
+    const fn is_open(&self) -> bool {
+        matches!(self, Self::Choice(dropdown) if dropdown.is_open())
+    }
+}
+
+#[derive(Debug)]
+struct Slot {
+    field: Field,
+    input: Input,
+    error: Option<String>,
+}
+
+impl Slot {
+    fn validate(&mut self) -> bool {
+        let value = self.input.value();
+        let missing = match self.input {
+            Input::Checkbox(_) => value != "true",
+            Input::Text(_) | Input::Choice(_) => value.is_empty(),
+        };
+        self.error = if self.field.required && missing {
+            Some("required".to_string())
+        } else {
+            self.field
+                .validators
+                .iter()
+                .find_map(|validator| validator(&value).err())
+        };
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormOutcome {
+    Submitted(Vec<(String, String)>),
+    Cancelled,
+}
+
+/// Labels and fields in two columns, each field followed by a row for its
+/// error message, with submit and cancel buttons below.
+///
+/// Tab and shift+tab follow the order the fields were given in. Enter in a
+/// text field submits and escape cancels; the result is reported through
+/// [`Form::take_outcome`].
+#[derive(Debug)]
+pub struct Form {
+    area: Rect,
+    slots: Vec<Slot>,
+    label_width: usize,
+    submit: Button,
+    cancel: Button,
+    focus: usize,
+    outcome: Option<FormOutcome>,
+}
+
+impl Form {
+    #[must_use]
+    pub fn new(area: Rect, fields: Vec<Field>) -> Self {
+        let label_width = fields
+            .iter()
+            .map(|field| field.label.chars().count() + if field.required { 2 } else { 0 })
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let (position, width, _) = area.pos_width_height();
+        let field_width = (width - label_width as f64).max(1.0);
+        let row_at = |row: usize| position + Vec2::new(label_width as f32, row as f32);
+        let slots = fields
+            .into_iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let rect = Rect::new(field_width, 1.0).with_position(row_at(2 * i));
+                Slot {
+                    input: Input::new(&field, rect, &area),
+                    field,
+                    error: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let buttons = row_at(2 * slots.len());
+        let button_width = |label: &str| label.width() + 4;
+        let button = |offset: usize, label: &str| {
+            let rect = Rect::new(button_width(label) as f64, 1.0)
+                .with_position(buttons + Vec2::new(offset as f32, 0.0));
+            Button::new(rect, label)
+        };
+        let (submit, cancel) = ("Submit", "Cancel");
+        let mut form = Self {
+            submit: button(0, submit),
+            cancel: button(button_width(submit) + 1, cancel),
+            area,
+            slots,
+            label_width,
+            focus: 0,
+            outcome: None,
+        };
+        form.set_focus(0);
+        form
+    }
+
+    #[must_use]
+    pub const fn focus(&self) -> usize {
+        self.focus
+    }
+
+    #[must_use]
+    pub fn value(&self, name: &str) -> Option<String> {
+        self.slot(name).map(|slot| slot.input.value())
+    }
+
+    #[must_use]
+    pub fn values(&self) -> Vec<(String, String)> {
+        self.slots
+            .iter()
+            .map(|slot| (slot.field.name.clone(), slot.input.value()))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn error(&self, name: &str) -> Option<&str> {
+        self.slot(name)?.error.as_deref()
+    }
+
+    pub const fn take_outcome(&mut self) -> Option<FormOutcome> {
+        self.outcome.take()
+    }
+
+    fn slot(&self, name: &str) -> Option<&Slot> {
+        self.slots.iter().find(|slot| slot.field.name == name)
+    }
+
+    /// Validates every field and returns whether all of them passed.
+    pub fn validate(&mut self) -> bool {
+        let mut valid = true;
+        for slot in &mut self.slots {
+            valid &= slot.validate();
+        }
+        valid
+    }
+
+    /// Submits when every field is valid, otherwise moves the focus to the
+    /// first invalid one.
+    pub fn submit(&mut self) {
+        if self.validate() {
+            self.outcome = Some(FormOutcome::Submitted(self.values()));
+        } else if let Some(index) = self.slots.iter().position(|slot| slot.error.is_some()) {
+            self.set_focus(index);
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.outcome = Some(FormOutcome::Cancelled);
+    }
+
+    /// Focus indices run through the fields and then the two buttons. The
+    /// field losing focus is validated.
+    pub fn set_focus(&mut self, focus: usize) {
+        let count = self.slots.len() + 2;
+        let focus = focus % count;
+        if let Some(slot) = self.slots.get_mut(self.focus) {
+            slot.input.set_focused(false);
+            if self.focus != focus {
+                slot.validate();
+            }
+        }
+        self.submit.set_focused(false);
+        self.cancel.set_focused(false);
+        match focus.checked_sub(self.slots.len()) {
+            None => self.slots[focus].input.set_focused(true),
+            Some(0) => self.submit.set_focused(true),
+            Some(_) => self.cancel.set_focused(true),
+        }
+        self.focus = focus;
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let count = self.slots.len() + 2;
+        self.set_focus(if forward {
+            self.focus + 1
+        } else {
+            self.focus + count - 1
+        });
+    }
+}
+
+impl Widget for Form {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let mut open = self.slots.iter().filter(|slot| slot.input.is_open());
+        if let Some(cell) = open.find_map(|slot| slot.input.widget().contents_of(x, y, theme)) {
+            return Some(cell);
+        }
+        let (column, row) = local_position(&self.area, x, y)?;
+        let fields = [&self.submit as &dyn Widget, &self.cancel]
+            .into_iter()
+            .chain(self.slots.iter().map(|slot| slot.input.widget()));
+        if let Some(cell) = fields.into_iter().find_map(|w| w.contents_of(x, y, theme)) {
+            return Some(cell);
+        }
+        let blank = Cell::blank(theme.style(Role::Text));
+        let Some(slot) = self.slots.get(row / 2) else {
+            return Some(blank);
+        };
+        Some(match (row % 2, column.checked_sub(self.label_width)) {
+            (0, None) => {
+                let label = &slot.field.label;
+                let length = label.chars().count();
+                match column.cmp(&length) {
+                    std::cmp::Ordering::Less => {
+                        let symbol = label.chars().nth(column).unwrap_or(' ');
+                        Cell::new(symbol, theme.style(Role::Text))
+                    }
+                    _ if slot.field.required && column == length + 1 => {
+                        Cell::new('*', theme.style(Role::Error))
+                    }
+                    _ => blank,
+                }
+            }
+            (1, Some(offset)) => slot.error.as_ref().map_or(blank, |error| {
+                let symbol = error.chars().nth(offset).unwrap_or(' ');
+                Cell::new(symbol, theme.style(Role::Error))
+            }),
+            _ => blank,
+        })
+    }
+
+    fn cursor_position(&self) -> Option<(usize, usize)> {
+        self.slots.get(self.focus)?.input.widget().cursor_position()
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let fields = self.slots.len();
+        if let Some(slot) = self.slots.get_mut(self.focus) {
+            if slot.input.is_open() {
+                return slot.input.widget_mut().handle_key(event);
+            }
+        }
+        match (event.key, self.focus.checked_sub(fields)) {
+            (Key::Tab, _) | (Key::Down | Key::Right, Some(_)) => self.move_focus(true),
+            (Key::BackTab, _) | (Key::Up | Key::Left, Some(_)) => self.move_focus(false),
+            (Key::Enter, Some(0)) => self.submit(),
+            (Key::Esc, _) | (Key::Enter, Some(_)) => self.cancel(),
+            (Key::Enter, None) if matches!(self.slots[self.focus].input, Input::Text(_)) => {
+                self.submit();
+            }
+            (key, None) => {
+                let used = self.slots[self.focus].input.widget_mut().handle_key(event);
+                match key {
+                    _ if used => {}
+                    Key::Up => self.move_focus(false),
+                    Key::Down => self.move_focus(true),
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let down = event.kind == MouseEventKind::Down(MouseButton::Left);
+        if let Some(slot) = self.slots.get_mut(self.focus) {
+            if slot.input.is_open() && slot.input.widget_mut().handle_mouse(event) {
+                return true;
+            }
+        }
+        let fields = self.slots.len();
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            let used = slot.input.widget_mut().handle_mouse(event);
+            let hit = match &slot.input {
+                Input::Text(input) => input.frame().contains(event.x, event.y),
+                Input::Checkbox(_) | Input::Choice(_) => false,
+            };
+            if (used || hit) && down && index != self.focus {
+                self.set_focus(index);
+                return true;
+            }
+        }
+        for index in [fields, fields + 1] {
+            let button = if index == fields {
+                &mut self.submit
+            } else {
+                &mut self.cancel
+            };
+            let pressed = button.is_pressed();
+            let hovered = button.handle_mouse(event);
+            let clicked = pressed && hovered && !button.is_pressed();
+            if down && hovered {
+                self.set_focus(index);
+                return true;
+            }
+            if clicked {
+                if index == fields {
+                    self.submit();
+                } else {
+                    self.cancel();
+                }
+                return true;
+            }
+        }
+        local_position(&self.area, event.x, event.y).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form() -> Form {
+        Form::new(
+            Rect::new(30.0, 8.0),
+            vec![
+                Field::text("name", "Name").required(),
+                Field::text("port", "Port")
+                    .with_value("80")
+                    .with_validator(|value| {
+                        value.parse::<u16>().map(drop).map_err(|e| e.to_string())
+                    }),
+                Field::checkbox("tls", "TLS"),
+            ],
+        )
+    }
+
+    fn render(form: &Form) -> Vec<String> {
+        let theme = Theme::default();
+        (0..8)
+            .map(|y| {
+                (0..30)
+                    .map(|x| form.contents_of(x, y, &theme).unwrap().symbol)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn typed(form: &mut Form, text: &str) {
+        for symbol in text.chars() {
+            form.handle_key(Key::Char(symbol).into());
+        }
+    }
+
+    #[test]
+    fn two_column_layout() {
+        assert_eq!(
+            render(&form()),
+            vec![
+                "Name *",
+                "",
+                "Port    80",
+                "",
+                "TLS     [ ]",
+                "",
+                "        [ Submit ] [ Cancel ]",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn required_fields_block_submit() {
+        let mut form = form();
+        form.set_focus(3);
+        form.handle_key(Key::Enter.into());
+        assert_eq!(form.take_outcome(), None);
+        assert_eq!(form.focus(), 0);
+        assert_eq!(render(&form)[1], "        required");
+
+        typed(&mut form, "web");
+        form.handle_key(Key::Tab.into());
+        form.handle_key(Key::Backspace.into());
+        typed(&mut form, "x");
+        form.handle_key(Key::Tab.into());
+        assert_eq!(form.error("port"), Some("invalid digit found in string"));
+        form.handle_key(Key::Char(' ').into());
+        form.handle_key(Key::BackTab.into());
+        form.handle_key(Key::Backspace.into());
+        form.handle_key(Key::Enter.into());
+        assert_eq!(
+            form.take_outcome(),
+            Some(FormOutcome::Submitted(vec![
+                ("name".to_string(), "web".to_string()),
+                ("port".to_string(), "8".to_string()),
+                ("tls".to_string(), "true".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn mouse_and_cancel() {
+        let mut form = form();
+        let click = |kind| MouseEvent::new(kind, 9, 4);
+        form.handle_mouse(click(MouseEventKind::Down(MouseButton::Left)));
+        form.handle_mouse(click(MouseEventKind::Up(MouseButton::Left)));
+        assert_eq!(
+            (form.focus(), form.value("tls").as_deref()),
+            (2, Some("true"))
+        );
+        form.handle_mouse(MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            12,
+            2,
+        ));
+        assert_eq!(form.focus(), 1);
+        form.handle_key(Key::Esc.into());
+        assert_eq!(form.take_outcome(), Some(FormOutcome::Cancelled));
+    }
+}
@@ -0,0 +1,410 @@
+use std::{
+    fmt::{self, Debug},
+    ops::Range,
+};
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::Container,
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+const GROUP: usize = 8;
+const SCROLL_ROWS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Offset(usize),
+    Hex(usize, u32),
+    Ascii(usize),
+    Border,
+    Blank,
+}
+
+/// A hex dump of anything that can be viewed as bytes, from a `Vec<u8>` to
+/// a memory-mapped file. Only the visible rows are ever looked at.
+///
+/// Each row shows the offset, the bytes in hex in groups of eight and the
+/// same bytes as ASCII. The byte under the cursor and the selection are
+/// highlighted in both columns.
+pub struct HexView<B> {
+    frame: Container,
+    data: B,
+    fixed_width: Option<usize>,
+    bytes_per_row: usize,
+    columns: Vec<Part>,
+    cursor: usize,
+    anchor: Option<usize>,
+    first_row: usize,
+}
+
+impl<B: AsRef<[u8]>> HexView<B> {
+    pub fn new(frame: Container, data: B) -> Self {
+        let mut view = Self {
+            frame,
+            data,
+            fixed_width: None,
+            bytes_per_row: 1,
+            columns: Vec::new(),
+            cursor: 0,
+            anchor: None,
+            first_row: 0,
+        };
+        view.layout();
+        view
+    }
+
+    /// Fixes the number of bytes per row instead of fitting as many groups
+    /// of eight as the content width allows.
+    #[must_use]
+    pub fn with_bytes_per_row(mut self, bytes: usize) -> Self {
+        self.fixed_width = Some(bytes.max(1));
+        self.layout();
+        self
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data().is_empty()
+    }
+
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    /// The frame, for styling. Resize it with [`Self::set_area`] instead, so
+    /// that the rows are fitted to the new width.
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    /// Moves or resizes the frame and fits the rows to it again, keeping the
+    /// cursor in view.
+    pub fn set_area(&mut self, area: Rect) {
+        self.frame.set_area(area);
+        self.layout();
+    }
+
+    pub const fn bytes_per_row(&self) -> usize {
+        self.bytes_per_row
+    }
+
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub const fn first_row(&self) -> usize {
+        self.first_row
+    }
+
+    /// The selected byte range, from the anchor to the cursor inclusive.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor) + 1)
+    }
+
+    pub fn selected_bytes(&self) -> &[u8] {
+        self.selection().map_or(&[], |range| &self.data()[range])
+    }
+
+    pub fn select(&mut self, range: Range<usize>) {
+        if range.is_empty() || range.end > self.len() {
+            return;
+        }
+        self.anchor = Some(range.start);
+        self.move_cursor(range.end - 1, true);
+    }
+
+    pub const fn clear_selection(&mut self) {
+        self.anchor = None;
+    }
+
+    /// Moves the cursor and scrolls it into view, starting or extending the
+    /// selection when `extend` is set. An empty buffer has nothing to select.
+    pub fn move_cursor(&mut self, index: usize, extend: bool) {
+        if extend && !self.is_empty() {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = index.min(self.len().saturating_sub(1));
+        let row = self.cursor / self.bytes_per_row;
+        let height = self.frame.content_size().1.max(1);
+        if row < self.first_row {
+            self.first_row = row;
+        } else if row >= self.first_row + height {
+            self.first_row = row + 1 - height;
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        self.len().div_ceil(self.bytes_per_row)
+    }
+
+    fn scroll_to(&mut self, row: usize) {
+        let height = self.frame.content_size().1;
+        self.first_row = row.min(self.row_count().saturating_sub(height));
+    }
+
+    /// Hex digits needed for the largest offset, at least eight.
+    fn offset_digits(&self) -> usize {
+        let bits = usize::BITS - self.len().leading_zeros();
+        (bits.div_ceil(4) as usize).max(8)
+    }
+
+    fn row_width(&self, bytes: usize) -> usize {
+        let gaps = (bytes - 1) / GROUP;
+        self.offset_digits() + 2 + 3 * bytes + gaps + 1 + bytes + 2
+    }
+
+    fn layout(&mut self) {
+        let (width, _) = self.frame.content_size();
+        self.bytes_per_row = self.fixed_width.unwrap_or_else(|| {
+            let fits = |bytes: usize| self.row_width(bytes) <= width;
+            let groups = (1..=width / GROUP).take_while(|g| fits(g * GROUP)).last();
+            groups.map_or_else(
+                || (1..GROUP).take_while(|&b| fits(b)).last().unwrap_or(1),
+                |groups| groups * GROUP,
+            )
+        });
+
+        let mut columns: Vec<_> = (0..self.offset_digits()).map(Part::Offset).collect();
+        columns.extend([Part::Blank, Part::Blank]);
+        for byte in 0..self.bytes_per_row {
+            if byte > 0 && byte % GROUP == 0 {
+                columns.push(Part::Blank);
+            }
+            columns.extend([Part::Hex(byte, 1), Part::Hex(byte, 0), Part::Blank]);
+        }
+        columns.extend([Part::Blank, Part::Border]);
+        columns.extend((0..self.bytes_per_row).map(Part::Ascii));
+        columns.push(Part::Border);
+        self.columns = columns;
+        self.scroll_to(self.first_row);
+        self.move_cursor(self.cursor, self.anchor.is_some());
+    }
+
+    /// The byte drawn in a content column of a row, if any.
+    fn byte_at(&self, column: usize, row: usize) -> Option<usize> {
+        let (Part::Hex(byte, _) | Part::Ascii(byte)) = self.columns.get(column)? else {
+            return None;
+        };
+        let index = (self.first_row + row) * self.bytes_per_row + byte;
+        (index < self.len()).then_some(index)
+    }
+
+    fn byte_style(&self, index: usize, theme: &Theme) -> ansi_term::Style {
+        let byte = self.data()[index];
+        let selected = self.selection().is_some_and(|range| range.contains(&index));
+        if index == self.cursor {
+            theme.style(Role::Selection).underline()
+        } else if selected {
+            theme.style(Role::Selection)
+        } else if byte == 0 || !(byte.is_ascii_graphic() || byte == b' ') {
+            theme.style(Role::TextMuted)
+        } else {
+            theme.style(Role::Text)
+        }
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let KeyEvent { key, modifiers } = event;
+        let row = self.bytes_per_row;
+        let page = row * self.frame.content_size().1.max(1);
+        let start_of_row = self.cursor - self.cursor % row;
+        let target = match key {
+            Key::Left => self.cursor.saturating_sub(1),
+            Key::Right => self.cursor + 1,
+            Key::Up => self.cursor.saturating_sub(row),
+            Key::Down if self.cursor + row < self.len() => self.cursor + row,
+            Key::Down => self.cursor,
+            Key::PageUp => self.cursor.saturating_sub(page),
+            Key::PageDown => self.cursor.saturating_add(page),
+            Key::Home if modifiers.ctrl => 0,
+            Key::End if modifiers.ctrl => usize::MAX,
+            Key::Home => start_of_row,
+            Key::End => start_of_row + row - 1,
+            Key::Esc if self.anchor.is_some() => {
+                self.clear_selection();
+                return true;
+            }
+            _ => return false,
+        };
+        self.move_cursor(target, modifiers.shift);
+        true
+    }
+}
+
+impl<B> Debug for HexView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HexView")
+            .field("frame", &self.frame)
+            .field("bytes_per_row", &self.bytes_per_row)
+            .field("cursor", &self.cursor)
+            .field("anchor", &self.anchor)
+            .field("first_row", &self.first_row)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: AsRef<[u8]>> Widget for HexView<B> {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((column, row)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let start = (self.first_row + row) * self.bytes_per_row;
+        let blank = Cell::blank(theme.style(Role::Text));
+        if start >= self.len() {
+            return Some(blank);
+        }
+        let part = self.columns.get(column).copied().unwrap_or(Part::Blank);
+        Some(match (part, self.byte_at(column, row)) {
+            (Part::Offset(digit), _) => {
+                let shift = 4 * (self.offset_digits() - 1 - digit);
+                let nibble = (start >> shift) & 0xF;
+                let symbol = char::from_digit(nibble as u32, 16).unwrap_or('0');
+                Cell::new(symbol, theme.style(Role::TextMuted))
+            }
+            (Part::Hex(_, nibble), Some(index)) => {
+                let value = (self.data()[index] >> (4 * nibble)) & 0xF;
+                let symbol = char::from_digit(u32::from(value), 16).unwrap_or('0');
+                Cell::new(symbol, self.byte_style(index, theme))
+            }
+            (Part::Ascii(_), Some(index)) => {
+                let byte = self.data()[index];
+                let printable = byte.is_ascii_graphic() || byte == b' ';
+                let symbol = if printable { char::from(byte) } else { '.' };
+                Cell::new(symbol, self.byte_style(index, theme))
+            }
+            (Part::Border, _) => Cell::new('│', theme.style(Role::Border)),
+            _ => blank,
+        })
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let Some((column, row)) = self.frame.local_position(event.x, event.y) else {
+            return false;
+        };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left) => {
+                let extend =
+                    event.modifiers.shift || event.kind == MouseEventKind::Drag(MouseButton::Left);
+                match self.byte_at(column, row) {
+                    Some(index) => self.move_cursor(index, extend),
+                    None => return false,
+                }
+            }
+            MouseEventKind::ScrollUp => self.scroll_to(self.first_row.saturating_sub(SCROLL_ROWS)),
+            MouseEventKind::ScrollDown => self.scroll_to(self.first_row + SCROLL_ROWS),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::event::Modifiers;
+
+    fn rows(view: &HexView<impl AsRef<[u8]>>, width: usize, height: usize) -> Vec<String> {
+        let theme = Theme::default();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| view.contents_of(x, y, &theme).unwrap().symbol)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_rows_to_width() {
+        let data = b"Hello, world!\n\x00\x01 yui hex view".to_vec();
+        let view = HexView::new(Container::new(Rect::new(80.0, 3.0)), data.clone());
+        assert_eq!(view.bytes_per_row(), 16);
+        assert_eq!(
+            rows(&view, 80, 3),
+            vec![
+                "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  │Hello, world!...│",
+                "00000010  20 79 75 69 20 68 65 78  20 76 69 65 77           │ yui hex view   │",
+                "",
+            ]
+        );
+        let narrow = HexView::new(Container::new(Rect::new(50.0, 3.0)), data.clone());
+        assert_eq!(narrow.bytes_per_row(), 8);
+        let tiny = HexView::new(Container::new(Rect::new(24.0, 3.0)), data);
+        assert_eq!(tiny.bytes_per_row(), 2);
+    }
+
+    #[test]
+    fn cursor_highlights_both_columns() {
+        let mut view = HexView::new(Container::new(Rect::new(50.0, 2.0)), b"abcdefghij".to_vec());
+        view.handle_key(Key::Right.into());
+        view.handle_key(KeyEvent::new(Key::Down, Modifiers::SHIFT));
+        assert_eq!(view.selected_bytes(), b"bcdefghij");
+        let theme = Theme::default();
+        let cursor = theme.style(Role::Selection).underline();
+        let hex = view.contents_of(14, 1, &theme).unwrap();
+        let ascii = view.contents_of(37, 1, &theme).unwrap();
+        assert_eq!(
+            (hex, ascii),
+            (Cell::new('a', cursor), Cell::new('j', cursor))
+        );
+        let selected = view.contents_of(13, 0, &theme).unwrap();
+        assert_eq!(selected, Cell::new('6', theme.style(Role::Selection)));
+        view.handle_key(Key::Esc.into());
+        assert_eq!(view.selection(), None);
+    }
+
+    #[test]
+    fn empty_buffers_select_nothing() {
+        let mut view = HexView::new(Container::new(Rect::new(50.0, 2.0)), Vec::new());
+        view.handle_key(KeyEvent::new(Key::Right, Modifiers::SHIFT));
+        view.handle_key(KeyEvent::new(Key::End, Modifiers::SHIFT));
+        assert_eq!((view.cursor(), view.selection()), (0, None));
+        assert!(view.selected_bytes().is_empty());
+        assert_eq!(rows(&view, 50, 2), vec!["", ""]);
+    }
+
+    #[test]
+    fn scrolls_large_buffers() {
+        let data = vec![0xAB_u8; 1 << 22];
+        let mut view =
+            HexView::new(Container::new(Rect::new(80.0, 4.0)), data).with_bytes_per_row(32);
+        view.handle_key(KeyEvent::new(Key::End, Modifiers::CTRL));
+        assert_eq!(view.cursor(), (1 << 22) - 1);
+        assert_eq!(view.first_row(), (1 << 17) - 4);
+        assert!(rows(&view, 80, 4)[3].starts_with("003fffe0  ab ab"));
+        view.handle_mouse(MouseEvent::new(MouseEventKind::ScrollUp, 0, 0));
+        assert_eq!(view.first_row(), (1 << 17) - 7);
+    }
+
+    #[test]
+    fn fits_rows_again_when_resized() {
+        let data: Vec<u8> = (0..64).collect();
+        let mut view = HexView::new(Container::new(Rect::new(50.0, 2.0)), data);
+        view.move_cursor(63, false);
+        assert_eq!((view.bytes_per_row(), view.first_row()), (8, 6));
+        view.set_area(Rect::new(80.0, 4.0));
+        assert_eq!((view.bytes_per_row(), view.first_row()), (16, 0));
+        assert!(rows(&view, 80, 4)[3].starts_with("00000030  30 31"));
+    }
+}
@@ -0,0 +1,743 @@
+use std::{
+    fmt::{self, Debug},
+    ops::Range,
+};
+
+use super::{
+    cell::Cell,
+    container::Container,
+    event::{Key, KeyEvent},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+const HISTORY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Typing,
+    Deleting,
+    Other,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Snapshot {
+    text: Vec<char>,
+    cursor: usize,
+}
+
+/// Text, cursor, selection and undo history shared by the input widgets.
+/// Positions are char indices into the text.
+#[derive(Debug, Clone, Default)]
+struct Buffer {
+    text: Vec<char>,
+    cursor: usize,
+    anchor: Option<usize>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: Option<Edit>,
+}
+
+fn is_word(symbol: char) -> bool {
+    symbol.is_alphanumeric() || symbol == '_'
+}
+
+impl Buffer {
+    fn value(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    fn set(&mut self, value: &str) {
+        self.text = value.chars().collect();
+        self.cursor = self.text.len();
+        self.anchor = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+    }
+
+    fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|&anchor| anchor != self.cursor)?;
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    fn move_to(&mut self, position: usize, extend: bool) {
+        if extend {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = position.min(self.text.len());
+        self.last_edit = None;
+    }
+
+    const fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    /// Records the current state before an edit. Consecutive edits of the
+    /// same kind are undone together.
+    fn checkpoint(&mut self, edit: Edit) {
+        if edit == Edit::Other || self.last_edit != Some(edit) {
+            if self.undo.len() == HISTORY {
+                self.undo.remove(0);
+            }
+            self.undo.push(self.snapshot());
+        }
+        self.redo.clear();
+        self.last_edit = Some(edit);
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            cursor: self.cursor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.text = snapshot.text;
+        self.cursor = snapshot.cursor;
+        self.anchor = None;
+        self.last_edit = None;
+    }
+
+    fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    fn replace(&mut self, range: Range<usize>, text: &str) {
+        self.cursor = range.start + text.chars().count();
+        self.text.splice(range, text.chars());
+        self.anchor = None;
+    }
+
+    fn insert(&mut self, text: &str) {
+        let single = text.chars().count() == 1 && text.chars().all(is_word);
+        let edit = if single && self.selection().is_none() {
+            Edit::Typing
+        } else {
+            Edit::Other
+        };
+        self.checkpoint(edit);
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        self.replace(range, text);
+    }
+
+    fn delete(&mut self, to: usize) {
+        let range = self
+            .selection()
+            .unwrap_or_else(|| to.min(self.cursor)..to.max(self.cursor));
+        if range.is_empty() {
+            return;
+        }
+        self.checkpoint(Edit::Deleting);
+        self.replace(range, "");
+    }
+
+    const fn previous_char(&self) -> usize {
+        self.cursor.saturating_sub(1)
+    }
+
+    fn next_char(&self) -> usize {
+        (self.cursor + 1).min(self.text.len())
+    }
+
+    fn previous_word(&self) -> usize {
+        let mut position = self.cursor;
+        while position > 0 && !is_word(self.text[position - 1]) {
+            position -= 1;
+        }
+        while position > 0 && is_word(self.text[position - 1]) {
+            position -= 1;
+        }
+        position
+    }
+
+    fn next_word(&self) -> usize {
+        let mut position = self.cursor;
+        while position < self.text.len() && !is_word(self.text[position]) {
+            position += 1;
+        }
+        while position < self.text.len() && is_word(self.text[position]) {
+            position += 1;
+        }
+        position
+    }
+
+    fn line_start(&self) -> usize {
+        self.text[..self.cursor]
+            .iter()
+            .rposition(|&c| c == '\n')
+            .map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self) -> usize {
+        self.text[self.cursor..]
+            .iter()
+            .position(|&c| c == '\n')
+            .map_or(self.text.len(), |i| self.cursor + i)
+    }
+
+    /// Handles the keys both widgets share. Returns whether the key was used
+    /// and whether the text changed.
+    fn handle_key(&mut self, event: KeyEvent) -> (bool, bool) {
+        let KeyEvent { key, modifiers } = event;
+        let (word, extend) = (modifiers.ctrl || modifiers.alt, modifiers.shift);
+        let before = self.text.len();
+        let changed = match key {
+            Key::Char('a') if modifiers.ctrl => {
+                self.select_all();
+                false
+            }
+            Key::Char('z') if modifiers.ctrl && modifiers.shift => self.redo(),
+            Key::Char('z') if modifiers.ctrl => self.undo(),
+            Key::Char('y') if modifiers.ctrl => self.redo(),
+            Key::Char(symbol) if !word => {
+                self.insert(&symbol.to_string());
+                true
+            }
+            Key::Backspace => {
+                let to = if word {
+                    self.previous_word()
+                } else {
+                    self.previous_char()
+                };
+                self.delete(to);
+                self.text.len() != before
+            }
+            Key::Delete => {
+                let to = if word {
+                    self.next_word()
+                } else {
+                    self.next_char()
+                };
+                self.delete(to);
+                self.text.len() != before
+            }
+            _ => {
+                let to = match key {
+                    Key::Left if word => self.previous_word(),
+                    Key::Left => self.previous_char(),
+                    Key::Right if word => self.next_word(),
+                    Key::Right => self.next_char(),
+                    Key::Home if modifiers.ctrl => 0,
+                    Key::Home => self.line_start(),
+                    Key::End if modifiers.ctrl => self.text.len(),
+                    Key::End => self.line_end(),
+                    _ => return (false, false),
+                };
+                self.move_to(to, extend);
+                false
+            }
+        };
+        (true, changed)
+    }
+}
+
+type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// A single line of editable text. The error message of a failed validation
+/// is shown on the second content row when there is one.
+pub struct TextInput {
+    frame: Container,
+    buffer: Buffer,
+    placeholder: Option<String>,
+    mask: Option<char>,
+    validator: Option<Validator>,
+    error: Option<String>,
+    offset: usize,
+}
+
+impl TextInput {
+    #[must_use]
+    pub fn new(frame: Container) -> Self {
+        Self {
+            frame,
+            buffer: Buffer::default(),
+            placeholder: None,
+            mask: None,
+            validator: None,
+            error: None,
+            offset: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    #[must_use]
+    pub fn with_placeholder(self, placeholder: impl Into<String>) -> Self {
+        Self {
+            placeholder: Some(placeholder.into()),
+            ..self
+        }
+    }
+
+    /// Shows every character as `mask`, e.g. `'*'` for passwords.
+    #[must_use]
+    pub fn with_mask(self, mask: char) -> Self {
+        Self {
+            mask: Some(mask),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validator = Some(Box::new(validator));
+        self.validate();
+        self
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.buffer.set(&value.replace('\n', " "));
+        self.validate();
+        self.scroll_to_cursor();
+    }
+
+    #[must_use]
+    pub fn value(&self) -> String {
+        self.buffer.value()
+    }
+
+    #[must_use]
+    pub const fn cursor(&self) -> usize {
+        self.buffer.cursor
+    }
+
+    #[must_use]
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.buffer.selection()
+    }
+
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    #[must_use]
+    pub const fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    #[must_use]
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    /// Enter and the vertical keys are left for the caller, e.g. to submit
+    /// or move the focus.
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let (handled, changed) = self.buffer.handle_key(event);
+        if changed {
+            self.validate();
+        }
+        self.scroll_to_cursor();
+        handled
+    }
+
+    fn validate(&mut self) {
+        let value = self.buffer.value();
+        self.error = self
+            .validator
+            .as_ref()
+            .and_then(|validator| validator(&value).err());
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let width = self.frame.content_size().0.max(1);
+        let cursor = self.buffer.cursor;
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if cursor >= self.offset + width {
+            self.offset = cursor + 1 - width;
+        }
+    }
+
+    fn text_cell(&self, x: usize, theme: &Theme) -> Cell {
+        if self.buffer.text.is_empty() {
+            let symbol = self
+                .placeholder
+                .as_ref()
+                .and_then(|placeholder| placeholder.chars().nth(x))
+                .unwrap_or(' ');
+            return Cell::new(symbol, theme.style(Role::TextMuted));
+        }
+        let index = self.offset + x;
+        let Some(&symbol) = self.buffer.text.get(index) else {
+            return Cell::blank(theme.style(Role::Text));
+        };
+        let selected = self.buffer.selection().is_some_and(|s| s.contains(&index));
+        let role = if selected {
+            Role::Selection
+        } else {
+            Role::Text
+        };
+        Cell::new(self.mask.unwrap_or(symbol), theme.style(role))
+    }
+}
+
+impl Widget for TextInput {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        Some(match (y, &self.error) {
+            (0, _) => self.text_cell(x, theme),
+            (1, Some(error)) => Cell::new(
+                error.chars().nth(x).unwrap_or(' '),
+                theme.style(Role::Error),
+            ),
+            _ => Cell::blank(theme.style(Role::Text)),
+        })
+    }
+
+    fn cursor_position(&self) -> Option<(usize, usize)> {
+        if !self.frame.is_focused() {
+            return None;
+        }
+        let (left, top) = self.frame.content_origin();
+        Some((left + self.buffer.cursor - self.offset, top))
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+}
+
+impl Debug for TextInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextInput")
+            .field("frame", &self.frame)
+            .field("buffer", &self.buffer)
+            .field("placeholder", &self.placeholder)
+            .field("mask", &self.mask)
+            .field("error", &self.error)
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A multi-line editor. Lines either wrap at word boundaries or scroll
+/// horizontally.
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    frame: Container,
+    buffer: Buffer,
+    wrap: bool,
+    rows: Vec<Range<usize>>,
+    row_offset: usize,
+    column_offset: usize,
+}
+
+impl TextArea {
+    #[must_use]
+    pub fn new(frame: Container) -> Self {
+        let mut area = Self {
+            frame,
+            buffer: Buffer::default(),
+            wrap: true,
+            rows: Vec::new(),
+            row_offset: 0,
+            column_offset: 0,
+        };
+        area.layout();
+        area
+    }
+
+    #[must_use]
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    #[must_use]
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self.layout();
+        self
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.buffer.set(value);
+        self.layout();
+    }
+
+    #[must_use]
+    pub fn value(&self) -> String {
+        self.buffer.value()
+    }
+
+    #[must_use]
+    pub fn lines(&self) -> usize {
+        self.buffer.text.iter().filter(|&&c| c == '\n').count() + 1
+    }
+
+    /// Line and column of the cursor, both counted in chars.
+    #[must_use]
+    pub fn cursor(&self) -> (usize, usize) {
+        let before = &self.buffer.text[..self.buffer.cursor];
+        let line = before.iter().filter(|&&c| c == '\n').count();
+        (line, self.buffer.cursor - self.buffer.line_start())
+    }
+
+    #[must_use]
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.buffer.selection()
+    }
+
+    #[must_use]
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let page = self.frame.content_size().1.max(1);
+        let extend = event.modifiers.shift;
+        let handled = match event.key {
+            Key::Enter => {
+                self.buffer.insert("\n");
+                true
+            }
+            Key::Up => self.move_rows(-1, extend),
+            Key::Down => self.move_rows(1, extend),
+            Key::PageUp => self.move_rows(-page.cast_signed(), extend),
+            Key::PageDown => self.move_rows(page.cast_signed(), extend),
+            _ => self.buffer.handle_key(event).0,
+        };
+        self.layout();
+        handled
+    }
+
+    fn row_of(&self, position: usize) -> usize {
+        self.rows
+            .partition_point(|row| row.start <= position)
+            .saturating_sub(1)
+    }
+
+    fn move_rows(&mut self, rows: isize, extend: bool) -> bool {
+        let current = self.row_of(self.buffer.cursor);
+        let column = self.buffer.cursor - self.rows[current].start;
+        let target = current.saturating_add_signed(rows).min(self.rows.len() - 1);
+        let row = &self.rows[target];
+        self.buffer
+            .move_to(row.start + column.min(row.len()), extend);
+        true
+    }
+
+    /// Splits the text into the rows shown on screen and scrolls the cursor
+    /// into view.
+    fn layout(&mut self) {
+        let (width, height) = self.frame.content_size();
+        let width = width.max(1);
+        let text = &self.buffer.text;
+        self.rows.clear();
+        let mut start = 0;
+        for line in text.split(|&c| c == '\n') {
+            let end = start + line.len();
+            let mut row_start = start;
+            while self.wrap && end - row_start > width {
+                let limit = row_start + width;
+                let split = text[row_start..limit]
+                    .iter()
+                    .rposition(|c| c.is_whitespace())
+                    .map_or(limit, |i| row_start + i + 1);
+                self.rows.push(row_start..split);
+                row_start = split;
+            }
+            self.rows.push(row_start..end);
+            if self.wrap && end - row_start == width {
+                self.rows.push(end..end);
+            }
+            start = end + 1;
+        }
+
+        let cursor = self.buffer.cursor;
+        let row = self.row_of(cursor);
+        let height = height.max(1);
+        if row < self.row_offset {
+            self.row_offset = row;
+        } else if row >= self.row_offset + height {
+            self.row_offset = row + 1 - height;
+        }
+        let column = cursor - self.rows[row].start;
+        if self.wrap || column < self.column_offset {
+            self.column_offset = if self.wrap { 0 } else { column };
+        } else if column >= self.column_offset + width {
+            self.column_offset = column + 1 - width;
+        }
+    }
+}
+
+impl Widget for TextArea {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let blank = Cell::blank(theme.style(Role::Text));
+        let Some(row) = self.rows.get(self.row_offset + y) else {
+            return Some(blank);
+        };
+        let index = row.start + self.column_offset + x;
+        if index >= row.end {
+            return Some(blank);
+        }
+        let selected = self.buffer.selection().is_some_and(|s| s.contains(&index));
+        let role = if selected {
+            Role::Selection
+        } else {
+            Role::Text
+        };
+        Some(Cell::new(self.buffer.text[index], theme.style(role)))
+    }
+
+    fn cursor_position(&self) -> Option<(usize, usize)> {
+        if !self.frame.is_focused() {
+            return None;
+        }
+        let (left, top) = self.frame.content_origin();
+        let row = self.row_of(self.buffer.cursor);
+        let column = self.buffer.cursor - self.rows[row].start;
+        Some((
+            left + column - self.column_offset,
+            top + row - self.row_offset,
+        ))
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rect::Rect, tui::event::Modifiers};
+
+    fn typed(widget: &mut TextInput, text: &str) {
+        for symbol in text.chars() {
+            widget.handle_key(Key::Char(symbol).into());
+        }
+    }
+
+    #[test]
+    fn undo_groups_typing() {
+        let mut input = TextInput::new(Container::new(Rect::new(20.0, 1.0)));
+        typed(&mut input, "hello world");
+        input.handle_key(KeyEvent::new(Key::Backspace, Modifiers::CTRL));
+        assert_eq!(input.value(), "hello ");
+        input.handle_key(KeyEvent::new(Key::Char('z'), Modifiers::CTRL));
+        assert_eq!(input.value(), "hello world");
+        input.handle_key(KeyEvent::new(Key::Char('z'), Modifiers::CTRL));
+        assert_eq!(input.value(), "hello ");
+        input.handle_key(KeyEvent::new(Key::Char('y'), Modifiers::CTRL));
+        assert_eq!(input.value(), "hello world");
+    }
+
+    #[test]
+    fn word_movement_and_selection() {
+        let mut input =
+            TextInput::new(Container::new(Rect::new(20.0, 1.0))).with_value("one two three");
+        input.handle_key(KeyEvent::new(Key::Left, Modifiers::CTRL));
+        assert_eq!(input.cursor(), 8);
+        let select_word = Modifiers {
+            ctrl: true,
+            shift: true,
+            ..Modifiers::NONE
+        };
+        input.handle_key(KeyEvent::new(Key::Left, select_word));
+        assert_eq!(input.selection(), Some(4..8));
+        typed(&mut input, "2 ");
+        assert_eq!(input.value(), "one 2 three");
+    }
+
+    #[test]
+    fn scrolls_and_masks() {
+        let mut frame = Container::new(Rect::new(4.0, 1.0));
+        frame.set_focused(true);
+        let input = TextInput::new(frame).with_mask('*').with_value("secret");
+        let theme = Theme::default();
+        let shown: String = (0..4)
+            .map(|x| input.contents_of(x, 0, &theme).unwrap().symbol)
+            .collect();
+        assert_eq!(shown, "*** ");
+        assert_eq!(input.cursor_position(), Some((3, 0)));
+    }
+
+    #[test]
+    fn validation_and_placeholder() {
+        let theme = Theme::default();
+        let mut input = TextInput::new(Container::new(Rect::new(10.0, 2.0)))
+            .with_placeholder("port")
+            .with_validator(|value| {
+                value
+                    .parse::<u16>()
+                    .map(drop)
+                    .map_err(|_| "not a port".to_string())
+            });
+        let cell = input.contents_of(0, 0, &theme).unwrap();
+        assert_eq!(cell, Cell::new('p', theme.style(Role::TextMuted)));
+        assert_eq!(input.error(), Some("not a port"));
+        assert_eq!(
+            input.contents_of(0, 1, &theme).unwrap().style,
+            theme.style(Role::Error)
+        );
+        typed(&mut input, "8080");
+        assert!(input.is_valid());
+    }
+
+    #[test]
+    fn text_area_wraps_and_moves_by_row() {
+        let mut frame = Container::new(Rect::new(6.0, 2.0));
+        frame.set_focused(true);
+        let mut area = TextArea::new(frame).with_value("alpha beta\ngamma");
+        assert_eq!(area.rows, vec![0..6, 6..10, 11..16]);
+        assert_eq!(area.cursor(), (1, 5));
+        assert_eq!(area.cursor_position(), Some((5, 1)));
+        area.handle_key(Key::Up.into());
+        assert_eq!(area.cursor(), (0, 10));
+        area.handle_key(Key::Up.into());
+        assert_eq!((area.cursor(), area.row_offset), ((0, 4), 0));
+
+        let mut area = area.with_wrap(false);
+        area.handle_key(KeyEvent::new(Key::End, Modifiers::CTRL));
+        area.handle_key(Key::Enter.into());
+        assert_eq!(area.lines(), 3);
+        area.handle_key(Key::Up.into());
+        area.handle_key(Key::End.into());
+        assert_eq!((area.cursor(), area.column_offset), ((1, 5), 0));
+        area.handle_key(Key::Up.into());
+        area.handle_key(Key::End.into());
+        assert_eq!((area.cursor(), area.column_offset), ((0, 10), 5));
+    }
+}
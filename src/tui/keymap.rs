@@ -0,0 +1,769 @@
+//! Bindings from key sequences to named actions.
+//!
+//! Bindings live in layers, each belonging to the global scope or to one
+//! widget and either to a single mode or to all of them. A keymap file is
+//! TOML with a table per scope; string values bind keys in every mode and
+//! nested tables bind them in one mode. An empty action removes a binding:
+//!
+//! ```toml
+//! [global]
+//! "Ctrl+Q" = "quit"
+//!
+//! [global.normal]
+//! "g g" = "go-to-top"
+//! "i" = "insert-mode"
+//!
+//! [global.insert]
+//! "Esc" = "normal-mode"
+//!
+//! [editor]
+//! "Ctrl+S" = "save"
+//! ```
+
+use std::{
+    fmt::{self, Display},
+    path::Path,
+    str::FromStr,
+};
+
+use glam::Vec2;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::TuiBorder,
+    event::{InvalidKey, Key, KeyEvent},
+    theme::{Role, Theme},
+    widget::{local_position, Widget},
+};
+
+const GLOBAL: &str = "global";
+const HELP_TITLE: &str = " Keys ";
+const HELP_TITLE_WIDTH: usize = HELP_TITLE.len();
+
+/// One or more keys pressed in turn, written like `g g` or `Ctrl+X Ctrl+S`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence(Vec<KeyEvent>);
+
+impl KeySequence {
+    #[must_use]
+    pub fn keys(&self) -> &[KeyEvent] {
+        &self.0
+    }
+}
+
+impl From<KeyEvent> for KeySequence {
+    fn from(event: KeyEvent) -> Self {
+        Self(vec![event])
+    }
+}
+
+impl From<Key> for KeySequence {
+    fn from(key: Key) -> Self {
+        Self::from(KeyEvent::from(key))
+    }
+}
+
+impl From<Vec<KeyEvent>> for KeySequence {
+    fn from(keys: Vec<KeyEvent>) -> Self {
+        Self(keys)
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = KeymapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err(KeymapError::InvalidKey(s.to_string()));
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Global,
+    /// Only active while the widget with this id has the focus.
+    Widget(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapOutcome {
+    Action(String),
+    /// The keys so far start a longer binding.
+    Pending,
+    /// No binding matched. Holds every key of the abandoned sequence so that
+    /// they can be passed on, e.g. as text typed in an insert mode.
+    Unbound(Vec<KeyEvent>),
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    scope: Scope,
+    mode: Option<String>,
+    bindings: Vec<(KeySequence, String)>,
+}
+
+/// Resolves key presses to actions.
+///
+/// The layers of the focused widget take precedence over global ones, and
+/// layers of the current mode over those for all modes. A binding fires as
+/// soon as its whole sequence is typed, so one that is a prefix of another
+/// shadows it.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    layers: Vec<Layer>,
+    descriptions: Vec<(String, String)>,
+    mode: String,
+    focus: Option<String>,
+    pending: Vec<KeyEvent>,
+}
+
+impl Keymap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            descriptions: Vec::new(),
+            mode: "normal".to_string(),
+            focus: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Binds `keys` globally in every mode.
+    #[must_use]
+    pub fn with_binding(mut self, keys: impl Into<KeySequence>, action: impl Into<String>) -> Self {
+        self.bind(Scope::Global, None, keys, action);
+        self
+    }
+
+    /// Text shown for `action` in the help overlay instead of its name.
+    #[must_use]
+    pub fn with_description(
+        mut self,
+        action: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let action = action.into();
+        self.descriptions.retain(|(a, _)| *a != action);
+        self.descriptions.push((action, description.into()));
+        self
+    }
+
+    /// Binds `keys` in `scope`, replacing what they were bound to there.
+    pub fn bind(
+        &mut self,
+        scope: Scope,
+        mode: Option<&str>,
+        keys: impl Into<KeySequence>,
+        action: impl Into<String>,
+    ) {
+        let keys = keys.into();
+        let action = action.into();
+        let layer = self.layer_mut(scope, mode);
+        match layer.bindings.iter_mut().find(|(k, _)| *k == keys) {
+            Some(binding) => binding.1 = action,
+            None => layer.bindings.push((keys, action)),
+        }
+    }
+
+    pub fn unbind(&mut self, scope: &Scope, mode: Option<&str>, keys: &KeySequence) {
+        for layer in &mut self.layers {
+            if layer.scope == *scope && layer.mode.as_deref() == mode {
+                layer.bindings.retain(|(k, _)| k != keys);
+            }
+        }
+    }
+
+    fn layer_mut(&mut self, scope: Scope, mode: Option<&str>) -> &mut Layer {
+        let index = self
+            .layers
+            .iter()
+            .position(|l| l.scope == scope && l.mode.as_deref() == mode)
+            .unwrap_or_else(|| {
+                self.layers.push(Layer {
+                    scope,
+                    mode: mode.map(str::to_string),
+                    bindings: Vec::new(),
+                });
+                self.layers.len() - 1
+            });
+        &mut self.layers[index]
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    /// Switches mode, dropping any half-typed sequence.
+    pub fn set_mode(&mut self, mode: impl Into<String>) {
+        self.mode = mode.into();
+        self.pending.clear();
+    }
+
+    #[must_use]
+    pub fn focus(&self) -> Option<&str> {
+        self.focus.as_deref()
+    }
+
+    /// Sets the widget whose bindings are active besides the global ones.
+    pub fn set_focus(&mut self, focus: Option<String>) {
+        self.focus = focus;
+        self.pending.clear();
+    }
+
+    /// The keys typed so far of a sequence that is not complete yet.
+    #[must_use]
+    pub fn pending(&self) -> &[KeyEvent] {
+        &self.pending
+    }
+
+    /// Active layers, most important first.
+    fn active_layers(&self) -> impl Iterator<Item = &Layer> {
+        let widget = self.focus.clone().map(Scope::Widget);
+        let rank = move |layer: &Layer| {
+            let scope = if layer.scope == Scope::Global {
+                Some(2)
+            } else if Some(&layer.scope) == widget.as_ref() {
+                Some(0)
+            } else {
+                None
+            };
+            let mode = match &layer.mode {
+                Some(mode) if *mode == self.mode => Some(0),
+                Some(_) => None,
+                None => Some(1),
+            };
+            Some(scope? + mode?)
+        };
+        let mut layers: Vec<_> = self
+            .layers
+            .iter()
+            .filter_map(|layer| Some((rank(layer)?, layer)))
+            .collect();
+        layers.sort_by_key(|(rank, _)| *rank);
+        layers.into_iter().map(|(_, layer)| layer)
+    }
+
+    /// Feeds one key into the pending sequence. When a chord breaks off,
+    /// the key that broke it is tried again as the start of a new sequence.
+    pub fn handle_key(&mut self, event: KeyEvent) -> KeymapOutcome {
+        self.pending.push(event);
+        match self.resolve_pending() {
+            KeymapOutcome::Unbound(typed) if typed.len() > 1 => {
+                self.pending.push(event);
+                match self.resolve_pending() {
+                    KeymapOutcome::Unbound(_) => KeymapOutcome::Unbound(typed),
+                    outcome => outcome,
+                }
+            }
+            outcome => outcome,
+        }
+    }
+
+    /// Resolves the typed keys layer by layer, so that the first layer that
+    /// binds them, or a longer sequence starting with them, decides.
+    fn resolve_pending(&mut self) -> KeymapOutcome {
+        let typed = self.pending.as_slice();
+        let outcome = self.active_layers().find_map(|layer| {
+            let action = layer.bindings.iter().find(|(keys, _)| keys.0 == typed);
+            if let Some((_, action)) = action {
+                Some(Some(action.clone()))
+            } else if layer
+                .bindings
+                .iter()
+                .any(|(keys, _)| keys.0.starts_with(typed))
+            {
+                Some(None)
+            } else {
+                None
+            }
+        });
+        match outcome {
+            Some(Some(action)) => {
+                self.pending.clear();
+                KeymapOutcome::Action(action)
+            }
+            Some(None) => KeymapOutcome::Pending,
+            None => KeymapOutcome::Unbound(std::mem::take(&mut self.pending)),
+        }
+    }
+
+    /// The bindings that currently apply, leaving out shadowed ones.
+    #[must_use]
+    pub fn active_bindings(&self) -> Vec<(&KeySequence, &str)> {
+        let mut bindings: Vec<(&KeySequence, &str)> = Vec::new();
+        for layer in self.active_layers() {
+            for (keys, action) in &layer.bindings {
+                if !bindings.iter().any(|(k, _)| *k == keys) {
+                    bindings.push((keys, action));
+                }
+            }
+        }
+        bindings
+    }
+
+    #[must_use]
+    pub fn description(&self, action: &str) -> Option<&str> {
+        self.descriptions
+            .iter()
+            .find(|(a, _)| a == action)
+            .map(|(_, description)| description.as_str())
+    }
+
+    /// A popup listing the active bindings, centred on `screen`.
+    #[must_use]
+    pub fn help(&self, screen: &Rect) -> KeymapHelp {
+        let entries = self
+            .active_bindings()
+            .into_iter()
+            .map(|(keys, action)| {
+                let text = self.description(action).unwrap_or(action);
+                (keys.to_string(), text.to_string())
+            })
+            .collect();
+        KeymapHelp::new(screen, entries)
+    }
+
+    /// Reads a keymap file, see [`Keymap::merge_toml`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeymapError::Io`] if the file can't be read, or any error
+    /// of [`Keymap::merge_toml`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeymapError> {
+        let source = std::fs::read_to_string(path).map_err(KeymapError::Io)?;
+        Self::from_toml(&source)
+    }
+
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Keymap::merge_toml`].
+    pub fn from_toml(source: &str) -> Result<Self, KeymapError> {
+        let mut keymap = Self::new();
+        keymap.merge_toml(source)?;
+        Ok(keymap)
+    }
+
+    /// Applies the bindings of a keymap file on top of the current ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for invalid TOML, a key that doesn't parse, or a
+    /// binding that isn't a string. Bindings read before the error stay.
+    pub fn merge_toml(&mut self, source: &str) -> Result<(), KeymapError> {
+        let table: toml::Table = source.parse().map_err(KeymapError::Toml)?;
+        for (scope, bindings) in &table {
+            let scope = if scope == GLOBAL {
+                Scope::Global
+            } else {
+                Scope::Widget(scope.clone())
+            };
+            let bindings = bindings
+                .as_table()
+                .ok_or_else(|| KeymapError::InvalidBinding(bindings.to_string()))?;
+            for (key, value) in bindings {
+                match value {
+                    toml::Value::Table(modal) => {
+                        for (keys, action) in modal {
+                            self.apply(scope.clone(), Some(key), keys, action)?;
+                        }
+                    }
+                    action => self.apply(scope.clone(), None, key, action)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        scope: Scope,
+        mode: Option<&str>,
+        keys: &str,
+        action: &toml::Value,
+    ) -> Result<(), KeymapError> {
+        let keys: KeySequence = keys.parse()?;
+        match action.as_str() {
+            Some("") => self.unbind(&scope, mode, &keys),
+            Some(action) => self.bind(scope, mode, keys, action),
+            None => return Err(KeymapError::InvalidBinding(action.to_string())),
+        }
+        Ok(())
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A bordered list of key sequences and what they do.
+#[derive(Debug, Clone)]
+pub struct KeymapHelp {
+    area: Rect,
+    entries: Vec<(String, String)>,
+    keys_width: usize,
+    offset: usize,
+}
+
+impl KeymapHelp {
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn new(screen: &Rect, entries: Vec<(String, String)>) -> Self {
+        let count = |text: &String| text.chars().count();
+        let keys_width = entries.iter().map(|(k, _)| count(k)).max().unwrap_or(0);
+        let text_width = entries.iter().map(|(_, t)| count(t)).max().unwrap_or(0);
+        let (position, width, height) = screen.pos_width_height();
+        let (width, height) = (width as usize, height as usize);
+        let columns = (keys_width + text_width + 6).clamp(10, width.max(10));
+        let rows = (entries.len() + 2).min(height.max(3));
+        let origin = position
+            + Vec2::new(
+                width.saturating_sub(columns) as f32 / 2.0,
+                height.saturating_sub(rows) as f32 / 2.0,
+            );
+        Self {
+            area: Rect::new(columns as f64, rows as f64).with_position(origin.floor()),
+            entries,
+            keys_width,
+            offset: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[(String, String)] {
+        &self.entries
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    const fn rows(&self) -> usize {
+        self.area.pos_width_height().2 as usize - 2
+    }
+
+    fn scroll_to(&mut self, offset: usize) {
+        self.offset = offset.min(self.entries.len().saturating_sub(self.rows()));
+    }
+
+    /// Scrolls with the arrow and page keys.
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let rows = self.rows();
+        match event.key {
+            Key::Up => self.scroll_to(self.offset.saturating_sub(1)),
+            Key::Down => self.scroll_to(self.offset + 1),
+            Key::PageUp => self.scroll_to(self.offset.saturating_sub(rows)),
+            Key::PageDown => self.scroll_to(self.offset + rows),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Widget for KeymapHelp {
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, row) = local_position(&self.area, x, y)?;
+        let (_, width, height) = self.area.pos_width_height();
+        let (right, bottom) = (width as usize - 1, height as usize - 1);
+        let symbols = TuiBorder::SmoothCorner.symbols();
+        let border = |symbol| Some(Cell::new(symbol, theme.style(Role::BorderFocused)));
+        match (column, row) {
+            (0, 0) => border(symbols.top_left),
+            (_, 0) if column == right => border(symbols.top_right),
+            (0, _) if row == bottom => border(symbols.bottom_left),
+            _ if (column, row) == (right, bottom) => border(symbols.bottom_right),
+            (1..=HELP_TITLE_WIDTH, 0) => {
+                let symbol = HELP_TITLE.chars().nth(column - 1).unwrap_or(' ');
+                Some(Cell::new(symbol, theme.style(Role::Title)))
+            }
+            (_, 0) => border(symbols.horizontal),
+            _ if row == bottom => border(symbols.horizontal),
+            (0, _) => border(symbols.vertical),
+            _ if column == right => border(symbols.vertical),
+            _ => {
+                let Some((keys, text)) = self.entries.get(self.offset + row - 1) else {
+                    return Some(Cell::blank(theme.style(Role::Text)));
+                };
+                let column = column - 1;
+                let text_start = self.keys_width + 3;
+                Some(if column < text_start {
+                    let symbol = column.checked_sub(1).and_then(|i| keys.chars().nth(i));
+                    Cell::new(symbol.unwrap_or(' '), theme.style(Role::Title))
+                } else {
+                    let symbol = text.chars().nth(column - text_start);
+                    Cell::new(symbol.unwrap_or(' '), theme.style(Role::Text))
+                })
+            }
+        }
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+}
+
+#[derive(Debug)]
+pub enum KeymapError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    InvalidKey(String),
+    InvalidBinding(String),
+}
+
+impl Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read keymap: {err}"),
+            Self::Toml(err) => write!(f, "could not parse keymap: {err}"),
+            Self::InvalidKey(key) => write!(f, "invalid key `{key}`"),
+            Self::InvalidBinding(binding) => write!(f, "invalid binding `{binding}`"),
+        }
+    }
+}
+
+impl From<InvalidKey> for KeymapError {
+    fn from(err: InvalidKey) -> Self {
+        Self::InvalidKey(err.0)
+    }
+}
+
+impl std::error::Error for KeymapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Toml(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::event::Modifiers;
+
+    fn keys(keymap: &mut Keymap, sequence: &str) -> Vec<KeymapOutcome> {
+        let sequence: KeySequence = sequence.parse().unwrap();
+        sequence
+            .keys()
+            .iter()
+            .map(|&key| keymap.handle_key(key))
+            .collect()
+    }
+
+    #[test]
+    fn parses_keys() {
+        let parse = |s: &str| s.parse::<KeyEvent>().unwrap();
+        assert_eq!(
+            parse("Ctrl+Shift+S"),
+            KeyEvent::new(
+                Key::Char('s'),
+                Modifiers {
+                    ctrl: true,
+                    shift: true,
+                    ..Modifiers::NONE
+                }
+            )
+        );
+        assert_eq!(parse("G"), Key::Char('G').into());
+        assert_eq!(
+            parse("ctrl++"),
+            KeyEvent::new(Key::Char('+'), Modifiers::CTRL)
+        );
+        assert_eq!(parse("Shift+Tab"), Key::BackTab.into());
+        assert_eq!(parse("f5"), Key::F(5).into());
+        for name in [
+            "Ctrl+S",
+            "Alt+PgDn",
+            "Space",
+            "g",
+            "G",
+            "Shift+Tab",
+            "Ctrl++",
+        ] {
+            assert_eq!(parse(name).to_string(), name);
+        }
+        assert!("Hyper+X".parse::<KeyEvent>().is_err());
+        assert!("".parse::<KeySequence>().is_err());
+    }
+
+    #[test]
+    fn resolves_chords_scopes_and_modes() {
+        let mut keymap = Keymap::new()
+            .with_binding("g g".parse::<KeySequence>().unwrap(), "top")
+            .with_binding(KeyEvent::new(Key::Char('s'), Modifiers::CTRL), "save-all");
+        keymap.bind(
+            Scope::Widget("editor".into()),
+            None,
+            KeyEvent::new(Key::Char('s'), Modifiers::CTRL),
+            "save",
+        );
+        keymap.bind(Scope::Global, Some("normal"), Key::Char('i'), "insert-mode");
+        keymap.bind(Scope::Global, Some("insert"), Key::Esc, "normal-mode");
+
+        assert_eq!(
+            keys(&mut keymap, "g g"),
+            [KeymapOutcome::Pending, KeymapOutcome::Action("top".into())]
+        );
+        assert_eq!(
+            keys(&mut keymap, "g x"),
+            [
+                KeymapOutcome::Pending,
+                KeymapOutcome::Unbound(vec![Key::Char('g').into(), Key::Char('x').into()])
+            ]
+        );
+        assert_eq!(
+            keys(&mut keymap, "Ctrl+S"),
+            [KeymapOutcome::Action("save-all".into())]
+        );
+        keymap.set_focus(Some("editor".into()));
+        assert_eq!(
+            keys(&mut keymap, "Ctrl+S"),
+            [KeymapOutcome::Action("save".into())]
+        );
+
+        assert_eq!(
+            keys(&mut keymap, "i"),
+            [KeymapOutcome::Action("insert-mode".into())]
+        );
+        keymap.set_mode("insert");
+        assert_eq!(
+            keys(&mut keymap, "i"),
+            [KeymapOutcome::Unbound(vec![Key::Char('i').into()])]
+        );
+        assert_eq!(
+            keys(&mut keymap, "Esc"),
+            [KeymapOutcome::Action("normal-mode".into())]
+        );
+    }
+
+    #[test]
+    fn broken_chord_retries_last_key() {
+        let mut keymap = Keymap::new()
+            .with_binding("g g".parse::<KeySequence>().unwrap(), "top")
+            .with_binding(KeyEvent::new(Key::Char('s'), Modifiers::CTRL), "save");
+        assert_eq!(
+            keys(&mut keymap, "g Ctrl+S"),
+            [KeymapOutcome::Pending, KeymapOutcome::Action("save".into())]
+        );
+        assert_eq!(
+            keys(&mut keymap, "g x g g"),
+            [
+                KeymapOutcome::Pending,
+                KeymapOutcome::Unbound(vec![Key::Char('g').into(), Key::Char('x').into()]),
+                KeymapOutcome::Pending,
+                KeymapOutcome::Action("top".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn loads_and_overrides_from_toml() {
+        let mut keymap = Keymap::from_toml(
+            r#"
+            [global]
+            "Ctrl+Q" = "quit"
+            "?" = "help"
+
+            [global.normal]
+            "g g" = "top"
+
+            [editor]
+            "Ctrl+S" = "save"
+            "#,
+        )
+        .unwrap();
+        keymap
+            .merge_toml("[global]\n\"Ctrl+Q\" = \"close\"\n\"?\" = \"\"")
+            .unwrap();
+        assert_eq!(
+            keys(&mut keymap, "Ctrl+Q"),
+            [KeymapOutcome::Action("close".into())]
+        );
+        assert_eq!(keys(&mut keymap, "?").len(), 1);
+        assert_eq!(
+            keymap.active_bindings().len(),
+            2,
+            "the editor scope is not focused and `?` was unbound"
+        );
+        assert!(matches!(
+            Keymap::from_toml("[global]\n\"Hyper+X\" = \"x\""),
+            Err(KeymapError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Keymap::from_toml("[global]\n\"x\" = 1"),
+            Err(KeymapError::InvalidBinding(_))
+        ));
+    }
+
+    #[test]
+    fn help_lists_active_bindings() {
+        let keymap = Keymap::new()
+            .with_binding("g g".parse::<KeySequence>().unwrap(), "top")
+            .with_binding(KeyEvent::new(Key::Char('q'), Modifiers::CTRL), "quit")
+            .with_description("quit", "Quit the app");
+        let help = keymap.help(&Rect::new(30.0, 6.0));
+        let theme = Theme::default();
+        let rows: Vec<String> = (0..6)
+            .map(|y| {
+                (0..30)
+                    .map(|x| help.contents_of(x, y, &theme).map_or(' ', |c| c.symbol))
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "",
+                "   ╭ Keys ────────────────╮",
+                "   │ g g     top          │",
+                "   │ Ctrl+Q  Quit the app │",
+                "   ╰──────────────────────╯",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn focused_chords_shadow_global_prefixes() {
+        let mut keymap = Keymap::new().with_binding(Key::Char('g'), "global-g");
+        keymap.bind(
+            Scope::Widget("list".into()),
+            None,
+            "g g".parse::<KeySequence>().unwrap(),
+            "top",
+        );
+        assert_eq!(
+            keys(&mut keymap, "g"),
+            [KeymapOutcome::Action("global-g".into())]
+        );
+        keymap.set_focus(Some("list".into()));
+        assert_eq!(
+            keys(&mut keymap, "g g"),
+            [KeymapOutcome::Pending, KeymapOutcome::Action("top".into())]
+        );
+    }
+}
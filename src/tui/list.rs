@@ -0,0 +1,330 @@
+use std::collections::BTreeSet;
+
+use ansi_term::Style;
+
+use super::{
+    cell::Cell,
+    container::Container,
+    event::{Key, KeyEvent},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListItem {
+    text: String,
+    style: Option<Style>,
+}
+
+impl ListItem {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            style: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_style(self, style: Style) -> Self {
+        Self {
+            style: Some(style),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl From<&str> for ListItem {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for ListItem {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    #[default]
+    Single,
+    Multiple,
+}
+
+#[derive(Debug, Clone)]
+pub struct List {
+    frame: Container,
+    items: Vec<ListItem>,
+    mode: SelectionMode,
+    cursor: Option<usize>,
+    selected: BTreeSet<usize>,
+    offset: usize,
+}
+
+impl List {
+    #[must_use]
+    pub fn new(frame: Container) -> Self {
+        Self {
+            frame,
+            items: Vec::new(),
+            mode: SelectionMode::default(),
+            cursor: None,
+            selected: BTreeSet::new(),
+            offset: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_items<T: Into<ListItem>>(mut self, items: impl IntoIterator<Item = T>) -> Self {
+        self.set_items(items);
+        self
+    }
+
+    #[must_use]
+    pub fn with_selection_mode(self, mode: SelectionMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    pub fn set_items<T: Into<ListItem>>(&mut self, items: impl IntoIterator<Item = T>) {
+        self.items = items.into_iter().map(Into::into).collect();
+        self.selected.clear();
+        self.offset = 0;
+        self.cursor = if self.items.is_empty() { None } else { Some(0) };
+    }
+
+    pub fn push(&mut self, item: impl Into<ListItem>) {
+        self.items.push(item.into());
+        if self.cursor.is_none() {
+            self.cursor = Some(0);
+        }
+    }
+
+    #[must_use]
+    pub fn items(&self) -> &[ListItem] {
+        &self.items
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    #[must_use]
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    #[must_use]
+    pub const fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// In single selection mode the selection is the item under the cursor.
+    #[must_use]
+    pub fn selected(&self) -> Vec<usize> {
+        match self.mode {
+            SelectionMode::Single => self.cursor.into_iter().collect(),
+            SelectionMode::Multiple => self.selected.iter().copied().collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_selected(&self, index: usize) -> bool {
+        match self.mode {
+            SelectionMode::Single => self.cursor == Some(index),
+            SelectionMode::Multiple => self.selected.contains(&index),
+        }
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index >= self.items.len() {
+            return;
+        }
+        self.cursor = Some(index);
+        if self.mode == SelectionMode::Multiple {
+            self.selected.insert(index);
+        }
+        self.scroll_to_cursor();
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let (SelectionMode::Multiple, Some(cursor)) = (self.mode, self.cursor) {
+            if !self.selected.remove(&cursor) {
+                self.selected.insert(cursor);
+            }
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+
+    pub fn select_next(&mut self) {
+        self.move_cursor_to(|cursor, len| (cursor + 1) % len);
+    }
+
+    pub fn select_previous(&mut self) {
+        self.move_cursor_to(|cursor, len| (cursor + len - 1) % len);
+    }
+
+    pub fn select_first(&mut self) {
+        self.move_cursor_to(|_, _| 0);
+    }
+
+    pub fn select_last(&mut self) {
+        self.move_cursor_to(|_, len| len - 1);
+    }
+
+    pub fn page_down(&mut self) {
+        let page = self.visible_rows().max(1);
+        self.move_cursor_to(|cursor, len| (cursor + page).min(len - 1));
+    }
+
+    pub fn page_up(&mut self) {
+        let page = self.visible_rows().max(1);
+        self.move_cursor_to(|cursor, _| cursor.saturating_sub(page));
+    }
+
+    fn move_cursor_to(&mut self, next: impl FnOnce(usize, usize) -> usize) {
+        if self.items.is_empty() {
+            return;
+        }
+        self.cursor = Some(next(self.cursor.unwrap_or(0), self.items.len()));
+        self.scroll_to_cursor();
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        match event.key {
+            Key::Up => self.select_previous(),
+            Key::Down => self.select_next(),
+            Key::Home => self.select_first(),
+            Key::End => self.select_last(),
+            Key::PageUp => self.page_up(),
+            Key::PageDown => self.page_down(),
+            Key::Char(' ') if self.mode == SelectionMode::Multiple => self.toggle_selected(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn visible_rows(&self) -> usize {
+        self.frame.content_size().1
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        let rows = self.visible_rows().max(1);
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if cursor >= self.offset + rows {
+            self.offset = cursor + 1 - rows;
+        }
+    }
+
+    fn marker(&self, index: usize) -> &'static str {
+        match (self.mode, self.selected.contains(&index)) {
+            (SelectionMode::Single, _) => "",
+            (SelectionMode::Multiple, true) => "[x] ",
+            (SelectionMode::Multiple, false) => "[ ] ",
+        }
+    }
+}
+
+impl Widget for List {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let index = self.offset + y;
+        let Some(item) = self.items.get(index) else {
+            return Some(Cell::blank(theme.style(Role::Text)));
+        };
+        let style = if self.cursor == Some(index) {
+            theme.style(Role::Selection)
+        } else {
+            item.style.unwrap_or_else(|| theme.style(Role::Text))
+        };
+        let symbol = self
+            .marker(index)
+            .chars()
+            .chain(item.text.chars())
+            .nth(x)
+            .unwrap_or(' ');
+        Some(Cell::new(symbol, style))
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rect::Rect, tui::container::TuiBorder};
+
+    fn list(rows: f64, items: usize) -> List {
+        let frame =
+            Container::new(Rect::new(20.0, rows + 2.0)).with_border(TuiBorder::SmoothCorner);
+        List::new(frame).with_items((0..items).map(|i| format!("item {i}")))
+    }
+
+    #[test]
+    fn navigation_wraps_around() {
+        let mut list = list(3.0, 4);
+        assert_eq!(list.cursor(), Some(0));
+        list.handle_key(Key::Up.into());
+        assert_eq!(list.cursor(), Some(3));
+        list.handle_key(Key::Down.into());
+        assert_eq!(list.cursor(), Some(0));
+    }
+
+    #[test]
+    fn keeps_cursor_visible() {
+        let mut list = list(3.0, 1_000_000);
+        list.select(500_000);
+        assert_eq!(list.offset(), 499_998);
+        list.select_first();
+        assert_eq!(list.offset(), 0);
+        list.page_down();
+        assert_eq!((list.cursor(), list.offset()), (Some(3), 1));
+
+        let theme = Theme::default();
+        let cell = list.contents_of(1, 3, &theme).unwrap();
+        assert_eq!(cell, Cell::new('i', theme.style(Role::Selection)));
+    }
+
+    #[test]
+    fn multiple_selection() {
+        let mut list = list(3.0, 4).with_selection_mode(SelectionMode::Multiple);
+        list.handle_key(Key::Char(' ').into());
+        list.select_next();
+        list.select_next();
+        list.handle_key(Key::Char(' ').into());
+        assert_eq!(list.selected(), vec![0, 2]);
+        list.handle_key(Key::Char(' ').into());
+        assert_eq!(list.selected(), vec![0]);
+    }
+}
@@ -0,0 +1,659 @@
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use unicode_width::UnicodeWidthStr;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::Container,
+    event::{Key, KeyEvent, MouseEvent, MouseEventKind},
+    text::{Span, Text, TextLine},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+const SCROLL_LINES: isize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+    /// Time since the capture was created.
+    pub elapsed: Duration,
+}
+
+impl LogRecord {
+    fn to_line(&self) -> TextLine {
+        TextLine::new()
+            .with_span(Span::themed(
+                format!("{:>8.3} ", self.elapsed.as_secs_f64()),
+                Role::TextMuted,
+            ))
+            .with_span(Span::themed(
+                format!("{:<5} ", self.level),
+                level_role(self.level),
+            ))
+            .with_span(Span::themed(format!("{} ", self.target), Role::TextMuted))
+            .with_span(self.message.as_str())
+    }
+}
+
+/// The theme role a level's name is drawn with.
+#[must_use]
+pub const fn level_role(level: Level) -> Role {
+    match level {
+        Level::Error => Role::Error,
+        Level::Warn => Role::Warning,
+        Level::Info => Role::LogInfo,
+        Level::Debug => Role::LogDebug,
+        Level::Trace => Role::LogTrace,
+    }
+}
+
+#[derive(Debug)]
+struct Ring {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    /// Number of records ever pushed, so viewers can tell when to refresh.
+    /// The records still held are the last ones of these.
+    pushed: u64,
+}
+
+/// A bounded buffer of log records shared between a [`LogCapture`] and any
+/// number of [`LogView`]s. Once full, the oldest records are dropped.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<Ring>>,
+}
+
+impl LogBuffer {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Ring {
+                records: VecDeque::with_capacity(capacity),
+                capacity: capacity.max(1),
+                pushed: 0,
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Ring> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn push(&self, record: LogRecord) {
+        let mut inner = self.lock();
+        if inner.records.len() == inner.capacity {
+            inner.records.pop_front();
+        }
+        inner.records.push_back(record);
+        inner.pushed += 1;
+    }
+
+    /// A copy of the buffered records, oldest first.
+    #[must_use]
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.lock().records.iter().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().records.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    pub fn clear(&self) {
+        self.lock().records.clear();
+    }
+
+    /// The records pushed after the first `seen` ones that are still held,
+    /// with the number of records ever pushed and the number dropped since.
+    fn records_since(&self, seen: u64) -> (Vec<LogRecord>, u64, u64) {
+        let inner = self.lock();
+        let dropped = inner.pushed - inner.records.len() as u64;
+        let skip = usize::try_from(seen.saturating_sub(dropped)).unwrap_or(usize::MAX);
+        let records = inner.records.iter().skip(skip).cloned().collect();
+        (records, inner.pushed, dropped)
+    }
+}
+
+/// A [`Log`] implementation that stores records in a [`LogBuffer`] instead
+/// of writing to stderr, which would corrupt the screen.
+#[derive(Debug)]
+pub struct LogCapture {
+    buffer: LogBuffer,
+    level: LevelFilter,
+    start: Instant,
+}
+
+impl LogCapture {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer: LogBuffer::new(capacity),
+            level: LevelFilter::Trace,
+            start: Instant::now(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_level(self, level: LevelFilter) -> Self {
+        Self { level, ..self }
+    }
+
+    #[must_use]
+    pub const fn buffer(&self) -> &LogBuffer {
+        &self.buffer
+    }
+
+    /// Installs the capture as the global logger and returns its buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a logger was already installed.
+    pub fn install(self) -> Result<LogBuffer, SetLoggerError> {
+        let buffer = self.buffer.clone();
+        let level = self.level;
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(level);
+        Ok(buffer)
+    }
+}
+
+impl Log for LogCapture {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        self.buffer.push(LogRecord {
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            elapsed: self.start.elapsed(),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Shows the records of a [`LogBuffer`], newest at the bottom.
+///
+/// Records can be filtered by level and target. While following, the view
+/// stays scrolled to the newest record; scrolling up stops following and
+/// `End` resumes it. Call [`LogView::refresh`] before drawing to pick up
+/// new records, which are wrapped and appended without touching the lines
+/// already shown.
+#[derive(Debug)]
+pub struct LogView {
+    frame: Container,
+    buffer: LogBuffer,
+    seen: Option<u64>,
+    dropped: u64,
+    /// The number and line count of every record shown.
+    shown: VecDeque<(u64, usize)>,
+    level: LevelFilter,
+    target: Option<String>,
+    follow: bool,
+    lines: Vec<TextLine>,
+    query: String,
+    matches: Vec<(usize, Range<usize>)>,
+    current_match: Option<usize>,
+}
+
+impl LogView {
+    #[must_use]
+    pub fn new(frame: Container, buffer: LogBuffer) -> Self {
+        let mut view = Self {
+            frame,
+            buffer,
+            seen: None,
+            dropped: 0,
+            shown: VecDeque::new(),
+            level: LevelFilter::Trace,
+            target: None,
+            follow: true,
+            lines: Vec::new(),
+            query: String::new(),
+            matches: Vec::new(),
+            current_match: None,
+        };
+        view.refresh();
+        view
+    }
+
+    #[must_use]
+    pub fn with_level(mut self, level: LevelFilter) -> Self {
+        self.set_level(level);
+        self
+    }
+
+    #[must_use]
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.set_target(Some(target.into()));
+        self
+    }
+
+    #[must_use]
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    /// The frame, for styling. Resize it with [`Self::set_area`] instead, so
+    /// that the records are wrapped to the new width.
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    /// Moves or resizes the frame and wraps the records to it again.
+    pub fn set_area(&mut self, area: Rect) {
+        self.frame.set_area(area);
+        self.rebuild();
+    }
+
+    #[must_use]
+    pub const fn level(&self) -> LevelFilter {
+        self.level
+    }
+
+    /// Hides records less severe than `level`.
+    pub fn set_level(&mut self, level: LevelFilter) {
+        self.level = level;
+        self.rebuild();
+    }
+
+    #[must_use]
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// Only shows records whose target starts with `target`, such as a crate
+    /// or module path.
+    pub fn set_target(&mut self, target: Option<String>) {
+        self.target = target;
+        self.rebuild();
+    }
+
+    #[must_use]
+    pub const fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    pub fn set_follow(&mut self, follow: bool) {
+        self.follow = follow;
+        if follow {
+            self.frame.scroll_to(usize::MAX);
+        }
+    }
+
+    /// Number of lines shown after filtering and wrapping.
+    #[must_use]
+    pub const fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Picks up records logged since the last refresh. When old records
+    /// were dropped, the view is built again and keeps showing the same
+    /// lines.
+    pub fn refresh(&mut self) {
+        let seen = self.seen.unwrap_or(0);
+        let (records, pushed, dropped) = self.buffer.records_since(seen);
+        if self.seen == Some(pushed) && dropped == self.dropped {
+            return;
+        }
+        if self.seen.is_some() && dropped != self.dropped {
+            let removed: usize = self
+                .shown
+                .iter()
+                .take_while(|(number, _)| *number < dropped)
+                .map(|(_, lines)| lines)
+                .sum();
+            self.rebuild_at(self.frame.scroll_offset().saturating_sub(removed));
+        } else {
+            self.seen = Some(pushed);
+            self.dropped = dropped;
+            self.append(pushed - records.len() as u64, records);
+        }
+    }
+
+    fn shows(&self, record: &LogRecord) -> bool {
+        record.level <= self.level
+            && self
+                .target
+                .as_ref()
+                .is_none_or(|target| record.target.starts_with(target.as_str()))
+    }
+
+    /// Wraps and shows the records numbered from `first` on that pass the
+    /// filters.
+    fn append(&mut self, first: u64, records: Vec<LogRecord>) {
+        let width = self.frame.content_size().0;
+        let start = self.lines.len();
+        for (number, record) in (first..).zip(records) {
+            if !self.shows(&record) {
+                continue;
+            }
+            let text = Text::from(record.to_line()).wrapped(width);
+            self.shown.push_back((number, text.lines().len()));
+            self.lines.extend_from_slice(text.lines());
+        }
+        self.frame
+            .extend_content(self.lines[start..].iter().cloned());
+        if self.follow {
+            self.frame.scroll_to(usize::MAX);
+        }
+        self.find_from(start);
+    }
+
+    fn rebuild(&mut self) {
+        self.rebuild_at(self.frame.scroll_offset());
+    }
+
+    /// Shows every record again, scrolled to `offset` unless following.
+    fn rebuild_at(&mut self, offset: usize) {
+        let (records, pushed, dropped) = self.buffer.records_since(0);
+        self.seen = Some(pushed);
+        self.dropped = dropped;
+        self.shown.clear();
+        self.lines.clear();
+        self.frame.set_content(Text::new());
+        self.append(dropped, records);
+        if !self.follow {
+            self.frame.scroll_to(offset);
+        }
+        let query = std::mem::take(&mut self.query);
+        self.find(&query);
+    }
+
+    fn find(&mut self, query: &str) {
+        query.clone_into(&mut self.query);
+        self.matches.clear();
+        self.current_match = None;
+        self.find_from(0);
+    }
+
+    /// Adds the matches of the current query from line `start` on.
+    fn find_from(&mut self, start: usize) {
+        if self.query.is_empty() {
+            return;
+        }
+        for (row, line) in self.lines.iter().enumerate().skip(start) {
+            let line = line.to_string();
+            for (at, _) in line.match_indices(self.query.as_str()) {
+                let start = line[..at].width();
+                self.matches.push((row, start..start + self.query.width()));
+            }
+        }
+    }
+
+    /// Highlights every occurrence of `query` and jumps to the last one.
+    pub fn search(&mut self, query: &str) {
+        self.find(query);
+        if let Some(last) = self.matches.len().checked_sub(1) {
+            self.show_match(last);
+        }
+    }
+
+    #[must_use]
+    pub const fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    pub fn next_match(&mut self) {
+        if let Some(current) = self.current_match {
+            self.show_match((current + 1) % self.matches.len());
+        }
+    }
+
+    pub fn previous_match(&mut self) {
+        if let Some(current) = self.current_match {
+            let count = self.matches.len();
+            self.show_match((current + count - 1) % count);
+        }
+    }
+
+    fn show_match(&mut self, index: usize) {
+        let row = self.matches[index].0;
+        self.current_match = Some(index);
+        self.follow = false;
+        let (offset, height) = (self.frame.scroll_offset(), self.frame.content_size().1);
+        if row < offset || row >= offset + height {
+            self.frame.scroll_to(row.saturating_sub(height / 2));
+        }
+    }
+
+    fn scroll_by(&mut self, lines: isize) {
+        self.frame.scroll_by(lines);
+        let bottom = self.lines.len().saturating_sub(self.frame.content_size().1);
+        self.follow = self.frame.scroll_offset() >= bottom;
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let page = self.frame.content_size().1.max(1).cast_signed();
+        match event.key {
+            Key::Up => self.scroll_by(-1),
+            Key::Down => self.scroll_by(1),
+            Key::PageUp => self.scroll_by(-page),
+            Key::PageDown => self.scroll_by(page),
+            Key::Home => {
+                self.follow = false;
+                self.frame.scroll_to(0);
+            }
+            Key::End => self.set_follow(true),
+            Key::Char('n') => self.next_match(),
+            Key::Char('N') => self.previous_match(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Widget for LogView {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let mut cell = self.frame.contents_of(x, y, theme)?;
+        let Some((column, row)) = self.frame.local_position(x, y) else {
+            return Some(cell);
+        };
+        let line = row + self.frame.scroll_offset();
+        let found = self
+            .matches
+            .iter()
+            .position(|(r, columns)| *r == line && columns.contains(&column));
+        if let Some(index) = found {
+            cell.style = if Some(index) == self.current_match {
+                theme.style(Role::Selection)
+            } else {
+                theme.style(Role::Warning).reverse()
+            };
+        }
+        Some(cell)
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if self.frame.local_position(event.x, event.y).is_none() {
+            return false;
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp => self.scroll_by(-SCROLL_LINES),
+            MouseEventKind::ScrollDown => self.scroll_by(SCROLL_LINES),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(capture: &LogCapture, level: Level, target: &str, message: &str) {
+        capture.log(
+            &Record::builder()
+                .level(level)
+                .target(target)
+                .args(format_args!("{message}"))
+                .build(),
+        );
+    }
+
+    fn messages(view: &LogView) -> Vec<String> {
+        view.lines
+            .iter()
+            .map(|line| line.spans()[3].content().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn capture_is_bounded() {
+        let capture = LogCapture::new(3).with_level(LevelFilter::Info);
+        for i in 0..5 {
+            log(&capture, Level::Info, "yui", &format!("record {i}"));
+        }
+        log(&capture, Level::Debug, "yui", "too verbose");
+        let records = capture.buffer().records();
+        let messages: Vec<_> = records.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["record 2", "record 3", "record 4"]);
+        assert_eq!(records[0].target, "yui");
+    }
+
+    #[test]
+    fn filters_by_level_and_target() {
+        let capture = LogCapture::new(10);
+        log(&capture, Level::Warn, "yui::rect", "divided into one");
+        log(&capture, Level::Debug, "yui::tui", "drawn");
+        log(&capture, Level::Error, "app", "failed");
+        let mut view = LogView::new(
+            Container::new(Rect::new(60.0, 5.0)),
+            capture.buffer().clone(),
+        )
+        .with_level(LevelFilter::Warn);
+        assert_eq!(messages(&view), ["divided into one", "failed"]);
+        view.set_target(Some("yui".to_string()));
+        assert_eq!(messages(&view), ["divided into one"]);
+
+        let theme = Theme::default();
+        let level = view.contents_of(9, 0, &theme).unwrap();
+        assert_eq!(level, Cell::new('W', theme.style(Role::Warning)));
+        let target = view.contents_of(15, 0, &theme).unwrap();
+        assert_eq!(target, Cell::new('y', theme.style(Role::TextMuted)));
+    }
+
+    #[test]
+    fn follows_the_tail() {
+        let capture = LogCapture::new(100);
+        let mut view = LogView::new(
+            Container::new(Rect::new(60.0, 3.0)),
+            capture.buffer().clone(),
+        );
+        for i in 0..10 {
+            log(&capture, Level::Info, "app", &format!("record {i}"));
+        }
+        view.refresh();
+        assert_eq!(view.frame().scroll_offset(), 7);
+        view.handle_key(Key::Up.into());
+        assert!(!view.is_following());
+        log(&capture, Level::Info, "app", "record 10");
+        view.refresh();
+        assert_eq!(view.frame().scroll_offset(), 6);
+        view.handle_key(Key::End.into());
+        assert!(view.is_following());
+        assert_eq!(view.frame().scroll_offset(), 8);
+    }
+
+    #[test]
+    fn search_highlights_matches() {
+        let capture = LogCapture::new(100);
+        for i in 0..10 {
+            log(&capture, Level::Info, "app", &format!("record {i}"));
+        }
+        let mut view = LogView::new(
+            Container::new(Rect::new(60.0, 3.0)),
+            capture.buffer().clone(),
+        );
+        view.search("record 1");
+        assert_eq!(view.match_count(), 1);
+        assert!(!view.is_following());
+        assert_eq!(view.frame().scroll_offset(), 0);
+
+        let theme = Theme::default();
+        let line = view.lines[1].to_string();
+        let column = line.find("record").unwrap();
+        let cell = view.contents_of(column, 1, &theme).unwrap();
+        assert_eq!(cell, Cell::new('r', theme.style(Role::Selection)));
+    }
+
+    #[test]
+    fn dropping_old_records_keeps_the_view_still() {
+        let capture = LogCapture::new(6);
+        for i in 0..6 {
+            log(&capture, Level::Info, "app", &format!("record {i}"));
+        }
+        let mut view = LogView::new(
+            Container::new(Rect::new(60.0, 2.0)),
+            capture.buffer().clone(),
+        );
+        view.handle_key(Key::Up.into());
+        view.handle_key(Key::Up.into());
+        assert_eq!(view.frame().scroll_offset(), 2);
+        log(&capture, Level::Info, "app", "record 6");
+        view.refresh();
+        assert_eq!(messages(&view)[0], "record 1");
+        assert_eq!(view.frame().scroll_offset(), 1);
+
+        log(&capture, Level::Debug, "app", "record 7");
+        view.search("record");
+        view.set_follow(true);
+        log(&capture, Level::Info, "app", "record 8");
+        view.refresh();
+        assert_eq!(messages(&view).last().unwrap(), "record 8");
+        assert_eq!((view.line_count(), view.match_count()), (6, 6));
+        assert_eq!(view.frame().line_count(), 6);
+    }
+
+    #[test]
+    fn resizing_wraps_the_records_again() {
+        let capture = LogCapture::new(10);
+        for i in 0..3 {
+            log(&capture, Level::Info, "app", &format!("record {i}"));
+        }
+        let mut view = LogView::new(
+            Container::new(Rect::new(20.0, 3.0)),
+            capture.buffer().clone(),
+        );
+        assert_eq!(view.line_count(), 6);
+        view.set_area(Rect::new(60.0, 3.0));
+        assert_eq!((view.line_count(), view.frame().line_count()), (3, 3));
+        view.search("record 2");
+        assert_eq!(view.match_count(), 1);
+
+        let theme = Theme::default();
+        let column = view.lines[2].to_string().find("record").unwrap();
+        let cell = view.contents_of(column, 2, &theme).unwrap();
+        assert_eq!(cell, Cell::new('r', theme.style(Role::Selection)));
+    }
+}
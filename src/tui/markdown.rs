@@ -0,0 +1,602 @@
+use ansi_term::{Colour, Style};
+use pulldown_cmark::{
+    Alignment as ColumnAlignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd,
+};
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::{Container, TuiBorder},
+    event::{Key, KeyEvent, MouseEvent, MouseEventKind},
+    text::{layered, Span, Text, TextLine},
+    theme::Theme,
+    widget::Widget,
+};
+
+const BULLETS: [&str; 3] = ["• ", "◦ ", "▪ "];
+const SCROLL_LINES: isize = 3;
+
+fn code_style() -> Style {
+    Colour::Cyan.normal()
+}
+
+fn link_style() -> Style {
+    Colour::Blue.underline()
+}
+
+fn muted_style() -> Style {
+    Style::new().dimmed()
+}
+
+fn heading_style(level: HeadingLevel) -> Style {
+    match level {
+        HeadingLevel::H1 => Colour::Purple.bold(),
+        HeadingLevel::H2 => Colour::Blue.bold(),
+        _ => Style::new().bold(),
+    }
+}
+
+/// What nested blocks put in front of their lines: a list marker on the
+/// first line and indentation after it, or a quote bar on every line.
+#[derive(Debug)]
+struct Prefix {
+    first: String,
+    rest: String,
+    style: Option<Style>,
+    used: bool,
+}
+
+#[derive(Debug)]
+struct TableBuffer {
+    alignments: Vec<ColumnAlignment>,
+    rows: Vec<Vec<TextLine>>,
+}
+
+/// Turns parser events into lines no wider than `width`.
+#[derive(Debug)]
+struct Renderer {
+    width: usize,
+    text: Text,
+    inline: TextLine,
+    styles: Vec<Style>,
+    prefixes: Vec<Prefix>,
+    lists: Vec<Option<u64>>,
+    links: Vec<(String, usize)>,
+    code: Option<(String, String)>,
+    table: Option<TableBuffer>,
+    pending_blank: bool,
+}
+
+impl Renderer {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            text: Text::new(),
+            inline: TextLine::new(),
+            styles: Vec::new(),
+            prefixes: Vec::new(),
+            lists: Vec::new(),
+            links: Vec::new(),
+            code: None,
+            table: None,
+            pending_blank: false,
+        }
+    }
+
+    /// Room left for content once the prefixes are in place.
+    fn available(&self) -> usize {
+        let prefixes: usize = self.prefixes.iter().map(|p| p.rest.chars().count()).sum();
+        self.width.saturating_sub(prefixes).max(1)
+    }
+
+    fn push_style(&mut self, style: Style) {
+        let base = self.styles.last().copied().unwrap_or_default();
+        self.styles.push(layered(base, style));
+    }
+
+    fn push_text(&mut self, text: &str, style: Option<Style>) {
+        let base = self.styles.last().copied();
+        let style = match (base, style) {
+            (Some(base), Some(style)) => Some(layered(base, style)),
+            (base, style) => style.or(base),
+        };
+        self.inline.push(Span::new(text, style));
+    }
+
+    fn emit(&mut self, content: &TextLine) {
+        let mut line = TextLine::new();
+        if std::mem::take(&mut self.pending_blank) && !self.text.lines().is_empty() {
+            for prefix in self.prefixes.iter().filter(|prefix| prefix.used) {
+                let rest = prefix.rest.trim_end();
+                line.push(Span::new(rest, prefix.style));
+            }
+            self.text.push_line(std::mem::take(&mut line));
+        }
+        for prefix in &mut self.prefixes {
+            let symbols = if prefix.used {
+                &prefix.rest
+            } else {
+                &prefix.first
+            };
+            line.push(Span::new(symbols.as_str(), prefix.style));
+            prefix.used = true;
+        }
+        for span in content.spans() {
+            line.push(span.clone());
+        }
+        self.text.push_line(line);
+    }
+
+    fn flush_inline(&mut self) {
+        let inline = std::mem::take(&mut self.inline);
+        if inline.spans().is_empty() {
+            return;
+        }
+        for line in Text::from(inline).wrapped(self.available()).lines() {
+            self.emit(line);
+        }
+    }
+
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code {
+                Some((_, body)) => body.push_str(&text),
+                None => self.push_text(&text, None),
+            },
+            Event::Code(code) | Event::InlineMath(code) | Event::DisplayMath(code) => {
+                self.push_text(&code, Some(code_style()));
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                self.push_text(html.trim_end_matches('\n'), None);
+            }
+            Event::FootnoteReference(label) => self.push_text(&format!("[{label}]"), None),
+            Event::SoftBreak => self.push_text(" ", None),
+            Event::HardBreak => self.flush_inline(),
+            Event::Rule => {
+                self.flush_inline();
+                let rule = "─".repeat(self.available());
+                self.emit(&Span::styled(rule, muted_style()).into());
+                self.pending_blank = true;
+            }
+            Event::TaskListMarker(done) => {
+                self.push_text(if done { "[x] " } else { "[ ] " }, None);
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush_inline();
+                self.push_style(heading_style(level));
+            }
+            Tag::BlockQuote(_) => {
+                self.flush_inline();
+                self.prefixes.push(Prefix {
+                    first: "│ ".to_string(),
+                    rest: "│ ".to_string(),
+                    style: Some(muted_style()),
+                    used: false,
+                });
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush_inline();
+                let language = match kind {
+                    CodeBlockKind::Fenced(language) => language.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code = Some((language, String::new()));
+            }
+            Tag::List(start) => {
+                self.flush_inline();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush_inline();
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => BULLETS[depth % BULLETS.len()].to_string(),
+                };
+                self.prefixes.push(Prefix {
+                    rest: " ".repeat(marker.chars().count()),
+                    first: marker,
+                    style: None,
+                    used: false,
+                });
+            }
+            Tag::Table(alignments) => {
+                self.flush_inline();
+                self.table = Some(TableBuffer {
+                    alignments,
+                    rows: Vec::new(),
+                });
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::Emphasis => self.push_style(Style::new().italic()),
+            Tag::Strong => self.push_style(Style::new().bold()),
+            Tag::Strikethrough => self.push_style(Style::new().strikethrough()),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.links
+                    .push((dest_url.to_string(), self.inline.to_string().len()));
+                self.push_style(link_style());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::HtmlBlock => {
+                self.flush_inline();
+                self.pending_blank = true;
+            }
+            TagEnd::Heading(level) => {
+                self.styles.pop();
+                let heading = Text::from(std::mem::take(&mut self.inline));
+                let heading = heading.wrapped(self.available());
+                let width = heading.lines().iter().map(TextLine::width).max();
+                for line in heading.lines() {
+                    self.emit(line);
+                }
+                let underline = match level {
+                    HeadingLevel::H1 => "━",
+                    HeadingLevel::H2 => "─",
+                    _ => "",
+                };
+                if !underline.is_empty() {
+                    let underline = underline.repeat(width.unwrap_or(0));
+                    self.emit(&Span::styled(underline, heading_style(level)).into());
+                }
+                self.pending_blank = true;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush_inline();
+                self.prefixes.pop();
+                self.pending_blank = true;
+            }
+            TagEnd::CodeBlock => {
+                if let Some((language, body)) = self.code.take() {
+                    self.code_block(&language, &body);
+                }
+                self.pending_blank = true;
+            }
+            TagEnd::List(_) => {
+                self.flush_inline();
+                self.lists.pop();
+                self.pending_blank = self.lists.is_empty();
+            }
+            TagEnd::Item => {
+                self.flush_inline();
+                self.prefixes.pop();
+            }
+            TagEnd::TableCell => {
+                let cell = std::mem::take(&mut self.inline);
+                if let Some(row) = self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                    row.push(cell);
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.table_block(&table);
+                }
+                self.pending_blank = true;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                if let Some((url, start)) = self.links.pop() {
+                    let shown = self.inline.to_string();
+                    if !url.is_empty() && shown.get(start..) != Some(url.as_str()) {
+                        self.push_text(&format!(" ({url})"), Some(muted_style()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn code_block(&mut self, language: &str, body: &str) {
+        let symbols = TuiBorder::SmoothCorner.symbols();
+        let border = muted_style();
+        let inner = self.available().saturating_sub(4).max(1);
+        let horizontal = |count: usize| symbols.horizontal.to_string().repeat(count);
+        let label: String = language.chars().take(inner).collect();
+        let top = format!(
+            "{}{}{label}{}{}",
+            symbols.top_left,
+            symbols.horizontal,
+            horizontal(inner + 1 - label.chars().count()),
+            symbols.top_right
+        );
+        self.emit(&Span::styled(top, border).into());
+        for line in body.trim_end_matches('\n').split('\n') {
+            let line = TextLine::from(line.replace('\t', "    ")).columns(0..inner);
+            let padding = " ".repeat(inner - line.width());
+            let mut row =
+                TextLine::new().with_span(Span::styled(format!("{} ", symbols.vertical), border));
+            for span in line.spans() {
+                row.push(Span::styled(span.content(), code_style()));
+            }
+            row.push(padding);
+            row.push(Span::styled(format!(" {}", symbols.vertical), border));
+            self.emit(&row);
+        }
+        let bottom = format!(
+            "{}{}{}",
+            symbols.bottom_left,
+            horizontal(inner + 2),
+            symbols.bottom_right
+        );
+        self.emit(&Span::styled(bottom, border).into());
+    }
+
+    /// Columns get the width of their widest cell, and the widest columns
+    /// give up space first when the table does not fit.
+    fn table_block(&mut self, table: &TableBuffer) {
+        let count = table.alignments.len();
+        if count == 0 {
+            return;
+        }
+        let mut widths = vec![0; count];
+        for row in &table.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.width());
+            }
+        }
+        let room = self.available().saturating_sub(3 * (count - 1)).max(count);
+        while widths.iter().sum::<usize>() > room {
+            if let Some(widest) = widths.iter_mut().max() {
+                *widest -= 1;
+            }
+        }
+        let separator = Span::styled(" │ ", muted_style());
+        for (i, row) in table.rows.iter().enumerate() {
+            let mut line = TextLine::new();
+            for (column, width) in widths.iter().enumerate() {
+                if column > 0 {
+                    line.push(separator.clone());
+                }
+                let cell = row
+                    .get(column)
+                    .map(|c| c.columns(0..*width))
+                    .unwrap_or_default();
+                let spare = width - cell.width();
+                let before = match table.alignments[column] {
+                    ColumnAlignment::Right => spare,
+                    ColumnAlignment::Center => spare / 2,
+                    ColumnAlignment::Left | ColumnAlignment::None => 0,
+                };
+                line.push(" ".repeat(before));
+                for span in cell.spans() {
+                    line.push(match (i, span.style()) {
+                        (0, style) => Span::styled(
+                            span.content(),
+                            layered(style.unwrap_or_default(), Style::new().bold()),
+                        ),
+                        _ => span.clone(),
+                    });
+                }
+                line.push(" ".repeat(spare - before));
+            }
+            self.emit(&line);
+            if i == 0 {
+                let rule: Vec<_> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                self.emit(&Span::styled(rule.join("─┼─"), muted_style()).into());
+            }
+        }
+    }
+}
+
+/// Renders `CommonMark`, with tables, strikethrough and task lists, into
+/// styled lines that fit in `width` columns.
+#[must_use]
+pub fn render_markdown(source: &str, width: usize) -> Text {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut renderer = Renderer::new(width);
+    for event in Parser::new_ext(source, options) {
+        renderer.event(event);
+    }
+    renderer.flush_inline();
+    renderer.text
+}
+
+/// Markdown rendered into a container, scrolled with the arrow and page keys
+/// or the mouse wheel.
+#[derive(Debug)]
+pub struct Markdown {
+    frame: Container,
+    source: String,
+}
+
+impl Markdown {
+    #[must_use]
+    pub const fn new(frame: Container) -> Self {
+        Self {
+            frame,
+            source: String::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.set_source(source);
+        self
+    }
+
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = source.into();
+        self.render();
+    }
+
+    /// Moves or resizes the frame, rendering the source again at the new
+    /// width so that rules, tables and code blocks fit it.
+    pub fn set_area(&mut self, area: Rect) {
+        self.frame.set_area(area);
+        self.render();
+    }
+
+    fn render(&mut self) {
+        let (width, _) = self.frame.content_size();
+        self.frame.set_content(render_markdown(&self.source, width));
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    #[must_use]
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    /// The frame, for styling. Resize it with [`Self::set_area`] instead, so
+    /// that the source is rendered again.
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let (_, height) = self.frame.content_size();
+        let page = height.max(1).cast_signed();
+        match event.key {
+            Key::Up => self.frame.scroll_by(-1),
+            Key::Down => self.frame.scroll_by(1),
+            Key::PageUp => self.frame.scroll_by(-page),
+            Key::PageDown => self.frame.scroll_by(page),
+            Key::Home => self.frame.scroll_to(0),
+            Key::End => self.frame.scroll_to(usize::MAX),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Widget for Markdown {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        self.frame.contents_of(x, y, theme)
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if self.frame.local_position(event.x, event.y).is_none() {
+            return false;
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp => self.frame.scroll_by(-SCROLL_LINES),
+            MouseEventKind::ScrollDown => self.frame.scroll_by(SCROLL_LINES),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(source: &str, width: usize) -> String {
+        render_markdown(source, width).to_string()
+    }
+
+    #[test]
+    fn blocks() {
+        let source = "# Title\n\nSome *text* here.\n\n> quoted words\n> go on\n\n---\n\n- one\n- two\n  1. a\n  2. b\n";
+        let expected = "\
+Title
+━━━━━
+
+Some text
+here.
+
+│ quoted
+│ words go
+│ on
+
+──────────
+
+• one
+• two
+  1. a
+  2. b";
+        assert_eq!(plain(source, 10), expected);
+    }
+
+    #[test]
+    fn code_blocks_and_tables() {
+        let source = "```rs\nfn main() {}\n```\n\n| a | long header |\n|--:|:-:|\n| 1 | x |\n";
+        let expected = "\
+╭─rs────────────╮
+│ fn main() {}  │
+╰───────────────╯
+
+a │ long header
+──┼────────────
+1 │      x";
+        let rendered = plain(source, 17);
+        let lines: Vec<_> = rendered.lines().map(str::trim_end).collect();
+        assert_eq!(lines.join("\n"), expected);
+    }
+
+    #[test]
+    fn inline_styles_and_links() {
+        let text = render_markdown("**bold `code`** [site](https://x.rs) <https://y.rs>", 80);
+        let line = &text.lines()[0];
+        assert_eq!(
+            line.to_string(),
+            "bold code site (https://x.rs) https://y.rs"
+        );
+        let theme = Theme::default();
+        let style_at = |column| line.cell_at(column, &theme);
+        assert_eq!(style_at(0), Some(('b', Some(Style::new().bold()))));
+        assert_eq!(style_at(5), Some(('c', Some(Colour::Cyan.bold()))));
+        assert_eq!(style_at(10), Some(('s', Some(link_style()))));
+    }
+
+    #[test]
+    fn scrolls_within_frame() {
+        let source = (1..=20)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let mut markdown = Markdown::new(Container::new(Rect::new(10.0, 3.0))).with_source(source);
+        markdown.handle_key(Key::PageDown.into());
+        assert_eq!(markdown.frame().scroll_offset(), 3);
+        markdown.handle_key(Key::End.into());
+        let theme = Theme::default();
+        let last = markdown.contents_of(0, 2, &theme).unwrap();
+        assert_eq!((markdown.frame().scroll_offset(), last.symbol), (36, 'l'));
+    }
+
+    #[test]
+    fn renders_again_when_resized() {
+        let mut markdown =
+            Markdown::new(Container::new(Rect::new(6.0, 3.0))).with_source("one\n\n---");
+        let theme = Theme::default();
+        let rule = |markdown: &Markdown, width| -> String {
+            (0..width)
+                .map(|x| markdown.contents_of(x, 2, &theme).unwrap().symbol)
+                .collect()
+        };
+        assert_eq!(rule(&markdown, 6), "──────");
+        markdown.set_area(Rect::new(10.0, 3.0));
+        assert_eq!(rule(&markdown, 10), "──────────");
+    }
+}
@@ -0,0 +1,932 @@
+use glam::Vec2;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::TuiBorder,
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    theme::{Role, Theme},
+    widget::{local_position, popup_area, Placement, Widget},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    id: String,
+    label: String,
+    accelerator: Option<KeyEvent>,
+    enabled: bool,
+    checked: Option<bool>,
+    submenu: Vec<MenuEntry>,
+}
+
+impl MenuItem {
+    /// The label doubles as the id reported on activation unless one is set.
+    pub fn new(label: impl Into<String>) -> Self {
+        let label = label.into();
+        Self {
+            id: label.clone(),
+            label,
+            accelerator: None,
+            enabled: true,
+            checked: None,
+            submenu: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_id(self, id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_accelerator(self, accelerator: KeyEvent) -> Self {
+        Self {
+            accelerator: Some(accelerator),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    /// Makes the item checkable. Activating it flips the checkmark.
+    #[must_use]
+    pub fn with_checked(self, checked: bool) -> Self {
+        Self {
+            checked: Some(checked),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_submenu(self, submenu: Vec<MenuEntry>) -> Self {
+        Self { submenu, ..self }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[must_use]
+    pub const fn is_checked(&self) -> Option<bool> {
+        self.checked
+    }
+
+    #[must_use]
+    pub fn submenu(&self) -> &[MenuEntry] {
+        &self.submenu
+    }
+
+    /// Flips the checkmark and returns the id to report.
+    fn activate(&mut self) -> String {
+        self.checked = self.checked.map(|checked| !checked);
+        self.id.clone()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuEntry {
+    Item(MenuItem),
+    Separator,
+}
+
+impl From<MenuItem> for MenuEntry {
+    fn from(item: MenuItem) -> Self {
+        Self::Item(item)
+    }
+}
+
+/// Activates the enabled item bound to `event`, searching submenus too.
+fn trigger(entries: &mut [MenuEntry], event: KeyEvent) -> Option<String> {
+    entries.iter_mut().find_map(|entry| match entry {
+        MenuEntry::Item(item) if item.enabled && item.accelerator == Some(event) => {
+            Some(item.activate())
+        }
+        MenuEntry::Item(item) => trigger(&mut item.submenu, event),
+        MenuEntry::Separator => None,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Ignored,
+    Handled,
+    Activated(usize, String),
+    Closed,
+}
+
+/// An open list of entries. A submenu takes its entries from the parent
+/// item while it is open and hands them back when it closes.
+#[derive(Debug, Clone)]
+struct Popup {
+    entries: Vec<MenuEntry>,
+    area: Rect,
+    screen: Rect,
+    cursor: Option<usize>,
+    child: Option<(usize, Box<Self>)>,
+    widths: [usize; 4],
+}
+
+impl Popup {
+    fn open(entries: Vec<MenuEntry>, anchor: &Rect, screen: &Rect, placement: Placement) -> Self {
+        let items = || {
+            entries.iter().filter_map(|entry| match entry {
+                MenuEntry::Item(item) => Some(item),
+                MenuEntry::Separator => None,
+            })
+        };
+        let check = if items().any(|item| item.checked.is_some()) {
+            2
+        } else {
+            0
+        };
+        let label = items().map(|i| i.label.chars().count()).max().unwrap_or(0);
+        let accelerator = items()
+            .filter_map(|item| item.accelerator)
+            .map(|accelerator| accelerator.to_string().chars().count() + 2)
+            .max()
+            .unwrap_or(0);
+        let arrow = if items().any(|item| !item.submenu.is_empty()) {
+            2
+        } else {
+            0
+        };
+        let width = check + label + accelerator + arrow + 4;
+        let cursor = entries
+            .iter()
+            .position(|entry| matches!(entry, MenuEntry::Item(_)));
+        Self {
+            area: popup_area(anchor, width, entries.len() + 2, screen, placement),
+            screen: screen.clone(),
+            entries,
+            cursor,
+            child: None,
+            widths: [check, label, accelerator, arrow],
+        }
+    }
+
+    fn into_entries(mut self) -> Vec<MenuEntry> {
+        self.close_child();
+        self.entries
+    }
+
+    fn close_child(&mut self) {
+        if let Some((index, child)) = self.child.take() {
+            if let Some(MenuEntry::Item(item)) = self.entries.get_mut(index) {
+                item.submenu = child.into_entries();
+            }
+        }
+    }
+
+    fn on_submenu(&self) -> bool {
+        matches!(
+            self.cursor.and_then(|i| self.entries.get(i)),
+            Some(MenuEntry::Item(item)) if !item.submenu.is_empty()
+        )
+    }
+
+    fn move_cursor(&mut self, forward: bool) {
+        let len = self.entries.len();
+        let mut index = self.cursor.unwrap_or(0);
+        for _ in 0..len {
+            index = if forward {
+                (index + 1) % len
+            } else {
+                (index + len - 1) % len
+            };
+            if matches!(self.entries[index], MenuEntry::Item(_)) {
+                self.cursor = Some(index);
+                return;
+            }
+        }
+    }
+
+    fn activate(&mut self, index: usize) -> Outcome {
+        self.close_child();
+        let (position, width, _) = self.area.pos_width_height();
+        let Some(MenuEntry::Item(item)) = self.entries.get_mut(index) else {
+            return Outcome::Handled;
+        };
+        if !item.enabled {
+            return Outcome::Handled;
+        }
+        if item.submenu.is_empty() {
+            return Outcome::Activated(index, item.activate());
+        }
+        let entries = std::mem::take(&mut item.submenu);
+        let row = position + Vec2::new(0.0, (index + 1) as f32);
+        let anchor = Rect::new(width, 1.0).with_position(row);
+        let child = Self::open(entries, &anchor, &self.screen, Placement::Right);
+        self.child = Some((index, Box::new(child)));
+        Outcome::Handled
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> Outcome {
+        if let Some((_, child)) = &mut self.child {
+            return match child.handle_key(event) {
+                Outcome::Closed => {
+                    self.close_child();
+                    Outcome::Handled
+                }
+                outcome => outcome,
+            };
+        }
+        match event.key {
+            Key::Up => self.move_cursor(false),
+            Key::Down => self.move_cursor(true),
+            Key::Right if !self.on_submenu() => return Outcome::Ignored,
+            Key::Right | Key::Enter | Key::Char(' ') => {
+                return self.cursor.map_or(Outcome::Handled, |i| self.activate(i));
+            }
+            Key::Left | Key::Esc => return Outcome::Closed,
+            _ => return Outcome::Ignored,
+        }
+        Outcome::Handled
+    }
+
+    /// Clicks and moves anywhere over the popup are used up by it.
+    fn handle_mouse(&mut self, event: MouseEvent) -> Outcome {
+        if let Some((_, child)) = &mut self.child {
+            let outcome = child.handle_mouse(event);
+            if outcome != Outcome::Ignored {
+                return outcome;
+            }
+        }
+        let Some((_, row)) = local_position(&self.area, event.x, event.y) else {
+            return Outcome::Ignored;
+        };
+        let index = row
+            .checked_sub(1)
+            .filter(|&i| matches!(self.entries.get(i), Some(MenuEntry::Item(_))));
+        match (event.kind, index) {
+            (MouseEventKind::Moved, Some(index)) => self.cursor = Some(index),
+            (MouseEventKind::Down(MouseButton::Left), Some(index)) => {
+                self.cursor = Some(index);
+                return self.activate(index);
+            }
+            _ => {}
+        }
+        Outcome::Handled
+    }
+
+    fn item_symbol(&self, item: &MenuItem, column: usize) -> char {
+        let [check, label, accelerator, arrow] = self.widths;
+        let mut text = String::from(" ");
+        if check > 0 {
+            text.push_str(if item.checked == Some(true) {
+                "✓ "
+            } else {
+                "  "
+            });
+        }
+        text.push_str(&item.label);
+        let accelerator_text = item.accelerator.map(|a| a.to_string()).unwrap_or_default();
+        let pad =
+            label - item.label.chars().count() + accelerator - accelerator_text.chars().count();
+        text.extend(std::iter::repeat_n(' ', pad));
+        text.push_str(&accelerator_text);
+        if arrow > 0 {
+            text.push_str(if item.submenu.is_empty() {
+                "  "
+            } else {
+                " ▸"
+            });
+        }
+        text.chars().nth(column).unwrap_or(' ')
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        if let Some(cell) = self
+            .child
+            .as_ref()
+            .and_then(|(_, c)| c.contents_of(x, y, theme))
+        {
+            return Some(cell);
+        }
+        let (column, row) = local_position(&self.area, x, y)?;
+        let (_, width, height) = self.area.pos_width_height();
+        let (right, bottom) = (width as usize - 1, height as usize - 1);
+        let symbols = TuiBorder::SmoothCorner.symbols();
+        let border = |symbol| Some(Cell::new(symbol, theme.style(Role::Border)));
+        let entry = row.checked_sub(1).and_then(|i| self.entries.get(i));
+        match (column, row, entry) {
+            (0, 0, _) => border(symbols.top_left),
+            (_, 0, _) if column == right => border(symbols.top_right),
+            (0, _, _) if row == bottom => border(symbols.bottom_left),
+            (_, _, _) if (column, row) == (right, bottom) => border(symbols.bottom_right),
+            (_, 0, _) => border(symbols.horizontal),
+            (_, _, _) if row == bottom => border(symbols.horizontal),
+            (0, _, Some(MenuEntry::Separator)) => border(symbols.tee_right),
+            (_, _, Some(MenuEntry::Separator)) if column == right => border(symbols.tee_left),
+            (_, _, Some(MenuEntry::Separator)) => border(symbols.horizontal),
+            (0, _, _) => border(symbols.vertical),
+            (_, _, _) if column == right => border(symbols.vertical),
+            (_, _, Some(MenuEntry::Item(item))) => {
+                let role = if self.cursor == Some(row - 1) {
+                    Role::Selection
+                } else if item.enabled {
+                    Role::Text
+                } else {
+                    Role::TextMuted
+                };
+                let symbol = self.item_symbol(item, column - 1);
+                Some(Cell::new(symbol, theme.style(role)))
+            }
+            (_, _, None) => Some(Cell::blank(theme.style(Role::Text))),
+        }
+    }
+}
+
+/// A row of menu titles with dropdown menus.
+///
+/// F10 or Alt with the first letter of a title opens a menu, and
+/// accelerators work while all menus are closed. Activated items are
+/// reported through [`MenuBar::take_activated`].
+#[derive(Debug, Clone)]
+pub struct MenuBar {
+    area: Rect,
+    screen: Rect,
+    menus: Vec<(String, Vec<MenuEntry>)>,
+    open: Option<(usize, Popup)>,
+    activated: Option<String>,
+}
+
+impl MenuBar {
+    /// `screen` is the space popups have to stay inside of.
+    #[must_use]
+    pub const fn new(area: Rect, screen: Rect) -> Self {
+        Self {
+            area,
+            screen,
+            menus: Vec::new(),
+            open: None,
+            activated: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_menu(mut self, title: impl Into<String>, entries: Vec<MenuEntry>) -> Self {
+        self.menus.push((title.into(), entries));
+        self
+    }
+
+    #[must_use]
+    pub fn menu(&self, title: &str) -> Option<&[MenuEntry]> {
+        self.menus
+            .iter()
+            .find(|(t, _)| t == title)
+            .map(|(_, entries)| entries.as_slice())
+    }
+
+    #[must_use]
+    pub fn open_menu(&self) -> Option<usize> {
+        self.open.as_ref().map(|(i, _)| *i)
+    }
+
+    pub const fn take_activated(&mut self) -> Option<String> {
+        self.activated.take()
+    }
+
+    fn title_span(&self, index: usize) -> (usize, usize) {
+        let start = self.menus[..index]
+            .iter()
+            .map(|(title, _)| title.chars().count() + 2)
+            .sum();
+        (start, start + self.menus[index].0.chars().count() + 2)
+    }
+
+    fn title_at(&self, column: usize) -> Option<usize> {
+        (0..self.menus.len()).find(|&i| {
+            let (start, end) = self.title_span(i);
+            (start..end).contains(&column)
+        })
+    }
+
+    pub fn open(&mut self, index: usize) {
+        self.close();
+        let Some((_, entries)) = self.menus.get_mut(index) else {
+            return;
+        };
+        let entries = std::mem::take(entries);
+        let (start, end) = self.title_span(index);
+        let (position, ..) = self.area.pos_width_height();
+        let anchor = Rect::new((end - start) as f64, 1.0)
+            .with_position(position + Vec2::new(start as f32, 0.0));
+        let popup = Popup::open(entries, &anchor, &self.screen, Placement::Below);
+        self.open = Some((index, popup));
+    }
+
+    pub fn close(&mut self) {
+        if let Some((index, popup)) = self.open.take() {
+            self.menus[index].1 = popup.into_entries();
+        }
+    }
+
+    fn finish(&mut self, outcome: Outcome) -> bool {
+        match outcome {
+            Outcome::Activated(_, id) => {
+                self.activated = Some(id);
+                self.close();
+            }
+            Outcome::Closed => self.close(),
+            Outcome::Handled | Outcome::Ignored => {}
+        }
+        true
+    }
+}
+
+impl Widget for MenuBar {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        if let Some(cell) = self
+            .open
+            .as_ref()
+            .and_then(|(_, p)| p.contents_of(x, y, theme))
+        {
+            return Some(cell);
+        }
+        let (column, _) = local_position(&self.area, x, y)?;
+        let Some(index) = self.title_at(column) else {
+            return Some(Cell::blank(theme.style(Role::Text)));
+        };
+        let role = if self.open_menu() == Some(index) {
+            Role::Selection
+        } else {
+            Role::Text
+        };
+        let offset = column - self.title_span(index).0;
+        let title = &self.menus[index].0;
+        let symbol = offset
+            .checked_sub(1)
+            .and_then(|i| title.chars().nth(i))
+            .unwrap_or(' ');
+        Some(Cell::new(symbol, theme.style(role)))
+    }
+
+    /// Left and right move between menus unless a submenu is involved.
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let count = self.menus.len();
+        if let Some((index, popup)) = &mut self.open {
+            let (index, at_root) = (*index, popup.child.is_none());
+            match event.key {
+                Key::Left if at_root => self.open((index + count - 1) % count),
+                Key::Right if at_root && !popup.on_submenu() => self.open((index + 1) % count),
+                _ => {
+                    let outcome = popup.handle_key(event);
+                    return self.finish(outcome);
+                }
+            }
+            return true;
+        }
+        match event.key {
+            Key::F(10) if count > 0 => self.open(0),
+            Key::Char(letter) if event.modifiers.alt => {
+                let Some(index) = self.menus.iter().position(|(title, _)| {
+                    title
+                        .chars()
+                        .next()
+                        .is_some_and(|first| first.eq_ignore_ascii_case(&letter))
+                }) else {
+                    return false;
+                };
+                self.open(index);
+            }
+            _ => {
+                let found = self
+                    .menus
+                    .iter_mut()
+                    .find_map(|(_, entries)| trigger(entries, event));
+                let Some(id) = found else {
+                    return false;
+                };
+                self.activated = Some(id);
+            }
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if let Some((_, popup)) = &mut self.open {
+            let outcome = popup.handle_mouse(event);
+            if outcome != Outcome::Ignored {
+                return self.finish(outcome);
+            }
+        }
+        let Some((column, _)) = local_position(&self.area, event.x, event.y) else {
+            if let MouseEventKind::Down(_) = event.kind {
+                self.close();
+            }
+            return false;
+        };
+        let index = self.title_at(column);
+        match (event.kind, index) {
+            (MouseEventKind::Down(MouseButton::Left), Some(index)) => {
+                if self.open_menu() == Some(index) {
+                    self.close();
+                } else {
+                    self.open(index);
+                }
+            }
+            (MouseEventKind::Moved, Some(index))
+                if self.open.is_some() && self.open_menu() != Some(index) =>
+            {
+                self.open(index);
+            }
+            (MouseEventKind::Down(_), None) => self.close(),
+            _ => {}
+        }
+        true
+    }
+}
+
+/// A single row showing the selected option that opens a list of all of
+/// them.
+#[derive(Debug, Clone)]
+pub struct Dropdown {
+    area: Rect,
+    screen: Rect,
+    options: Vec<String>,
+    selected: Option<usize>,
+    popup: Option<Popup>,
+    focused: bool,
+}
+
+impl Dropdown {
+    #[must_use]
+    pub const fn new(area: Rect, screen: Rect) -> Self {
+        Self {
+            area,
+            screen,
+            options: Vec::new(),
+            selected: None,
+            popup: None,
+            focused: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_options<T: Into<String>>(mut self, options: impl IntoIterator<Item = T>) -> Self {
+        self.options = options.into_iter().map(Into::into).collect();
+        self.selected = (!self.options.is_empty()).then_some(0);
+        self
+    }
+
+    #[must_use]
+    pub const fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected_option(&self) -> Option<&str> {
+        self.options.get(self.selected?).map(String::as_str)
+    }
+
+    pub const fn select(&mut self, index: usize) {
+        if index < self.options.len() {
+            self.selected = Some(index);
+        }
+    }
+
+    pub const fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        self.popup.is_some()
+    }
+
+    pub fn open(&mut self) {
+        let entries = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                MenuItem::new(option)
+                    .with_checked(Some(i) == self.selected)
+                    .into()
+            })
+            .collect();
+        let mut popup = Popup::open(entries, &self.area, &self.screen, Placement::Below);
+        popup.cursor = self.selected.or(popup.cursor);
+        self.popup = Some(popup);
+    }
+
+    pub fn close(&mut self) {
+        self.popup = None;
+    }
+
+    fn finish(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Activated(index, _) => {
+                self.selected = Some(*index);
+                self.close();
+            }
+            Outcome::Closed => self.close(),
+            Outcome::Handled | Outcome::Ignored => {}
+        }
+    }
+}
+
+impl Widget for Dropdown {
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        if let Some(cell) = self.popup.as_ref().and_then(|p| p.contents_of(x, y, theme)) {
+            return Some(cell);
+        }
+        let (column, _) = local_position(&self.area, x, y)?;
+        let (_, width, _) = self.area.pos_width_height();
+        let role = if self.focused {
+            Role::Selection
+        } else {
+            Role::Text
+        };
+        let symbol = if column + 1 == width as usize {
+            '▾'
+        } else {
+            self.selected_option()
+                .and_then(|option| option.chars().nth(column))
+                .unwrap_or(' ')
+        };
+        Some(Cell::new(symbol, theme.style(role)))
+    }
+
+    /// Up and down change the selection directly while the list is closed.
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        if let Some(popup) = &mut self.popup {
+            let outcome = popup.handle_key(event);
+            self.finish(&outcome);
+            return outcome != Outcome::Ignored;
+        }
+        let last = self.options.len().saturating_sub(1);
+        match (event.key, self.selected) {
+            (Key::Enter | Key::Char(' '), _) => self.open(),
+            (Key::Up, Some(selected)) => self.selected = Some(selected.saturating_sub(1)),
+            (Key::Down, Some(selected)) => self.selected = Some((selected + 1).min(last)),
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if let Some(popup) = &mut self.popup {
+            let outcome = popup.handle_mouse(event);
+            if outcome != Outcome::Ignored {
+                self.finish(&outcome);
+                return true;
+            }
+        }
+        let inside = local_position(&self.area, event.x, event.y).is_some();
+        match (event.kind, inside) {
+            (MouseEventKind::Down(MouseButton::Left), true) if self.popup.is_none() => self.open(),
+            (MouseEventKind::Down(_), _) if self.popup.is_some() => self.close(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// A menu that opens where `region` is right-clicked.
+#[derive(Debug, Clone)]
+pub struct ContextMenu {
+    region: Rect,
+    screen: Rect,
+    entries: Vec<MenuEntry>,
+    popup: Option<Popup>,
+    activated: Option<String>,
+}
+
+impl ContextMenu {
+    #[must_use]
+    pub const fn new(region: Rect, screen: Rect, entries: Vec<MenuEntry>) -> Self {
+        Self {
+            region,
+            screen,
+            entries,
+            popup: None,
+            activated: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        self.popup.is_some()
+    }
+
+    pub const fn take_activated(&mut self) -> Option<String> {
+        self.activated.take()
+    }
+
+    pub fn open_at(&mut self, x: usize, y: usize) {
+        self.close();
+        let anchor = Rect::new(0.0, 0.0).with_position(Vec2::new(x as f32, y as f32));
+        let entries = std::mem::take(&mut self.entries);
+        self.popup = Some(Popup::open(
+            entries,
+            &anchor,
+            &self.screen,
+            Placement::Below,
+        ));
+    }
+
+    pub fn close(&mut self) {
+        if let Some(popup) = self.popup.take() {
+            self.entries = popup.into_entries();
+        }
+    }
+
+    fn finish(&mut self, outcome: Outcome) -> bool {
+        match outcome {
+            Outcome::Activated(_, id) => {
+                self.activated = Some(id);
+                self.close();
+            }
+            Outcome::Closed => self.close(),
+            Outcome::Ignored => return false,
+            Outcome::Handled => {}
+        }
+        true
+    }
+}
+
+impl Widget for ContextMenu {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        self.popup.as_ref()?.contents_of(x, y, theme)
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let Some(popup) = &mut self.popup else {
+            return false;
+        };
+        let outcome = popup.handle_key(event);
+        self.finish(outcome)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if let Some(popup) = &mut self.popup {
+            let outcome = popup.handle_mouse(event);
+            if outcome != Outcome::Ignored {
+                return self.finish(outcome);
+            }
+        }
+        let inside = local_position(&self.region, event.x, event.y).is_some();
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Right) if inside => {
+                self.open_at(event.x, event.y);
+                true
+            }
+            MouseEventKind::Down(_) if self.popup.is_some() => {
+                self.close();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::event::Modifiers;
+
+    fn render(widget: &impl Widget, width: usize, height: usize) -> Vec<String> {
+        let theme = Theme::default();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| widget.contents_of(x, y, &theme).map_or(' ', |c| c.symbol))
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn file_menu() -> Vec<MenuEntry> {
+        vec![
+            MenuItem::new("Save")
+                .with_accelerator(KeyEvent::new(Key::Char('s'), Modifiers::CTRL))
+                .into(),
+            MenuItem::new("Close").with_enabled(false).into(),
+            MenuEntry::Separator,
+            MenuItem::new("Wrap").with_checked(false).into(),
+        ]
+    }
+
+    fn menu_bar() -> MenuBar {
+        MenuBar::new(Rect::new(30.0, 1.0), Rect::new(30.0, 10.0))
+            .with_menu("File", file_menu())
+            .with_menu(
+                "View",
+                vec![MenuItem::new("Zoom")
+                    .with_submenu(vec![
+                        MenuItem::new("In").into(),
+                        MenuItem::new("Out").into(),
+                    ])
+                    .into()],
+            )
+    }
+
+    #[test]
+    fn popups_stay_on_screen() {
+        let screen = Rect::new(20.0, 10.0);
+        let anchor = Rect::new(4.0, 1.0).with_position(Vec2::new(18.0, 8.0));
+        let below = popup_area(&anchor, 6, 4, &screen, Placement::Below);
+        assert_eq!(below.pos_width_height().0, Vec2::new(14.0, 4.0));
+        let right = popup_area(&anchor, 6, 4, &screen, Placement::Right);
+        assert_eq!(right.pos_width_height().0, Vec2::new(12.0, 6.0));
+    }
+
+    #[test]
+    fn menu_bar_renders_dropdown() {
+        let mut bar = menu_bar();
+        bar.handle_key(KeyEvent::new(Key::Char('f'), Modifiers::ALT));
+        assert_eq!(
+            render(&bar, 22, 6),
+            vec![
+                " File  View",
+                "╭─────────────────╮",
+                "│   Save   Ctrl+S │",
+                "│   Close         │",
+                "├─────────────────┤",
+                "│   Wrap          │",
+            ]
+        );
+        bar.handle_key(Key::Down.into());
+        bar.handle_key(Key::Down.into());
+        bar.handle_key(Key::Enter.into());
+        assert_eq!(bar.take_activated().as_deref(), Some("Wrap"));
+        assert_eq!(bar.open_menu(), None);
+        let wrap = &bar.menu("File").unwrap()[3];
+        assert!(matches!(wrap, MenuEntry::Item(item) if item.is_checked() == Some(true)));
+    }
+
+    #[test]
+    fn submenus_and_accelerators() {
+        let mut bar = menu_bar();
+        bar.handle_key(KeyEvent::new(Key::Char('s'), Modifiers::CTRL));
+        assert_eq!(bar.take_activated().as_deref(), Some("Save"));
+
+        bar.handle_mouse(MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            7,
+            0,
+        ));
+        assert_eq!(bar.open_menu(), Some(1));
+        bar.handle_key(Key::Right.into());
+        bar.handle_key(Key::Down.into());
+        bar.handle_key(Key::Enter.into());
+        assert_eq!(bar.take_activated().as_deref(), Some("Out"));
+        assert_eq!(
+            bar.menu("View").unwrap()[0],
+            menu_bar().menu("View").unwrap()[0]
+        );
+    }
+
+    #[test]
+    fn dropdown_selects_option() {
+        let mut dropdown = Dropdown::new(Rect::new(8.0, 1.0), Rect::new(20.0, 10.0))
+            .with_options(["red", "green", "blue"]);
+        dropdown.handle_key(Key::Down.into());
+        assert_eq!(dropdown.selected_option(), Some("green"));
+        dropdown.handle_key(Key::Enter.into());
+        assert!(dropdown.is_open());
+        dropdown.handle_key(Key::Down.into());
+        dropdown.handle_key(Key::Enter.into());
+        assert_eq!((dropdown.selected(), dropdown.is_open()), (Some(2), false));
+        assert_eq!(render(&dropdown, 8, 1), vec!["blue   ▾"]);
+    }
+
+    #[test]
+    fn context_menu_opens_at_click() {
+        let screen = Rect::new(20.0, 10.0);
+        let mut menu = ContextMenu::new(
+            screen.clone(),
+            screen,
+            vec![MenuItem::new("Copy").into(), MenuItem::new("Paste").into()],
+        );
+        let click = |kind, x, y| MouseEvent::new(kind, x, y);
+        menu.handle_mouse(click(MouseEventKind::Down(MouseButton::Right), 18, 9));
+        assert!(menu.is_open());
+        assert_eq!(render(&menu, 20, 10)[5], "           ╭───────╮");
+        menu.handle_mouse(click(MouseEventKind::Down(MouseButton::Left), 14, 7));
+        assert_eq!(menu.take_activated().as_deref(), Some("Paste"));
+        assert!(!menu.is_open());
+    }
+}
@@ -1,44 +1,101 @@
+use std::time::Instant;
+
 use crate::{
     common::{Margin, Padding},
     rect::Rect,
 };
 
+mod ansi;
+mod canvas;
+mod cell;
+mod chart;
+mod code;
 mod container;
-use container::Container;
+mod controls;
+mod diff;
+mod event;
+mod form;
+mod hex;
+mod input;
+mod keymap;
+mod list;
+mod logs;
+mod markdown;
+mod menu;
+mod mouse;
+mod palette;
+mod progress;
+mod split;
+mod table;
+mod tabs;
+mod text;
+mod theme;
+mod tiling;
+mod tree;
+mod widget;
+
+pub use ansi::parse_ansi;
+pub use canvas::{Canvas, Circle, Grid, Line, Marker, Painter, Points, Rectangle, Shape};
+pub use cell::{paint, Cell};
+pub use chart::{Axis, Bar, BarChart, BarGroup, Chart, Dataset, GraphType, Sparkline};
+pub use code::{Code, Syntax, Token, TokenKind, Tokenizer};
+pub use container::{BorderSymbols, Container, InvalidBorder, TuiBorder};
+pub use controls::{Button, Checkbox, Control, ControlState, RadioGroup};
+pub use diff::{Diff, DiffLayout, DiffLine, DiffView, Hunk, LineKind};
+pub use event::{InvalidKey, Key, KeyEvent, Modifiers, MouseButton, MouseEvent, MouseEventKind};
+pub use form::{Field, Form, FormOutcome};
+pub use hex::HexView;
+pub use input::{TextArea, TextInput};
+pub use keymap::{KeySequence, Keymap, KeymapError, KeymapHelp, KeymapOutcome, Scope};
+pub use list::{List, ListItem, SelectionMode};
+pub use logs::{level_role, LogBuffer, LogCapture, LogRecord, LogView};
+pub use markdown::{render_markdown, Markdown};
+pub use menu::{ContextMenu, Dropdown, MenuBar, MenuEntry, MenuItem};
+pub use mouse::{MouseRouter, MouseTarget, RoutedMouseEvent, DOUBLE_CLICK_INTERVAL};
+pub use palette::{fuzzy_match, Command, CommandPalette};
+pub use progress::{Gauge, IndeterminateBar, ProgressBar, Spinner, SpinnerStyle};
+pub use split::{InvalidSplitState, Pane, Split, SplitState};
+pub use table::{Alignment, Column, Constraint, Row, SortOrder, Table};
+pub use tabs::Tabs;
+pub use text::{InvalidMarkup, Span, Text, TextLine};
+pub use theme::{parse_colour, parse_style, style_spec, Role, Theme, ThemeError};
+pub use tiling::{InvalidLayout, Layout, PaneId, Tiling};
+pub use tree::{Tree, TreeNode};
+pub use widget::{popup_area, Direction, Placement, Widget};
 
-use self::container::TuiBorder;
 #[derive(Debug)]
 pub struct Terminal {
     width: usize,
     height: usize,
-    free_space: Option<Rect>,
+    theme: Theme,
     pub containers: Vec<Container>,
+    pub widgets: Vec<Box<dyn Widget>>,
+    mouse: MouseRouter,
 }
 
 impl Terminal {
+    #[must_use]
     pub fn new() -> Self {
         let (width, height) = term_size::dimensions().unwrap_or((80, 5));
         let mut rect = Rect::new(width as f64, height as f64);
-        let container1 = Container::new(rect.cut_right((width / 3) as f64))
+        let right_pane = Container::new(rect.cut_right((width / 3) as f64))
             .with_padding(Padding::same(2.0))
             .with_margin(Margin::top(3.0))
             .with_border(TuiBorder::SmoothCorner);
-        let container2 = Container::new(rect.cut_left((width / 3) as f64))
+        let left_pane = Container::new(rect.cut_left((width / 3) as f64))
             .with_padding(Padding::same(2.0))
             .with_margin(Margin::same(1.0));
         let rects = rect.divide_vertically(3);
-        let mut containers: Vec<Container> = rects
-            .into_iter()
-            .map(|rect| {
-                Container::new(rect)
-                    .with_margin(Margin::top(1.0))
-                    .with_border(TuiBorder::SmoothCorner)
-            })
-            .collect();
+        let panes = rects.into_iter().enumerate().map(|(i, rect)| {
+            Container::new(rect)
+                .with_margin(Margin::top(1.0))
+                .with_border(TuiBorder::SmoothCorner)
+                .with_title(format!("Pane {}", i + 1))
+        });
 
-        let mut containers: Vec<Container> = vec![container1, container2]
+        let mut containers: Vec<Container> = vec![right_pane, left_pane]
             .into_iter()
-            .chain(containers.into_iter())
+            .chain(panes)
             .collect();
 
         for container in &mut containers {
@@ -53,44 +110,130 @@ impl Terminal {
             ]
             .into_iter()
             .cycle();
-            container.set_content(words.take(50).collect());
+            container.set_content(words.take(50).collect::<String>());
         }
 
         Self {
             width,
             height,
-            free_space: None,
+            theme: Theme::default(),
             containers,
+            widgets: Vec::new(),
+            mouse: MouseRouter::default(),
         }
     }
 
+    #[must_use]
+    pub fn with_theme(self, theme: Theme) -> Self {
+        Self { theme, ..self }
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    #[must_use]
+    pub const fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn add_widget(&mut self, widget: impl Widget + 'static) {
+        self.widgets.push(Box::new(widget));
+    }
+
     pub fn update(&mut self) {
         let (width, height) = term_size::dimensions().unwrap_or((80, 5));
         self.width = width;
         self.height = height;
     }
 
-    pub fn display(&self) {
-        let (width, height) = (self.width, self.height);
-        let capacity = (width + 1) * height;
-        let mut buffer = String::with_capacity(capacity);
-
-        for y in 0..height {
-            for x in 0..width {
-                let contents = self
-                    .containers
+    /// The topmost element drawn at a position. Widgets are above
+    /// containers, and later widgets above earlier ones. Container margins
+    /// are see-through, for clicks as well as for drawing.
+    pub fn hit_test(&self, x: usize, y: usize) -> Option<MouseTarget> {
+        self.widgets
+            .iter()
+            .rposition(|w| w.contents_of(x, y, &self.theme).is_some())
+            .map(MouseTarget::Widget)
+            .or_else(|| {
+                self.containers
                     .iter()
-                    .filter_map(|c| c.contents_of(x, y))
-                    .next()
-                    .unwrap_or(' ');
+                    .position(|c| c.contains(x, y))
+                    .map(MouseTarget::Container)
+            })
+    }
 
-                buffer.push(contents);
-            }
-            buffer.push('\n');
-        }
+    /// Delivers a mouse event to the elements it concerns, see
+    /// [`MouseRouter`]. Widgets handle it themselves, and the wheel scrolls
+    /// the container under the pointer.
+    pub fn handle_mouse(&mut self, event: MouseEvent) -> Vec<RoutedMouseEvent> {
+        let hit = self.hit_test(event.x, event.y);
+        let routed = self.mouse.route(event, hit, Instant::now());
+        routed
+            .into_iter()
+            .map(|(target, event)| {
+                let local = match target {
+                    MouseTarget::Widget(index) => {
+                        self.widgets[index].handle_mouse(event);
+                        None
+                    }
+                    MouseTarget::Container(index) => {
+                        let container = &mut self.containers[index];
+                        match event.kind {
+                            MouseEventKind::ScrollUp => container.scroll_by(-SCROLL_LINES),
+                            MouseEventKind::ScrollDown => container.scroll_by(SCROLL_LINES),
+                            _ => {}
+                        }
+                        container
+                            .local_position(event.x, event.y)
+                            .map(|(x, y)| (x, y + container.scroll_offset()))
+                    }
+                };
+                RoutedMouseEvent {
+                    target,
+                    event,
+                    local,
+                }
+            })
+            .collect()
+    }
+
+    /// What is drawn at a position: the same element [`Self::hit_test`]
+    /// finds, or a blank cell.
+    fn cell_at(&self, x: usize, y: usize) -> Cell {
+        self.widgets
+            .iter()
+            .rev()
+            .find_map(|w| w.contents_of(x, y, &self.theme))
+            .or_else(|| {
+                self.containers
+                    .iter()
+                    .find(|c| c.contains(x, y))
+                    .and_then(|c| c.contents_of(x, y, &self.theme))
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn display(&self) {
+        let buffer: Vec<String> = (0..self.height)
+            .map(|y| paint((0..self.width).map(|x| self.cell_at(x, y))))
+            .collect();
         println!("{CLEAR}");
-        println!("{buffer}");
+        print!("{}", buffer.join("\n"));
+        match self.widgets.iter().rev().find_map(|w| w.cursor_position()) {
+            Some((x, y)) => println!("\x1B[{};{}H{SHOW_CURSOR}", y + 1, x + 1),
+            None => println!("{HIDE_CURSOR}"),
+        }
+    }
+}
+
+impl Default for Terminal {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-const CLEAR: &str = "\x1B[2J\x1B[1;1H";
\ No newline at end of file
+const SCROLL_LINES: isize = 3;
+const CLEAR: &str = "\x1B[2J\x1B[1;1H";
+const SHOW_CURSOR: &str = "\x1B[?25h";
+const HIDE_CURSOR: &str = "\x1B[?25l";
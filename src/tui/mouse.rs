@@ -0,0 +1,208 @@
+use std::time::{Duration, Instant};
+
+use super::event::{MouseButton, MouseEvent, MouseEventKind};
+
+/// Longest time between two clicks that still counts as a double click.
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// What a mouse event landed on, by index into the terminal's widgets or
+/// containers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseTarget {
+    Widget(usize),
+    Container(usize),
+}
+
+/// A mouse event together with the element it was delivered to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoutedMouseEvent {
+    pub target: MouseTarget,
+    pub event: MouseEvent,
+    /// For containers, the position in their content as column and line,
+    /// counting the lines scrolled past.
+    pub local: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Click {
+    button: MouseButton,
+    target: MouseTarget,
+    x: usize,
+    y: usize,
+    at: Instant,
+}
+
+/// Turns raw mouse events into what each element should see.
+///
+/// The element under the pointer gets `Enter` and `Leave` as the pointer
+/// moves between elements. Once a button goes down, drags and the release
+/// go to the element that was pressed even when the pointer leaves it, and
+/// a quick second press in the same place adds a `DoubleClick`.
+#[derive(Debug, Clone, Default)]
+pub struct MouseRouter {
+    hovered: Option<MouseTarget>,
+    pressed: Option<(MouseButton, Option<MouseTarget>)>,
+    last_click: Option<Click>,
+}
+
+impl MouseRouter {
+    #[must_use]
+    pub const fn hovered(&self) -> Option<MouseTarget> {
+        self.hovered
+    }
+
+    /// Routes `event`, given the topmost element under the pointer.
+    pub fn route(
+        &mut self,
+        mut event: MouseEvent,
+        hit: Option<MouseTarget>,
+        now: Instant,
+    ) -> Vec<(MouseTarget, MouseEvent)> {
+        let mut routed = Vec::new();
+        if let (MouseEventKind::Moved, Some((button, _))) = (event.kind, self.pressed) {
+            event.kind = MouseEventKind::Drag(button);
+        }
+        if hit != self.hovered {
+            let with = |kind| MouseEvent { kind, ..event };
+            routed.extend(self.hovered.map(|old| (old, with(MouseEventKind::Leave))));
+            routed.extend(hit.map(|new| (new, with(MouseEventKind::Enter))));
+            self.hovered = hit;
+        }
+
+        let target = match event.kind {
+            MouseEventKind::Drag(_) | MouseEventKind::Up(_) if self.pressed.is_some() => {
+                self.pressed.and_then(|(_, target)| target)
+            }
+            _ => hit,
+        };
+        match event.kind {
+            MouseEventKind::Down(button) => self.pressed = Some((button, hit)),
+            MouseEventKind::Up(_) => self.pressed = None,
+            _ => {}
+        }
+        let Some(target) = target else {
+            return routed;
+        };
+        routed.push((target, event));
+
+        if let MouseEventKind::Down(button) = event.kind {
+            let double = self.last_click.is_some_and(|last| {
+                last.button == button
+                    && last.target == target
+                    && last.x.abs_diff(event.x) <= 1
+                    && last.y == event.y
+                    && now.duration_since(last.at) <= DOUBLE_CLICK_INTERVAL
+            });
+            if double {
+                self.last_click = None;
+                let kind = MouseEventKind::DoubleClick(button);
+                routed.push((target, MouseEvent { kind, ..event }));
+            } else {
+                self.last_click = Some(Click {
+                    button,
+                    target,
+                    x: event.x,
+                    y: event.y,
+                    at: now,
+                });
+            }
+        }
+        routed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rect::Rect,
+        tui::{container::Container, controls::Button, theme::Theme, Terminal},
+    };
+
+    fn kinds(routed: &[(MouseTarget, MouseEvent)]) -> Vec<(MouseTarget, MouseEventKind)> {
+        routed.iter().map(|(t, e)| (*t, e.kind)).collect()
+    }
+
+    #[test]
+    fn tracks_hover_and_captures_drags() {
+        let (a, b) = (MouseTarget::Container(0), MouseTarget::Widget(0));
+        let mut router = MouseRouter::default();
+        let now = Instant::now();
+        let moved = |x| MouseEvent::new(MouseEventKind::Moved, x, 0);
+        assert_eq!(
+            kinds(&router.route(moved(1), Some(a), now)),
+            [(a, MouseEventKind::Enter), (a, MouseEventKind::Moved)]
+        );
+        let down = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 1, 0);
+        router.route(down, Some(a), now);
+        assert_eq!(
+            kinds(&router.route(moved(5), Some(b), now)),
+            [
+                (a, MouseEventKind::Leave),
+                (b, MouseEventKind::Enter),
+                (a, MouseEventKind::Drag(MouseButton::Left)),
+            ]
+        );
+        let up = MouseEvent::new(MouseEventKind::Up(MouseButton::Left), 5, 0);
+        assert_eq!(
+            kinds(&router.route(up, Some(b), now)),
+            [(a, MouseEventKind::Up(MouseButton::Left))]
+        );
+        assert_eq!(router.hovered(), Some(b));
+        assert_eq!(
+            kinds(&router.route(moved(6), None, now)),
+            [(b, MouseEventKind::Leave)]
+        );
+    }
+
+    #[test]
+    fn detects_double_clicks() {
+        let target = MouseTarget::Widget(0);
+        let mut router = MouseRouter::default();
+        let start = Instant::now();
+        let mut click = |x, after| {
+            let down = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), x, 2);
+            let events = router.route(down, Some(target), start + after);
+            let up = MouseEvent::new(MouseEventKind::Up(MouseButton::Left), x, 2);
+            router.route(up, Some(target), start + after);
+            events
+                .iter()
+                .any(|(_, e)| e.kind == MouseEventKind::DoubleClick(MouseButton::Left))
+        };
+        assert!(!click(3, Duration::ZERO));
+        assert!(click(4, Duration::from_millis(200)));
+        assert!(
+            !click(4, Duration::from_millis(300)),
+            "a third click starts over"
+        );
+        assert!(!click(4, Duration::from_millis(900)));
+        assert!(!click(9, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn terminal_routes_to_topmost_element() {
+        let mut pane = Container::new(Rect::new(20.0, 4.0));
+        let lines: Vec<_> = (0..10).map(|i| format!("line {i}")).collect();
+        pane.set_content(lines.join("\n"));
+        let mut terminal = Terminal {
+            width: 20,
+            height: 4,
+            theme: Theme::default(),
+            containers: vec![pane],
+            widgets: Vec::new(),
+            mouse: MouseRouter::default(),
+        };
+        terminal.add_widget(Button::new(Rect::new(6.0, 1.0), "Ok"));
+
+        assert_eq!(terminal.hit_test(2, 0), Some(MouseTarget::Widget(0)));
+        assert_eq!(terminal.hit_test(2, 1), Some(MouseTarget::Container(0)));
+        assert_eq!(terminal.hit_test(30, 1), None);
+
+        let scroll = MouseEvent::new(MouseEventKind::ScrollDown, 2, 1);
+        let routed = terminal.handle_mouse(scroll);
+        assert_eq!(terminal.containers[0].scroll_offset(), 3);
+        let last = routed.last().unwrap();
+        assert_eq!(last.target, MouseTarget::Container(0));
+        assert_eq!(last.local, Some((2, 4)));
+    }
+}
@@ -0,0 +1,554 @@
+use std::fmt::{self, Debug};
+
+use ansi_term::Style;
+use glam::Vec2;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::{Container, TuiBorder},
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    input::TextInput,
+    theme::{Role, Theme},
+    widget::{local_position, Widget},
+};
+
+const MAX_WIDTH: usize = 60;
+const MAX_ROWS: usize = 10;
+const RECENT: usize = 10;
+/// Narrowest label column that keybinding hints leave free.
+const MIN_LABEL_WIDTH: usize = 8;
+
+/// Matches `pattern` as a case-insensitive subsequence of `candidate`.
+///
+/// Whitespace in the pattern is ignored. Matches at word starts and runs of
+/// consecutive characters score higher, gaps lower. Returns the best score
+/// with the char indices of the matched characters.
+#[must_use]
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<(isize, Vec<usize>)> {
+    let pattern: Vec<char> = pattern.chars().filter(|c| !c.is_whitespace()).collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let Some(&first) = pattern.first() else {
+        return Some((0, Vec::new()));
+    };
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+    let word_start = |i: usize| {
+        i == 0
+            || !chars[i - 1].is_alphanumeric()
+            || (chars[i - 1].is_lowercase() && chars[i].is_uppercase())
+    };
+
+    // Tries every place the first character matches and takes the rest
+    // greedily from there.
+    let attempt = |start: usize| {
+        let mut positions = Vec::with_capacity(pattern.len());
+        let mut index = start;
+        for &wanted in &pattern {
+            index += chars[index..].iter().position(|&c| same(c, wanted))?;
+            positions.push(index);
+            index += 1;
+        }
+        let mut score = -start.min(10).cast_signed();
+        for (n, &position) in positions.iter().enumerate() {
+            score += if word_start(position) { 40 } else { 16 };
+            if let Some(previous) = n.checked_sub(1).map(|p| positions[p]) {
+                let gap = position - previous - 1;
+                score += if gap == 0 {
+                    16
+                } else {
+                    -gap.min(8).cast_signed()
+                };
+            }
+        }
+        Some((score, positions))
+    };
+    (0..chars.len())
+        .filter(|&start| same(chars[start], first))
+        .filter_map(attempt)
+        .max_by_key(|(score, positions)| (*score, std::cmp::Reverse(positions[0])))
+}
+
+/// An action that can be run from a [`CommandPalette`].
+pub struct Command {
+    id: String,
+    label: String,
+    keybinding: Option<KeyEvent>,
+    on_run: Option<Box<dyn FnMut()>>,
+}
+
+impl Command {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            keybinding: None,
+            on_run: None,
+        }
+    }
+
+    /// The key that runs the command outside the palette, shown as a hint.
+    #[must_use]
+    pub fn with_keybinding(self, keybinding: KeyEvent) -> Self {
+        Self {
+            keybinding: Some(keybinding),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn on_run(self, on_run: impl FnMut() + 'static) -> Self {
+        Self {
+            on_run: Some(Box::new(on_run)),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    #[must_use]
+    pub const fn keybinding(&self) -> Option<KeyEvent> {
+        self.keybinding
+    }
+}
+
+impl Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Command")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .field("keybinding", &self.keybinding)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A popup for finding and running registered commands by typing part of
+/// their name.
+///
+/// Results are ranked by [`fuzzy_match`], with recently run commands
+/// boosted. While open, the palette takes every key. Running a command
+/// calls its callback, closes the palette and reports the id through
+/// [`CommandPalette::take_activated`].
+#[derive(Debug)]
+pub struct CommandPalette {
+    area: Rect,
+    input: TextInput,
+    commands: Vec<Command>,
+    recent: Vec<String>,
+    results: Vec<(usize, Vec<usize>)>,
+    cursor: usize,
+    offset: usize,
+    open: bool,
+    activated: Option<String>,
+}
+
+impl CommandPalette {
+    /// Centres the palette horizontally near the top of `screen`.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn new(screen: &Rect) -> Self {
+        let (position, width, height) = screen.pos_width_height();
+        let (width, height) = (width as usize, height as usize);
+        let columns = width.saturating_sub(4).clamp(10, MAX_WIDTH);
+        let rows = height.saturating_sub(height / 6 + 4).clamp(1, MAX_ROWS);
+        let origin = position
+            + Vec2::new(
+                (width - columns.min(width)) as f32 / 2.0,
+                (height / 6) as f32,
+            );
+        let area = Rect::new(columns as f64, (rows + 4) as f64).with_position(origin.floor());
+        let field = Rect::new((columns - 2) as f64, 1.0).with_position(origin.floor() + Vec2::ONE);
+        let mut frame = Container::new(field);
+        frame.set_focused(true);
+        Self {
+            area,
+            input: TextInput::new(frame).with_placeholder("Type a command"),
+            commands: Vec::new(),
+            recent: Vec::new(),
+            results: Vec::new(),
+            cursor: 0,
+            offset: 0,
+            open: false,
+            activated: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_command(mut self, command: Command) -> Self {
+        self.register(command);
+        self
+    }
+
+    /// Adds a command, replacing any with the same id.
+    pub fn register(&mut self, command: Command) {
+        match self.commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+        self.filter();
+    }
+
+    #[must_use]
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Ids of recently run commands, most recent first.
+    #[must_use]
+    pub fn recent(&self) -> &[String] {
+        &self.recent
+    }
+
+    #[must_use]
+    pub fn query(&self) -> String {
+        self.input.value()
+    }
+
+    /// The ids of the commands currently listed, best match first.
+    #[must_use]
+    pub fn results(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .map(|(index, _)| self.commands[*index].id.as_str())
+            .collect()
+    }
+
+    #[must_use]
+    pub const fn selected(&self) -> usize {
+        self.cursor
+    }
+
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.input.set_value("");
+        self.filter();
+    }
+
+    pub const fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub const fn take_activated(&mut self) -> Option<String> {
+        self.activated.take()
+    }
+
+    /// Runs the command with `id` as if it had been picked from the list.
+    pub fn run(&mut self, id: &str) {
+        let Some(command) = self.commands.iter_mut().find(|c| c.id == id) else {
+            return;
+        };
+        if let Some(on_run) = &mut command.on_run {
+            on_run();
+        }
+        self.recent.retain(|recent| recent != id);
+        self.recent.insert(0, id.to_string());
+        self.recent.truncate(RECENT);
+        self.activated = Some(id.to_string());
+        self.close();
+    }
+
+    fn recency_bonus(&self, id: &str) -> isize {
+        self.recent
+            .iter()
+            .position(|recent| recent == id)
+            .map_or(0, |rank| 8 * (RECENT - rank).cast_signed())
+    }
+
+    fn filter(&mut self) {
+        let query = self.input.value();
+        let mut ranked: Vec<_> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                let (score, positions) = fuzzy_match(&query, &command.label)?;
+                let score = score + self.recency_bonus(&command.id);
+                Some((score, index, positions))
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.results = ranked
+            .into_iter()
+            .map(|(_, index, positions)| (index, positions))
+            .collect();
+        self.cursor = 0;
+        self.offset = 0;
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    const fn rows(&self) -> usize {
+        self.area.pos_width_height().2 as usize - 4
+    }
+
+    fn move_cursor(&mut self, cursor: usize) {
+        let rows = self.rows();
+        self.cursor = cursor.min(self.results.len().saturating_sub(1));
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + rows {
+            self.offset = self.cursor + 1 - rows;
+        }
+    }
+
+    fn run_selected(&mut self) {
+        if let Some((index, _)) = self.results.get(self.cursor) {
+            let id = self.commands[*index].id.clone();
+            self.run(&id);
+        }
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn result_cell(&self, result: usize, column: usize, theme: &Theme) -> Cell {
+        let role = if result == self.cursor {
+            Role::Selection
+        } else {
+            Role::Text
+        };
+        let style = theme.style(role);
+        let Some((index, positions)) = self.results.get(result) else {
+            return Cell::blank(style);
+        };
+        let command = &self.commands[*index];
+        let width = (self.area.pos_width_height().1 as usize).saturating_sub(2);
+        let hint = command
+            .keybinding
+            .map(|k| k.to_string())
+            .filter(|hint| hint.chars().count() + MIN_LABEL_WIDTH + 3 <= width)
+            .unwrap_or_default();
+        let hint_start = width.saturating_sub(1 + hint.chars().count());
+        if column >= hint_start {
+            let symbol = hint.chars().nth(column - hint_start).unwrap_or(' ');
+            let background = style.background;
+            let muted = theme.style(Role::TextMuted);
+            return Cell::new(
+                symbol,
+                Style {
+                    background,
+                    ..muted
+                },
+            );
+        }
+        let Some(position) = column.checked_sub(1) else {
+            return Cell::blank(style);
+        };
+        match command.label.chars().nth(position) {
+            Some(_) if position + 2 >= hint_start => Cell::new('…', style),
+            Some(symbol) if positions.contains(&position) => {
+                let foreground = theme.style(Role::Warning).foreground;
+                Cell::new(
+                    symbol,
+                    Style {
+                        foreground,
+                        ..style
+                    }
+                    .bold(),
+                )
+            }
+            Some(symbol) => Cell::new(symbol, style),
+            None => Cell::blank(style),
+        }
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        if !self.open {
+            return false;
+        }
+        let rows = self.rows();
+        match event.key {
+            Key::Esc => self.close(),
+            Key::Enter => self.run_selected(),
+            Key::Up => self.move_cursor(self.cursor.saturating_sub(1)),
+            Key::Down => self.move_cursor(self.cursor + 1),
+            Key::PageUp => self.move_cursor(self.cursor.saturating_sub(rows)),
+            Key::PageDown => self.move_cursor(self.cursor + rows),
+            _ => {
+                let query = self.input.value();
+                self.input.handle_key(event);
+                if self.input.value() != query {
+                    self.filter();
+                }
+            }
+        }
+        true
+    }
+}
+
+impl Widget for CommandPalette {
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        if !self.open {
+            return None;
+        }
+        if let Some(cell) = self.input.contents_of(x, y, theme) {
+            return Some(cell);
+        }
+        let (column, row) = local_position(&self.area, x, y)?;
+        let (_, width, height) = self.area.pos_width_height();
+        let (right, bottom) = (width as usize - 1, height as usize - 1);
+        let symbols = TuiBorder::SmoothCorner.symbols();
+        let border = |symbol| Some(Cell::new(symbol, theme.style(Role::BorderFocused)));
+        match (column, row) {
+            (0, 0) => border(symbols.top_left),
+            (_, 0) if column == right => border(symbols.top_right),
+            (0, _) if row == bottom => border(symbols.bottom_left),
+            _ if (column, row) == (right, bottom) => border(symbols.bottom_right),
+            (0, 2) => border(symbols.tee_right),
+            (_, 2) if column == right => border(symbols.tee_left),
+            (_, 0 | 2) => border(symbols.horizontal),
+            _ if row == bottom => border(symbols.horizontal),
+            (0, _) => border(symbols.vertical),
+            _ if column == right => border(symbols.vertical),
+            _ => Some(self.result_cell(self.offset + row - 3, column - 1, theme)),
+        }
+    }
+
+    fn cursor_position(&self) -> Option<(usize, usize)> {
+        self.open.then(|| self.input.cursor_position()).flatten()
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    /// Clicking a result runs it and clicking outside closes the palette.
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if !self.open {
+            return false;
+        }
+        let Some((_, row)) = local_position(&self.area, event.x, event.y) else {
+            if matches!(event.kind, MouseEventKind::Down(_)) {
+                self.close();
+            }
+            return true;
+        };
+        let result = row
+            .checked_sub(3)
+            .map(|row| self.offset + row)
+            .filter(|&result| result < self.results.len() && row < self.rows() + 3);
+        match (event.kind, result) {
+            (MouseEventKind::Moved, Some(result)) => self.cursor = result,
+            (MouseEventKind::Down(MouseButton::Left), Some(result)) => {
+                self.cursor = result;
+                self.run_selected();
+            }
+            (MouseEventKind::ScrollUp, _) => self.move_cursor(self.cursor.saturating_sub(1)),
+            (MouseEventKind::ScrollDown, _) => self.move_cursor(self.cursor + 1),
+            _ => {}
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell as Flag, rc::Rc};
+
+    use super::*;
+    use crate::tui::event::Modifiers;
+
+    fn palette() -> CommandPalette {
+        CommandPalette::new(&Rect::new(40.0, 14.0))
+            .with_command(Command::new("file.open", "Open File"))
+            .with_command(
+                Command::new("file.save", "Save File")
+                    .with_keybinding(KeyEvent::new(Key::Char('s'), Modifiers::CTRL)),
+            )
+            .with_command(Command::new("view.fold", "Toggle Folding"))
+            .with_command(Command::new("tab.close", "Close Tab"))
+    }
+
+    fn type_text(palette: &mut CommandPalette, text: &str) {
+        for symbol in text.chars() {
+            palette.handle_key(Key::Char(symbol).into());
+        }
+    }
+
+    #[test]
+    fn scores_word_starts_and_runs() {
+        assert_eq!(fuzzy_match("sf", "Save File").unwrap().1, vec![0, 5]);
+        assert_eq!(
+            fuzzy_match("FIL", "profile file").unwrap().1,
+            vec![8, 9, 10]
+        );
+        assert_eq!(fuzzy_match("xyz", "Save File"), None);
+        let score = |candidate| fuzzy_match("of", candidate).unwrap().0;
+        assert!(score("Open File") > score("Toggle Folding"));
+        assert!(score("openFile") > score("profile"));
+    }
+
+    #[test]
+    fn ranks_and_boosts_recent() {
+        let mut palette = palette();
+        palette.open();
+        type_text(&mut palette, "of");
+        assert_eq!(palette.results(), ["file.open", "view.fold"]);
+        palette.run("view.fold");
+        palette.open();
+        type_text(&mut palette, "o f");
+        assert_eq!(palette.results(), ["view.fold", "file.open"]);
+        palette.handle_key(Key::Esc.into());
+        palette.open();
+        assert_eq!(palette.results()[0], "view.fold");
+        assert_eq!(palette.recent(), ["view.fold"]);
+    }
+
+    #[test]
+    fn renders_hints_and_runs_callbacks() {
+        let saved = Rc::new(Flag::new(false));
+        let flag = saved.clone();
+        let mut palette = palette().with_command(
+            Command::new("file.save", "Save File")
+                .with_keybinding(KeyEvent::new(Key::Char('s'), Modifiers::CTRL))
+                .on_run(move || flag.set(true)),
+        );
+        palette.open();
+        type_text(&mut palette, "sav");
+        let theme = Theme::default();
+        let row: String = (0..40)
+            .map(|x| palette.contents_of(x, 5, &theme).map_or(' ', |c| c.symbol))
+            .collect();
+        assert_eq!(row.trim_end(), "  │ Save File                 Ctrl+S │");
+        let matched = palette.contents_of(5, 5, &theme).unwrap();
+        assert!(matched.style.is_bold);
+        assert_eq!(
+            matched.style.background,
+            theme.style(Role::Selection).background
+        );
+
+        palette.handle_key(Key::Enter.into());
+        assert!(saved.get());
+        assert!(!palette.is_open());
+        assert_eq!(palette.take_activated().as_deref(), Some("file.save"));
+        assert_eq!(palette.contents_of(5, 5, &theme), None);
+    }
+
+    #[test]
+    fn drops_wide_hints_and_truncates_labels() {
+        let binding = "ctrl+alt+shift+backspace".parse().unwrap();
+        let mut palette = CommandPalette::new(&Rect::new(24.0, 14.0)).with_command(
+            Command::new("edit.delete", "Delete PreviousWord").with_keybinding(binding),
+        );
+        palette.open();
+        type_text(&mut palette, "dpw");
+        let theme = Theme::default();
+        let row: String = (0..24)
+            .map(|x| palette.contents_of(x, 5, &theme).map_or(' ', |c| c.symbol))
+            .collect();
+        assert_eq!(row.trim_end(), "  │ Delete Previous… │");
+        let ellipsis = palette.contents_of(19, 5, &theme).unwrap();
+        assert!(!ellipsis.style.is_bold);
+    }
+}
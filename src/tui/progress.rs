@@ -0,0 +1,379 @@
+use std::{cmp::Ordering, time::Duration};
+
+use crate::rect::Rect;
+
+use super::{
+    cell::{paint, Cell},
+    table::Alignment,
+    theme::{Role, Theme},
+    widget::{local_position, Widget},
+};
+
+const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+const FULL: char = '█';
+
+#[allow(clippy::cast_sign_loss)]
+fn bar_symbol(ratio: f64, column: usize, width: usize) -> Option<char> {
+    let filled = ratio.clamp(0.0, 1.0) * width as f64;
+    let full = filled.floor() as usize;
+    match column.cmp(&full) {
+        Ordering::Less => Some(FULL),
+        Ordering::Equal => {
+            let eighths = ((filled - filled.floor()) * 8.0) as usize;
+            (eighths > 0).then_some(EIGHTHS[eighths])
+        }
+        Ordering::Greater => None,
+    }
+}
+
+fn bar_cell(ratio: f64, column: usize, width: usize, theme: &Theme) -> Cell {
+    bar_symbol(ratio, column, width).map_or_else(
+        || Cell::blank(theme.style(Role::Text)),
+        |symbol| Cell::new(symbol, theme.style(Role::Progress)),
+    )
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgressBar {
+    area: Rect,
+    ratio: f64,
+}
+
+impl ProgressBar {
+    #[must_use]
+    pub const fn new(area: Rect) -> Self {
+        Self { area, ratio: 0.0 }
+    }
+
+    #[must_use]
+    pub const fn with_ratio(self, ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub const fn set_ratio(&mut self, ratio: f64) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+    }
+
+    #[must_use]
+    pub const fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    #[must_use]
+    pub fn render_line(&self, width: usize, theme: &Theme) -> String {
+        paint((0..width).map(|column| bar_cell(self.ratio, column, width, theme)))
+    }
+}
+
+impl Widget for ProgressBar {
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, _) = local_position(&self.area, x, y)?;
+        let (_, width, _) = self.area.pos_width_height();
+        Some(bar_cell(self.ratio, column, width as usize, theme))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Gauge {
+    area: Rect,
+    ratio: f64,
+    label: Option<String>,
+}
+
+impl Gauge {
+    #[must_use]
+    pub const fn new(area: Rect) -> Self {
+        Self {
+            area,
+            ratio: 0.0,
+            label: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_ratio(self, ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    pub const fn set_ratio(&mut self, ratio: f64) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    #[must_use]
+    pub const fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// The custom label, or the percentage when none is set.
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.label
+            .clone()
+            .unwrap_or_else(|| format!("{:.0}%", self.ratio * 100.0))
+    }
+
+    fn cell_at(&self, column: usize, width: usize, with_label: bool, theme: &Theme) -> Cell {
+        let bar = bar_cell(self.ratio, column, width, theme);
+        if !with_label {
+            return bar;
+        }
+        let label = self.label();
+        let start = Alignment::Center.offset(label.chars().count(), width);
+        let Some(symbol) = column.checked_sub(start).and_then(|i| label.chars().nth(i)) else {
+            return bar;
+        };
+        if bar.symbol == FULL {
+            Cell::new(symbol, theme.style(Role::Progress).reverse())
+        } else {
+            Cell::new(symbol, theme.style(Role::Text))
+        }
+    }
+
+    #[must_use]
+    pub fn render_line(&self, width: usize, theme: &Theme) -> String {
+        paint((0..width).map(|column| self.cell_at(column, width, true, theme)))
+    }
+}
+
+impl Widget for Gauge {
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, row) = local_position(&self.area, x, y)?;
+        let (_, width, height) = self.area.pos_width_height();
+        let middle = row == height as usize / 2;
+        Some(self.cell_at(column, width as usize, middle, theme))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IndeterminateBar {
+    area: Rect,
+    elapsed: Duration,
+    period: Duration,
+}
+
+impl IndeterminateBar {
+    #[must_use]
+    pub const fn new(area: Rect) -> Self {
+        Self {
+            area,
+            elapsed: Duration::ZERO,
+            period: Duration::from_secs(2),
+        }
+    }
+
+    /// Time for the block to travel to the far end and back.
+    #[must_use]
+    pub const fn with_period(self, period: Duration) -> Self {
+        Self { period, ..self }
+    }
+
+    pub const fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn block(&self, width: usize) -> (usize, usize) {
+        let length = (width / 4).max(1).min(width);
+        let travel = width - length;
+        let period = self.period.as_secs_f64().max(f64::EPSILON);
+        let phase = (self.elapsed.as_secs_f64() % period) / period;
+        let bounce = 1.0 - phase.mul_add(2.0, -1.0).abs();
+        let start = (bounce * travel as f64).round() as usize;
+        (start, start + length)
+    }
+
+    fn cell_at(&self, column: usize, width: usize, theme: &Theme) -> Cell {
+        let (start, end) = self.block(width);
+        if (start..end).contains(&column) {
+            Cell::new(FULL, theme.style(Role::Progress))
+        } else {
+            Cell::blank(theme.style(Role::Text))
+        }
+    }
+
+    #[must_use]
+    pub fn render_line(&self, width: usize, theme: &Theme) -> String {
+        paint((0..width).map(|column| self.cell_at(column, width, theme)))
+    }
+}
+
+impl Widget for IndeterminateBar {
+    #[allow(clippy::cast_sign_loss)]
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, _) = local_position(&self.area, x, y)?;
+        let (_, width, _) = self.area.pos_width_height();
+        Some(self.cell_at(column, width as usize, theme))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpinnerStyle {
+    #[default]
+    Dots,
+    Line,
+    Arc,
+    Quadrants,
+    Bounce,
+}
+
+impl SpinnerStyle {
+    #[must_use]
+    pub const fn frames(self) -> &'static [char] {
+        match self {
+            Self::Dots => &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'],
+            Self::Line => &['-', '\\', '|', '/'],
+            Self::Arc => &['◜', '◠', '◝', '◞', '◡', '◟'],
+            Self::Quadrants => &['▖', '▘', '▝', '▗'],
+            Self::Bounce => &['⠁', '⠂', '⠄', '⠂'],
+        }
+    }
+
+    #[must_use]
+    pub const fn interval(self) -> Duration {
+        match self {
+            Self::Dots | Self::Arc => Duration::from_millis(80),
+            Self::Line | Self::Quadrants => Duration::from_millis(130),
+            Self::Bounce => Duration::from_millis(120),
+        }
+    }
+
+    #[must_use]
+    pub fn frame_at(self, elapsed: Duration) -> char {
+        let frames = self.frames();
+        let index = elapsed.as_millis() / self.interval().as_millis();
+        frames[(index % frames.len() as u128) as usize]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Spinner {
+    area: Rect,
+    style: SpinnerStyle,
+    elapsed: Duration,
+    label: Option<String>,
+}
+
+impl Spinner {
+    #[must_use]
+    pub const fn new(area: Rect) -> Self {
+        Self {
+            area,
+            style: SpinnerStyle::Dots,
+            elapsed: Duration::ZERO,
+            label: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_style(self, style: SpinnerStyle) -> Self {
+        Self { style, ..self }
+    }
+
+    #[must_use]
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    pub const fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+    }
+
+    fn cell_at(&self, column: usize, theme: &Theme) -> Cell {
+        match (column, &self.label) {
+            (0, _) => Cell::new(
+                self.style.frame_at(self.elapsed),
+                theme.style(Role::Progress),
+            ),
+            (column, Some(label)) => Cell::new(
+                column
+                    .checked_sub(2)
+                    .and_then(|i| label.chars().nth(i))
+                    .unwrap_or(' '),
+                theme.style(Role::Text),
+            ),
+            _ => Cell::blank(theme.style(Role::Text)),
+        }
+    }
+
+    #[must_use]
+    pub fn render_line(&self, theme: &Theme) -> String {
+        let width = self
+            .label
+            .as_ref()
+            .map_or(1, |label| label.chars().count() + 2);
+        paint((0..width).map(|column| self.cell_at(column, theme)))
+    }
+}
+
+impl Widget for Spinner {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, _) = local_position(&self.area, x, y)?;
+        Some(self.cell_at(column, theme))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sub_cell_precision() {
+        let symbols: String = (0..4)
+            .map(|column| bar_symbol(0.3, column, 4).unwrap_or(' '))
+            .collect();
+        assert_eq!(symbols, "█▏  ");
+        assert_eq!(bar_symbol(1.0, 3, 4), Some(FULL));
+        assert_eq!(bar_symbol(0.0, 0, 4), None);
+    }
+
+    #[test]
+    fn gauge_label_is_centered() {
+        let theme = Theme::default();
+        let gauge = Gauge::new(Rect::new(10.0, 1.0)).with_ratio(0.5);
+        let symbols: String = (0..10)
+            .map(|x| gauge.contents_of(x, 0, &theme).unwrap().symbol)
+            .collect();
+        assert_eq!(symbols, "███50%    ");
+    }
+
+    #[test]
+    fn indeterminate_bar_bounces() {
+        let mut bar = IndeterminateBar::new(Rect::new(8.0, 1.0));
+        assert_eq!(bar.block(8), (0, 2));
+        bar.set_elapsed(Duration::from_secs(1));
+        assert_eq!(bar.block(8), (6, 8));
+        bar.set_elapsed(Duration::from_millis(1500));
+        assert_eq!(bar.block(8), (3, 5));
+    }
+
+    #[test]
+    fn spinner_frames() {
+        let style = SpinnerStyle::Line;
+        assert_eq!(style.frame_at(Duration::ZERO), '-');
+        assert_eq!(style.frame_at(Duration::from_millis(270)), '|');
+        assert_eq!(style.frame_at(Duration::from_millis(520)), '-');
+    }
+}
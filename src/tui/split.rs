@@ -0,0 +1,419 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use glam::Vec2;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    theme::{Role, Theme},
+    widget::{Direction, Widget},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    First,
+    Second,
+}
+
+/// What is worth keeping of a [`Split`] between runs. Written as the ratio
+/// followed by the collapsed pane, if any, e.g. `0.3` or `0.3 second`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplitState {
+    pub ratio: f64,
+    pub collapsed: Option<Pane>,
+}
+
+impl Display for SplitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ratio)?;
+        match self.collapsed {
+            Some(Pane::First) => f.write_str(" first"),
+            Some(Pane::Second) => f.write_str(" second"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for SplitState {
+    type Err = InvalidSplitState;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidSplitState(s.to_string());
+        let mut words = s.split_whitespace();
+        let ratio: f64 = words
+            .next()
+            .and_then(|r| r.parse().ok())
+            .ok_or_else(invalid)?;
+        let collapsed = match words.next() {
+            None => None,
+            Some("first") => Some(Pane::First),
+            Some("second") => Some(Pane::Second),
+            Some(_) => return Err(invalid()),
+        };
+        if !(0.0..=1.0).contains(&ratio) || words.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self { ratio, collapsed })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSplitState(pub String);
+
+impl Display for InvalidSplitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid split state `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidSplitState {}
+
+/// Two panes sharing an area, with a one cell divider between them.
+///
+/// The divider sits at a ratio of the space left for the panes, so it keeps
+/// its relative place when the area is resized. It can be dragged with the
+/// mouse, and moved with Alt and the arrow keys while focused; Alt+Home and
+/// Alt+End collapse the first or second pane. The split only draws the
+/// divider; lay out the panes themselves with [`Split::panes`].
+#[derive(Debug, Clone)]
+pub struct Split {
+    area: Rect,
+    direction: Direction,
+    ratio: f64,
+    min: [f64; 2],
+    max: [f64; 2],
+    collapsed: Option<Pane>,
+    focused: bool,
+    hovered: bool,
+    dragging: bool,
+}
+
+impl Split {
+    /// Horizontal puts the panes side by side, vertical stacks them.
+    #[must_use]
+    pub const fn new(area: Rect, direction: Direction) -> Self {
+        Self {
+            area,
+            direction,
+            ratio: 0.5,
+            min: [0.0; 2],
+            max: [f64::INFINITY; 2],
+            collapsed: None,
+            focused: false,
+            hovered: false,
+            dragging: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_ratio(mut self, ratio: f64) -> Self {
+        self.set_ratio(ratio);
+        self
+    }
+
+    /// Keeps `pane` at least `cells` long unless it is collapsed.
+    #[must_use]
+    pub const fn with_min_size(mut self, pane: Pane, cells: f64) -> Self {
+        self.min[pane as usize] = cells;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_size(mut self, pane: Pane, cells: f64) -> Self {
+        self.max[pane as usize] = cells;
+        self
+    }
+
+    #[must_use]
+    pub const fn area(&self) -> &Rect {
+        &self.area
+    }
+
+    pub const fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    #[must_use]
+    pub const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    #[must_use]
+    pub const fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Moves the divider and expands a collapsed pane.
+    pub const fn set_ratio(&mut self, ratio: f64) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self.collapsed = None;
+    }
+
+    #[must_use]
+    pub const fn collapsed(&self) -> Option<Pane> {
+        self.collapsed
+    }
+
+    /// Shrinks `pane` to nothing, keeping the ratio to restore it later.
+    pub const fn collapse(&mut self, pane: Pane) {
+        self.collapsed = Some(pane);
+    }
+
+    pub const fn expand(&mut self) {
+        self.collapsed = None;
+    }
+
+    pub fn toggle_collapse(&mut self, pane: Pane) {
+        if self.collapsed == Some(pane) {
+            self.expand();
+        } else {
+            self.collapse(pane);
+        }
+    }
+
+    #[must_use]
+    pub const fn state(&self) -> SplitState {
+        SplitState {
+            ratio: self.ratio,
+            collapsed: self.collapsed,
+        }
+    }
+
+    pub const fn restore(&mut self, state: SplitState) {
+        self.set_ratio(state.ratio);
+        self.collapsed = state.collapsed;
+    }
+
+    pub const fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    #[must_use]
+    pub const fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Start and length of the area along the split axis.
+    fn axis(&self) -> (f64, f64) {
+        let (position, width, height) = self.area.pos_width_height();
+        match self.direction {
+            Direction::Horizontal => (f64::from(position.x), width),
+            Direction::Vertical => (f64::from(position.y), height),
+        }
+    }
+
+    /// Room for the panes, without the divider.
+    fn available(&self) -> f64 {
+        (self.axis().1 - 1.0).max(0.0)
+    }
+
+    /// `size` for the first pane, moved as little as needed to respect the
+    /// limits of both panes.
+    fn clamped(&self, size: f64) -> f64 {
+        let available = self.available();
+        let low = self.min[0].max(available - self.max[1]);
+        let high = self.max[0].min(available - self.min[1]);
+        size.max(low).min(high).clamp(0.0, available).round()
+    }
+
+    /// Length of the first pane in cells.
+    #[must_use]
+    pub fn first_size(&self) -> f64 {
+        match self.collapsed {
+            Some(Pane::First) => 0.0,
+            Some(Pane::Second) => self.available(),
+            None => self.clamped(self.available() * self.ratio),
+        }
+    }
+
+    fn set_first_size(&mut self, size: f64) {
+        let available = self.available();
+        let size = self.clamped(size);
+        self.set_ratio(if available > 0.0 {
+            size / available
+        } else {
+            0.5
+        });
+    }
+
+    /// Moves the divider by `cells`, expanding a collapsed pane.
+    pub fn resize_by(&mut self, cells: f64) {
+        self.set_first_size(self.first_size() + cells);
+    }
+
+    fn cut(&self, rect: &mut Rect, amount: f64) -> Rect {
+        match self.direction {
+            Direction::Horizontal => rect.cut_left(amount),
+            Direction::Vertical => rect.cut_top(amount),
+        }
+    }
+
+    /// The areas of the first pane, the divider and the second pane.
+    fn layout(&self) -> [Rect; 3] {
+        let mut rest = self.area.clone();
+        let first = self.cut(&mut rest, self.first_size());
+        let divider = self.cut(&mut rest, self.axis().1.min(1.0));
+        [first, divider, rest]
+    }
+
+    #[must_use]
+    pub fn panes(&self) -> (Rect, Rect) {
+        let [first, _, second] = self.layout();
+        (first, second)
+    }
+
+    #[must_use]
+    pub fn divider(&self) -> Rect {
+        let [_, divider, _] = self.layout();
+        divider
+    }
+
+    fn on_divider(&self, x: usize, y: usize) -> bool {
+        self.divider().contains(Vec2::new(x as f32, y as f32))
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        if !self.focused || !event.modifiers.alt {
+            return false;
+        }
+        match (self.direction, event.key) {
+            (Direction::Horizontal, Key::Left) | (Direction::Vertical, Key::Up) => {
+                self.resize_by(-1.0);
+            }
+            (Direction::Horizontal, Key::Right) | (Direction::Vertical, Key::Down) => {
+                self.resize_by(1.0);
+            }
+            (_, Key::Home) => self.toggle_collapse(Pane::First),
+            (_, Key::End) => self.toggle_collapse(Pane::Second),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl Widget for Split {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        if !self.on_divider(x, y) {
+            return None;
+        }
+        let symbol = match self.direction {
+            Direction::Horizontal => '│',
+            Direction::Vertical => '─',
+        };
+        let role = if self.focused || self.hovered || self.dragging {
+            Role::BorderFocused
+        } else {
+            Role::Border
+        };
+        Some(Cell::new(symbol, theme.style(role)))
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    /// Dragging the divider moves it, and double-clicking it splits evenly.
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let on_divider = self.on_divider(event.x, event.y);
+        self.hovered = on_divider && event.kind != MouseEventKind::Leave;
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) if on_divider => self.dragging = true,
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                let position = match self.direction {
+                    Direction::Horizontal => event.x,
+                    Direction::Vertical => event.y,
+                };
+                self.set_first_size(position as f64 - self.axis().0);
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.dragging => self.dragging = false,
+            MouseEventKind::DoubleClick(MouseButton::Left) if on_divider => self.set_ratio(0.5),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::event::Modifiers;
+
+    fn sizes(split: &Split) -> (f64, f64, f64) {
+        let (first, second) = split.panes();
+        let length = |rect: &Rect| match split.direction() {
+            Direction::Horizontal => rect.pos_width_height().1,
+            Direction::Vertical => rect.pos_width_height().2,
+        };
+        let start = match split.direction() {
+            Direction::Horizontal => second.pos_width_height().0.x,
+            Direction::Vertical => second.pos_width_height().0.y,
+        };
+        (length(&first), f64::from(start), length(&second))
+    }
+
+    #[test]
+    fn splits_by_ratio() {
+        let area = Rect::new(21.0, 8.0).with_position(Vec2::new(4.0, 2.0));
+        let split = Split::new(area.clone(), Direction::Horizontal).with_ratio(0.25);
+        assert_eq!(sizes(&split), (5.0, 10.0, 15.0));
+        let divider = split.divider();
+        assert_eq!(divider.pos_width_height(), (Vec2::new(9.0, 2.0), 1.0, 8.0));
+
+        let stacked = Split::new(area, Direction::Vertical);
+        assert_eq!(sizes(&stacked), (4.0, 7.0, 3.0));
+        let theme = Theme::default();
+        assert_eq!(stacked.contents_of(4, 6, &theme).unwrap().symbol, '─');
+        assert_eq!(stacked.contents_of(4, 5, &theme), None);
+    }
+
+    #[test]
+    fn limits_and_collapse() {
+        let mut split = Split::new(Rect::new(21.0, 1.0), Direction::Horizontal)
+            .with_min_size(Pane::First, 4.0)
+            .with_max_size(Pane::Second, 10.0)
+            .with_ratio(0.1);
+        assert_eq!(sizes(&split), (10.0, 11.0, 10.0));
+        split.set_ratio(0.0);
+        assert_eq!(split.first_size(), 10.0);
+        split.set_ratio(0.9);
+        assert_eq!(split.first_size(), 18.0);
+
+        split.collapse(Pane::First);
+        assert_eq!(sizes(&split), (0.0, 1.0, 20.0));
+        split.toggle_collapse(Pane::Second);
+        assert_eq!(sizes(&split), (20.0, 21.0, 0.0));
+        split.expand();
+        assert_eq!(split.first_size(), 18.0);
+    }
+
+    #[test]
+    fn drags_and_persists() {
+        let mut split = Split::new(Rect::new(21.0, 4.0), Direction::Horizontal);
+        let event = |kind, x| MouseEvent::new(kind, x, 1);
+        assert!(!split.handle_mouse(event(MouseEventKind::Down(MouseButton::Left), 3)));
+        assert!(split.handle_mouse(event(MouseEventKind::Down(MouseButton::Left), 10)));
+        split.handle_mouse(event(MouseEventKind::Drag(MouseButton::Left), 5));
+        split.handle_mouse(event(MouseEventKind::Up(MouseButton::Left), 5));
+        assert_eq!(split.ratio(), 0.25);
+
+        let alt = |key| KeyEvent::new(key, Modifiers::ALT);
+        assert!(!split.handle_key(alt(Key::Right)), "not focused");
+        split.set_focused(true);
+        split.handle_key(alt(Key::Right));
+        split.handle_key(alt(Key::End));
+        assert_eq!(split.state().to_string(), "0.3 second");
+
+        let mut restored = Split::new(Rect::new(41.0, 4.0), Direction::Horizontal);
+        restored.restore("0.3 second".parse().unwrap());
+        assert_eq!(restored.state(), split.state());
+        restored.handle_mouse(event(MouseEventKind::DoubleClick(MouseButton::Left), 40));
+        assert_eq!(restored.state().to_string(), "0.5");
+        assert!("1.5".parse::<SplitState>().is_err());
+        assert!("0.5 third".parse::<SplitState>().is_err());
+    }
+}
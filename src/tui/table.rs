@@ -0,0 +1,520 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug},
+};
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    container::Container,
+    event::{Key, KeyEvent},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    Fixed(usize),
+    Percent(u16),
+    Min(usize),
+    Max(usize),
+    FitContent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    /// Offset of text `len` cells long inside a slot `width` cells wide.
+    #[must_use]
+    pub const fn offset(self, len: usize, width: usize) -> usize {
+        let free = width.saturating_sub(len);
+        match self {
+            Self::Left => 0,
+            Self::Center => free / 2,
+            Self::Right => free,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    const fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+
+    const fn indicator(self) -> &'static str {
+        match self {
+            Self::Ascending => " ▲",
+            Self::Descending => " ▼",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    header: String,
+    constraint: Constraint,
+    alignment: Alignment,
+}
+
+impl Column {
+    pub fn new(header: impl Into<String>, constraint: Constraint) -> Self {
+        Self {
+            header: header.into(),
+            constraint,
+            alignment: Alignment::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_alignment(self, alignment: Alignment) -> Self {
+        Self { alignment, ..self }
+    }
+}
+
+pub type Row = Vec<String>;
+type SortCallback = Box<dyn FnMut(&mut [Row], usize, SortOrder)>;
+
+pub struct Table {
+    frame: Container,
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+    widths: Vec<usize>,
+    cursor: Option<usize>,
+    offset: usize,
+    sort: Option<(usize, SortOrder)>,
+    on_sort: Option<SortCallback>,
+}
+
+impl Table {
+    #[must_use]
+    pub fn new(frame: Container, columns: Vec<Column>) -> Self {
+        let mut table = Self {
+            frame,
+            columns,
+            rows: Vec::new(),
+            widths: Vec::new(),
+            cursor: None,
+            offset: 0,
+            sort: None,
+            on_sort: None,
+        };
+        table.update_widths();
+        table
+    }
+
+    #[must_use]
+    pub fn with_rows(mut self, rows: impl IntoIterator<Item = Row>) -> Self {
+        self.set_rows(rows);
+        self
+    }
+
+    #[must_use]
+    pub fn with_sort_callback(
+        self,
+        on_sort: impl FnMut(&mut [Row], usize, SortOrder) + 'static,
+    ) -> Self {
+        Self {
+            on_sort: Some(Box::new(on_sort)),
+            ..self
+        }
+    }
+
+    pub fn set_rows(&mut self, rows: impl IntoIterator<Item = Row>) {
+        self.rows = rows.into_iter().collect();
+        self.offset = 0;
+        self.cursor = if self.rows.is_empty() { None } else { Some(0) };
+        self.update_widths();
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    #[must_use]
+    pub fn widths(&self) -> &[usize] {
+        &self.widths
+    }
+
+    #[must_use]
+    pub const fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[must_use]
+    pub const fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    /// Sorting the same column again flips the order. The rows themselves are
+    /// reordered by the sort callback, the table only tracks the indicator.
+    pub fn sort_by(&mut self, column: usize) {
+        if column >= self.columns.len() {
+            return;
+        }
+        let order = match self.sort {
+            Some((current, order)) if current == column => order.reversed(),
+            _ => SortOrder::Ascending,
+        };
+        self.sort = Some((column, order));
+        if let Some(on_sort) = &mut self.on_sort {
+            // Rows can't be told apart from equal ones, so the cursor stays
+            // on the row with the same contents and the same rank among them.
+            let selected = self.cursor.map(|cursor| {
+                let row = self.rows[cursor].clone();
+                let rank = self.rows[..cursor].iter().filter(|r| **r == row).count();
+                (row, rank)
+            });
+            on_sort(&mut self.rows, column, order);
+            if let Some((row, rank)) = selected {
+                let mut equal = self.rows.iter().enumerate().filter(|(_, r)| **r == row);
+                self.cursor = equal.nth(rank).map(|(i, _)| i).or(self.cursor);
+                self.scroll_to_cursor();
+            }
+        }
+        self.update_widths();
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.rows.len() {
+            self.cursor = Some(index);
+            self.scroll_to_cursor();
+        }
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let len = self.rows.len();
+        let Some(cursor) = self.cursor else {
+            return false;
+        };
+        let page = self.visible_rows().max(1);
+        let next = match event.key {
+            Key::Up => (cursor + len - 1) % len,
+            Key::Down => (cursor + 1) % len,
+            Key::Home => 0,
+            Key::End => len - 1,
+            Key::PageUp => cursor.saturating_sub(page),
+            Key::PageDown => (cursor + page).min(len - 1),
+            _ => return false,
+        };
+        self.select(next);
+        true
+    }
+
+    fn visible_rows(&self) -> usize {
+        self.frame.content_size().1.saturating_sub(2)
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        let rows = self.visible_rows().max(1);
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if cursor >= self.offset + rows {
+            self.offset = cursor + 1 - rows;
+        }
+    }
+
+    fn update_widths(&mut self) {
+        let fit: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let header = column.header.chars().count()
+                    + SortOrder::Ascending.indicator().chars().count();
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|text| text.chars().count())
+                    .fold(header, usize::max)
+            })
+            .collect();
+        let constraints: Vec<Constraint> = self.columns.iter().map(|c| c.constraint).collect();
+        self.widths = layout_columns(&constraints, &fit, self.frame.content_size().0);
+    }
+
+    fn header_text(&self, column: usize) -> String {
+        let header = &self.columns[column].header;
+        match self.sort {
+            Some((sorted, order)) if sorted == column => format!("{header}{}", order.indicator()),
+            _ => header.clone(),
+        }
+    }
+
+    fn slot_at(&self, x: usize) -> Slot {
+        let mut start = 0;
+        for (i, width) in self.widths.iter().enumerate() {
+            if x < start + width {
+                return Slot::Column(i, x - start);
+            }
+            start += width;
+            if x == start && i + 1 < self.widths.len() {
+                return Slot::Separator;
+            }
+            start += 1;
+        }
+        Slot::Outside
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Column(usize, usize),
+    Separator,
+    Outside,
+}
+
+/// Splits `width` cells between columns, leaving one cell between each pair
+/// for a separator. Whatever `Fixed`, `Percent` and `FitContent` columns leave
+/// over is shared between `Min` and `Max` columns, and what a `Max` column
+/// can't take goes to the other ones.
+#[allow(clippy::cast_sign_loss)]
+fn layout_columns(constraints: &[Constraint], fit: &[usize], width: usize) -> Vec<usize> {
+    let available = width.saturating_sub(constraints.len().saturating_sub(1));
+    let mut widths: Vec<usize> = constraints
+        .iter()
+        .zip(fit)
+        .map(|(constraint, &fit)| match *constraint {
+            Constraint::Fixed(width) | Constraint::Min(width) => width,
+            Constraint::Percent(percent) => available * usize::from(percent.min(100)) / 100,
+            Constraint::Max(_) => 0,
+            Constraint::FitContent => fit,
+        })
+        .collect();
+
+    let used: usize = widths.iter().sum();
+    let mut flexible: Vec<usize> = constraints
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| matches!(c, Constraint::Min(_) | Constraint::Max(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let mut leftover = available.saturating_sub(used);
+    while leftover > 0 && !flexible.is_empty() {
+        let shares = shares(leftover, flexible.len());
+        // `Max` columns that their share would push past the cap stop there,
+        // and what they don't take is shared again between the others.
+        let capped: Vec<(usize, usize)> = flexible
+            .iter()
+            .zip(&shares)
+            .filter_map(|(&i, share)| match constraints[i] {
+                Constraint::Max(max) if widths[i] + share >= max => Some((i, max)),
+                _ => None,
+            })
+            .collect();
+        if capped.is_empty() {
+            for (&i, share) in flexible.iter().zip(shares) {
+                widths[i] += share;
+            }
+            break;
+        }
+        for (i, max) in capped {
+            leftover -= max.saturating_sub(widths[i]).min(leftover);
+            widths[i] = widths[i].max(max);
+            flexible.retain(|&j| j != i);
+        }
+    }
+
+    let mut overflow = widths.iter().sum::<usize>().saturating_sub(available);
+    for width in widths.iter_mut().rev() {
+        let cut = overflow.min(*width);
+        *width -= cut;
+        overflow -= cut;
+    }
+    widths
+}
+
+/// Splits `width` cells into `count` shares the same way
+/// [`Rect::divide_horizontally`] does.
+#[allow(clippy::cast_sign_loss)]
+fn shares(width: usize, count: usize) -> Vec<usize> {
+    let area = Rect::new(width as f64, 1.0);
+    let areas = if count == 1 {
+        vec![area]
+    } else {
+        area.divide_horizontally(count)
+    };
+    areas
+        .iter()
+        .map(|share| {
+            let (position, share_width, _) = share.pos_width_height();
+            let start = f64::from(position.x).round();
+            let end = (f64::from(position.x) + share_width).round();
+            (end - start) as usize
+        })
+        .collect()
+}
+
+impl Widget for Table {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let symbols = self.frame.border().symbols();
+        let slot = self.slot_at(x);
+        if y == 1 {
+            let symbol = match slot {
+                Slot::Separator => symbols.cross,
+                _ => symbols.horizontal,
+            };
+            return Some(Cell::new(symbol, theme.style(Role::Border)));
+        }
+
+        let (row, style) = if y == 0 {
+            (None, theme.style(Role::Title))
+        } else {
+            let index = self.offset + y - 2;
+            let Some(row) = self.rows.get(index) else {
+                return Some(Cell::blank(theme.style(Role::Text)));
+            };
+            let role = if self.cursor == Some(index) {
+                Role::Selection
+            } else {
+                Role::Text
+            };
+            (Some(row), theme.style(role))
+        };
+        Some(match slot {
+            Slot::Column(i, offset) => {
+                let text = row.map_or_else(
+                    || Cow::Owned(self.header_text(i)),
+                    |row| Cow::Borrowed(row.get(i).map_or("", String::as_str)),
+                );
+                let symbol = aligned_char(&text, self.columns[i].alignment, self.widths[i], offset);
+                Cell::new(symbol, style)
+            }
+            Slot::Separator => Cell::new(symbols.vertical, theme.style(Role::Border)),
+            Slot::Outside => Cell::blank(style),
+        })
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+}
+
+fn aligned_char(text: &str, alignment: Alignment, width: usize, offset: usize) -> char {
+    let start = alignment.offset(text.chars().count(), width);
+    offset
+        .checked_sub(start)
+        .and_then(|i| text.chars().nth(i))
+        .unwrap_or(' ')
+}
+
+impl Debug for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Table")
+            .field("frame", &self.frame)
+            .field("columns", &self.columns)
+            .field("rows", &self.rows.len())
+            .field("widths", &self.widths)
+            .field("cursor", &self.cursor)
+            .field("offset", &self.offset)
+            .field("sort", &self.sort)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::container::TuiBorder;
+
+    #[test]
+    fn column_constraints() {
+        let constraints = [
+            Constraint::Fixed(4),
+            Constraint::Percent(25),
+            Constraint::FitContent,
+            Constraint::Min(3),
+            Constraint::Max(5),
+        ];
+        let widths = layout_columns(&constraints, &[0, 0, 6, 0, 0], 44);
+        assert_eq!(widths, vec![4, 10, 6, 15, 5]);
+        let constraints = [Constraint::Max(2), Constraint::Min(1), Constraint::Max(20)];
+        assert_eq!(layout_columns(&constraints, &[0; 3], 32), vec![2, 15, 13]);
+    }
+
+    #[test]
+    fn columns_shrink_to_fit() {
+        let constraints = [Constraint::Fixed(10), Constraint::Fixed(10)];
+        assert_eq!(layout_columns(&constraints, &[0, 0], 15), vec![10, 4]);
+    }
+
+    fn table() -> Table {
+        let frame = Container::new(Rect::new(20.0, 5.0)).with_border(TuiBorder::SmoothCorner);
+        let columns = vec![
+            Column::new("name", Constraint::Min(4)),
+            Column::new("cpu", Constraint::Fixed(6)).with_alignment(Alignment::Right),
+        ];
+        let rows = [("init", "0.1"), ("yui", "12.5"), ("cargo", "99.0")]
+            .into_iter()
+            .map(|(name, cpu)| vec![name.to_string(), cpu.to_string()]);
+        Table::new(frame, columns)
+            .with_rows(rows)
+            .with_sort_callback(|rows, column, order| {
+                rows.sort_by(|a, b| a[column].cmp(&b[column]));
+                if order == SortOrder::Descending {
+                    rows.reverse();
+                }
+            })
+    }
+
+    fn render_row(table: &Table, y: usize) -> String {
+        let theme = Theme::default();
+        (0..20)
+            .map(|x| table.contents_of(x, y, &theme).unwrap().symbol)
+            .collect()
+    }
+
+    #[test]
+    fn sticky_header_and_separators() {
+        let mut table = table();
+        assert_eq!(table.widths(), &[11, 6]);
+        assert_eq!(render_row(&table, 1), "│name       │   cpu│");
+        assert_eq!(render_row(&table, 2), "│───────────┼──────│");
+        assert_eq!(render_row(&table, 3), "│init       │   0.1│");
+        table.handle_key(Key::End.into());
+        assert_eq!(table.offset(), 2);
+        assert_eq!(render_row(&table, 1), "│name       │   cpu│");
+        assert_eq!(render_row(&table, 3), "│cargo      │  99.0│");
+    }
+
+    #[test]
+    fn sort_indicator() {
+        let mut table = table();
+        table.select(1);
+        table.sort_by(0);
+        assert_eq!(table.rows()[0][0], "cargo");
+        assert_eq!(table.cursor(), Some(2));
+        assert_eq!(render_row(&table, 1), "│name ▲     │   cpu│");
+        table.sort_by(0);
+        assert_eq!(table.sort(), Some((0, SortOrder::Descending)));
+        assert_eq!(table.rows()[0][0], "yui");
+        assert_eq!(render_row(&table, 1), "│name ▼     │   cpu│");
+    }
+}
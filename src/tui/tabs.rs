@@ -0,0 +1,305 @@
+use std::ops::RangeInclusive;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    theme::{Role, Theme},
+    widget::{local_position, Widget},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Tab(usize),
+    Separator,
+    Previous,
+    Next,
+    Blank,
+}
+
+#[derive(Debug)]
+struct Tab {
+    title: String,
+    page: Box<dyn Widget>,
+}
+
+/// A strip of tab titles on the first row of `area` above the active page.
+/// Hidden pages are kept as they are, so they come back with their scroll
+/// position and focus untouched.
+#[derive(Debug)]
+pub struct Tabs {
+    area: Rect,
+    entries: Vec<Tab>,
+    active: usize,
+    first: usize,
+    strip: Vec<(char, Part)>,
+}
+
+impl Tabs {
+    #[must_use]
+    pub fn new(area: Rect) -> Self {
+        let mut tabs = Self {
+            area,
+            entries: Vec::new(),
+            active: 0,
+            first: 0,
+            strip: Vec::new(),
+        };
+        tabs.layout();
+        tabs
+    }
+
+    #[must_use]
+    pub fn with_tab(mut self, title: impl Into<String>, page: impl Widget + 'static) -> Self {
+        self.push(title, page);
+        self
+    }
+
+    pub fn push(&mut self, title: impl Into<String>, page: impl Widget + 'static) {
+        self.entries.push(Tab {
+            title: title.into(),
+            page: Box::new(page),
+        });
+        self.layout();
+    }
+
+    /// The space below the strip that pages should be laid out in.
+    #[must_use]
+    pub fn page_area(&self) -> Rect {
+        let mut area = self.area.clone();
+        let (_, _, height) = area.pos_width_height();
+        let _strip = area.cut_top(height.min(1.0));
+        area
+    }
+
+    #[must_use]
+    pub const fn active(&self) -> usize {
+        self.active
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn title(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(|tab| tab.title.as_str())
+    }
+
+    #[must_use]
+    pub fn page(&self, index: usize) -> Option<&dyn Widget> {
+        self.entries.get(index).map(|tab| tab.page.as_ref())
+    }
+
+    pub fn page_mut(&mut self, index: usize) -> Option<&mut (dyn Widget + 'static)> {
+        self.entries.get_mut(index).map(|tab| tab.page.as_mut())
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.active = index;
+            self.layout();
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.entries.is_empty() {
+            self.select((self.active + 1) % self.entries.len());
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.entries.is_empty() {
+            self.select((self.active + self.entries.len() - 1) % self.entries.len());
+        }
+    }
+
+    fn tab_width(&self, index: usize) -> usize {
+        self.entries[index].title.chars().count() + 2
+    }
+
+    /// Width of a run of tabs including the separators between them.
+    fn span(&self, tabs: RangeInclusive<usize>) -> usize {
+        tabs.map(|i| self.tab_width(i) + 1).sum::<usize>() - 1
+    }
+
+    /// Lays out the strip, scrolling it so that the active tab is visible
+    /// when the titles do not all fit.
+    #[allow(clippy::cast_sign_loss)]
+    fn layout(&mut self) {
+        let (_, width, _) = self.area.pos_width_height();
+        let width = width as usize;
+        let total: usize = (0..self.entries.len()).map(|i| self.tab_width(i) + 1).sum();
+        let overflows = total.saturating_sub(1) > width;
+        let room = if overflows {
+            width.saturating_sub(2)
+        } else {
+            width
+        };
+
+        self.first = if overflows {
+            self.first.min(self.active)
+        } else {
+            0
+        };
+        while self.first < self.active && self.span(self.first..=self.active) > room {
+            self.first += 1;
+        }
+
+        let mut cells = Vec::with_capacity(total);
+        for (i, tab) in self.entries.iter().enumerate().skip(self.first) {
+            if i > self.first {
+                cells.push(('│', Part::Separator));
+            }
+            let title = format!(" {} ", tab.title);
+            cells.extend(title.chars().map(|symbol| (symbol, Part::Tab(i))));
+        }
+        let hidden_after = cells.len() > room;
+        cells.resize(room, (' ', Part::Blank));
+        if overflows {
+            let previous = if self.first > 0 { '‹' } else { ' ' };
+            let next = if hidden_after { '›' } else { ' ' };
+            cells.insert(0, (previous, Part::Previous));
+            cells.push((next, Part::Next));
+        }
+        self.strip = cells;
+    }
+}
+
+impl Widget for Tabs {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let (column, row) = local_position(&self.area, x, y)?;
+        if row > 0 {
+            let page = self.entries.get(self.active);
+            return Some(
+                page.and_then(|tab| tab.page.contents_of(x, y, theme))
+                    .unwrap_or_else(|| Cell::blank(theme.style(Role::Text))),
+            );
+        }
+        let (symbol, part) = self.strip.get(column).copied()?;
+        let role = match part {
+            Part::Tab(i) if i == self.active => Role::Selection,
+            Part::Tab(_) => Role::TextMuted,
+            Part::Separator | Part::Previous | Part::Next => Role::Border,
+            Part::Blank => Role::Text,
+        };
+        Some(Cell::new(symbol, theme.style(role)))
+    }
+
+    fn cursor_position(&self) -> Option<(usize, usize)> {
+        self.entries.get(self.active)?.page.cursor_position()
+    }
+
+    /// Ctrl+PageUp and Ctrl+PageDown cycle through the tabs and Alt with a
+    /// digit jumps to one. Other keys go to the active page.
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let KeyEvent { key, modifiers } = event;
+        match key {
+            Key::PageDown if modifiers.ctrl => self.select_next(),
+            Key::PageUp if modifiers.ctrl => self.select_previous(),
+            Key::Char(digit @ '1'..='9') if modifiers.alt => {
+                self.select(digit as usize - '1' as usize);
+            }
+            _ => {
+                return self
+                    .entries
+                    .get_mut(self.active)
+                    .is_some_and(|tab| tab.page.handle_key(event));
+            }
+        }
+        true
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        let Some((column, 0)) = local_position(&self.area, event.x, event.y) else {
+            return self
+                .entries
+                .get_mut(self.active)
+                .is_some_and(|tab| tab.page.handle_mouse(event));
+        };
+        let part = self.strip.get(column).map(|&(_, part)| part);
+        match (event.kind, part) {
+            (MouseEventKind::Down(MouseButton::Left), Some(Part::Tab(i))) => self.select(i),
+            (MouseEventKind::Down(MouseButton::Left), Some(Part::Previous))
+            | (MouseEventKind::ScrollUp, _) => self.select_previous(),
+            (MouseEventKind::Down(MouseButton::Left), Some(Part::Next))
+            | (MouseEventKind::ScrollDown, _) => self.select_next(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::{container::Container, event::Modifiers, list::List};
+
+    fn tabs(width: f64, titles: &[&str]) -> Tabs {
+        let mut tabs = Tabs::new(Rect::new(width, 4.0));
+        for title in titles {
+            let page = List::new(Container::new(tabs.page_area()))
+                .with_items((0..5).map(|i| format!("{title} {i}")));
+            tabs.push(*title, page);
+        }
+        tabs
+    }
+
+    fn strip(tabs: &Tabs, width: usize) -> String {
+        let theme = Theme::default();
+        (0..width)
+            .map(|x| tabs.contents_of(x, 0, &theme).unwrap().symbol)
+            .collect()
+    }
+
+    #[test]
+    fn highlights_active_tab() {
+        let mut tabs = tabs(16.0, &["one", "two"]);
+        assert_eq!(strip(&tabs, 16), " one │ two      ");
+        tabs.handle_key(KeyEvent::new(Key::PageDown, Modifiers::CTRL));
+        let theme = Theme::default();
+        let cell = tabs.contents_of(7, 0, &theme).unwrap();
+        assert_eq!(cell, Cell::new('t', theme.style(Role::Selection)));
+        let page = tabs.contents_of(0, 1, &theme).unwrap();
+        assert_eq!(page.symbol, 't');
+    }
+
+    #[test]
+    fn overflowing_strip_scrolls() {
+        let mut tabs = tabs(12.0, &["alpha", "beta", "gamma"]);
+        assert_eq!(strip(&tabs, 12), "  alpha │ b›");
+        tabs.select(2);
+        assert_eq!(strip(&tabs, 12), "‹ gamma     ");
+        tabs.handle_mouse(MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            0,
+            0,
+        ));
+        assert_eq!(tabs.active(), 1);
+        assert_eq!(strip(&tabs, 12), "‹ beta │ ga›");
+    }
+
+    #[test]
+    fn hidden_pages_keep_their_state() {
+        let mut tabs = tabs(20.0, &["one", "two"]);
+        tabs.handle_key(Key::Down.into());
+        tabs.handle_key(Key::Down.into());
+        tabs.handle_mouse(MouseEvent::new(
+            MouseEventKind::Down(MouseButton::Left),
+            8,
+            0,
+        ));
+        assert_eq!(tabs.active(), 1);
+        tabs.handle_key(KeyEvent::new(Key::Char('1'), Modifiers::ALT));
+        let theme = Theme::default();
+        let cell = tabs.contents_of(0, 3, &theme).unwrap();
+        assert_eq!(cell, Cell::new('o', theme.style(Role::Selection)));
+    }
+}
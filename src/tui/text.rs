@@ -0,0 +1,448 @@
+use std::{borrow::Cow, fmt::Display, ops::Range};
+
+use ansi_term::Style;
+use textwrap::{Options, WordSplitter};
+use unicode_width::UnicodeWidthChar;
+
+use super::theme::{parse_style, Role, Theme};
+
+/// A run of text in one style, or in whatever style the theme gives a role
+/// when it is drawn. Spans with neither are drawn with the theme's text role.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Span {
+    content: String,
+    style: Option<Style>,
+    role: Option<Role>,
+}
+
+impl Span {
+    pub fn new(content: impl Into<String>, style: Option<Style>) -> Self {
+        Self {
+            content: content.into(),
+            style,
+            role: None,
+        }
+    }
+
+    pub fn raw(content: impl Into<String>) -> Self {
+        Self::new(content, None)
+    }
+
+    pub fn styled(content: impl Into<String>, style: Style) -> Self {
+        Self::new(content, Some(style))
+    }
+
+    /// A span drawn in the style of `role` in the theme at hand.
+    pub fn themed(content: impl Into<String>, role: Role) -> Self {
+        Self {
+            content: content.into(),
+            style: None,
+            role: Some(role),
+        }
+    }
+
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    #[must_use]
+    pub const fn style(&self) -> Option<Style> {
+        self.style
+    }
+
+    #[must_use]
+    pub const fn role(&self) -> Option<Role> {
+        self.role
+    }
+
+    /// The span's style, or else the style `theme` gives its role.
+    #[must_use]
+    pub fn style_in(&self, theme: &Theme) -> Option<Style> {
+        self.style
+            .or_else(|| self.role.map(|role| theme.style(role)))
+    }
+
+    /// The same style and role over other content.
+    fn with_content(&self, content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            style: self.style,
+            role: self.role,
+        }
+    }
+}
+
+impl From<&str> for Span {
+    fn from(content: &str) -> Self {
+        Self::raw(content)
+    }
+}
+
+impl From<String> for Span {
+    fn from(content: String) -> Self {
+        Self::raw(content)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLine {
+    spans: Vec<Span>,
+}
+
+impl TextLine {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_span(mut self, span: impl Into<Span>) -> Self {
+        self.push(span);
+        self
+    }
+
+    /// Adds a span, merging it into the last one when the styles match.
+    pub fn push(&mut self, span: impl Into<Span>) {
+        let span = span.into();
+        match self.spans.last_mut() {
+            _ if span.content.is_empty() => {}
+            Some(last) if (last.style, last.role) == (span.style, span.role) => {
+                last.content.push_str(&span.content);
+            }
+            _ => self.spans.push(span),
+        }
+    }
+
+    #[must_use]
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Width in terminal columns, counting wide symbols twice.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.symbols()
+            .map(|(symbol, _)| symbol.width().unwrap_or(0))
+            .sum()
+    }
+
+    /// The symbol and style in the given column, with roles looked up in
+    /// `theme`. The column after a double width symbol holds a
+    /// [`Cell::continuation`](super::Cell::continuation) symbol, and zero
+    /// width symbols take up no column at all.
+    #[must_use]
+    pub fn cell_at(&self, column: usize, theme: &Theme) -> Option<(char, Option<Style>)> {
+        let mut start = 0;
+        for (symbol, span) in self.symbols() {
+            let width = symbol.width().unwrap_or(0);
+            if column < start + width {
+                let symbol = if column == start { symbol } else { '\0' };
+                return Some((symbol, span.style_in(theme)));
+            }
+            start += width;
+        }
+        None
+    }
+
+    /// The symbols lying entirely within a range of columns.
+    #[must_use]
+    pub fn columns(&self, columns: Range<usize>) -> Self {
+        let mut line = Self::new();
+        let mut start = 0;
+        for (symbol, span) in self.symbols() {
+            let end = start + symbol.width().unwrap_or(0);
+            if columns.start <= start && end <= columns.end {
+                line.push(span.with_content(symbol));
+            }
+            start = end;
+        }
+        line
+    }
+
+    fn symbols(&self) -> impl Iterator<Item = (char, &Span)> + '_ {
+        self.spans
+            .iter()
+            .flat_map(|span| span.content.chars().map(move |symbol| (symbol, span)))
+    }
+
+    /// The spans covering a byte range of the line's text.
+    fn slice(&self, range: Range<usize>) -> Self {
+        let mut line = Self::new();
+        let mut start = 0;
+        for span in &self.spans {
+            let end = start + span.content.len();
+            let from = range.start.clamp(start, end) - start;
+            let to = range.end.clamp(start, end) - start;
+            line.push(span.with_content(&span.content[from..to]));
+            start = end;
+        }
+        line
+    }
+}
+
+impl From<Vec<Span>> for TextLine {
+    fn from(spans: Vec<Span>) -> Self {
+        spans.into_iter().fold(Self::new(), Self::with_span)
+    }
+}
+
+impl From<Span> for TextLine {
+    fn from(span: Span) -> Self {
+        Self::new().with_span(span)
+    }
+}
+
+impl From<&str> for TextLine {
+    fn from(content: &str) -> Self {
+        Self::new().with_span(content)
+    }
+}
+
+impl From<String> for TextLine {
+    fn from(content: String) -> Self {
+        Self::new().with_span(content)
+    }
+}
+
+impl Display for TextLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.spans
+            .iter()
+            .try_for_each(|span| f.write_str(&span.content))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMarkup(pub String);
+
+impl Display for InvalidMarkup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid markup `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidMarkup {}
+
+/// Lines of styled spans, built up line by line or parsed from markup.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Text {
+    lines: Vec<TextLine>,
+}
+
+impl Text {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_line(mut self, line: impl Into<TextLine>) -> Self {
+        self.push_line(line);
+        self
+    }
+
+    pub fn push_line(&mut self, line: impl Into<TextLine>) {
+        self.lines.push(line.into());
+    }
+
+    #[must_use]
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// Parses inline markup where `[style]` starts a span styled with
+    /// anything [`parse_style`] accepts and `[/]` ends it. Tags nest, and
+    /// `[[` is a literal bracket.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidMarkup`] for unclosed or unmatched tags, or a tag
+    /// that isn't a style.
+    pub fn parse_markup(markup: &str) -> Result<Self, InvalidMarkup> {
+        let invalid = || InvalidMarkup(markup.to_string());
+        let mut text = Self::new();
+        let mut line = TextLine::new();
+        let mut styles: Vec<Style> = Vec::new();
+        let mut rest = markup;
+        while let Some(i) = rest.find(['[', '\n']) {
+            let style = styles.last().copied();
+            line.push(Span::new(&rest[..i], style));
+            let after = &rest[i + 1..];
+            if rest[i..].starts_with('\n') {
+                text.push_line(std::mem::replace(&mut line, TextLine::new()));
+                rest = after;
+            } else if let Some(after) = after.strip_prefix('[') {
+                line.push(Span::new("[", style));
+                rest = after;
+            } else {
+                let end = after.find(']').ok_or_else(invalid)?;
+                match &after[..end] {
+                    "/" => drop(styles.pop().ok_or_else(invalid)?),
+                    spec => styles.push(layered(
+                        style.unwrap_or_default(),
+                        parse_style(spec).map_err(|_| invalid())?,
+                    )),
+                }
+                rest = &after[end + 1..];
+            }
+        }
+        line.push(Span::new(rest, styles.last().copied()));
+        text.push_line(line);
+        Ok(text)
+    }
+
+    /// Wraps every line at word boundaries, keeping the style of each piece.
+    #[must_use]
+    pub fn wrapped(&self, width: usize) -> Self {
+        let options = Options::new(width.max(1)).word_splitter(WordSplitter::NoHyphenation);
+        let mut text = Self::new();
+        for line in &self.lines {
+            let plain = line.to_string();
+            if plain.is_empty() {
+                text.push_line(TextLine::new());
+                continue;
+            }
+            for piece in textwrap::wrap(&plain, &options) {
+                text.push_line(match piece {
+                    Cow::Borrowed(piece) => {
+                        let start = piece.as_ptr() as usize - plain.as_ptr() as usize;
+                        line.slice(start..start + piece.len())
+                    }
+                    Cow::Owned(piece) => TextLine::from(piece),
+                });
+            }
+        }
+        text
+    }
+}
+
+impl From<&str> for Text {
+    fn from(content: &str) -> Self {
+        Self {
+            lines: content.split('\n').map(TextLine::from).collect(),
+        }
+    }
+}
+
+impl From<String> for Text {
+    fn from(content: String) -> Self {
+        Self::from(content.as_str())
+    }
+}
+
+impl From<TextLine> for Text {
+    fn from(line: TextLine) -> Self {
+        Self::new().with_line(line)
+    }
+}
+
+impl Display for Text {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `over` on top of `base`: colours set in `over` win and modifiers add up.
+pub const fn layered(base: Style, over: Style) -> Style {
+    Style {
+        foreground: if over.foreground.is_some() {
+            over.foreground
+        } else {
+            base.foreground
+        },
+        background: if over.background.is_some() {
+            over.background
+        } else {
+            base.background
+        },
+        is_bold: base.is_bold || over.is_bold,
+        is_dimmed: base.is_dimmed || over.is_dimmed,
+        is_italic: base.is_italic || over.is_italic,
+        is_underline: base.is_underline || over.is_underline,
+        is_blink: base.is_blink || over.is_blink,
+        is_reverse: base.is_reverse || over.is_reverse,
+        is_hidden: base.is_hidden || over.is_hidden,
+        is_strikethrough: base.is_strikethrough || over.is_strikethrough,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ansi_term::Colour;
+
+    use super::*;
+
+    #[test]
+    fn markup_nests_styles() {
+        let text =
+            Text::parse_markup("[bold red]error[/]: [[x] [on blue]a [italic]b[/][/]").unwrap();
+        let red = Colour::Red.bold();
+        let blue = Style::new().on(Colour::Blue);
+        let expected = TextLine::new()
+            .with_span(Span::styled("error", red))
+            .with_span(": [x] ")
+            .with_span(Span::styled("a ", blue))
+            .with_span(Span::styled("b", blue.italic()));
+        assert_eq!(text, Text::from(expected));
+    }
+
+    #[test]
+    fn invalid_markup() {
+        assert_eq!(
+            Text::parse_markup("oops[/]"),
+            Err(InvalidMarkup("oops[/]".to_string()))
+        );
+        assert!(Text::parse_markup("[bold").is_err());
+        let err = Text::parse_markup("[sparkly]x[/]").unwrap_err();
+        assert_eq!(err.to_string(), "invalid markup `[sparkly]x[/]`");
+    }
+
+    #[test]
+    fn wrapping_splits_spans() {
+        let text = Text::parse_markup("plain [green]coloured words[/] end\nnext").unwrap();
+        let wrapped = text.wrapped(10);
+        assert_eq!(wrapped.to_string(), "plain\ncoloured\nwords end\nnext");
+        let green = Style::new().fg(Colour::Green);
+        assert_eq!(wrapped.lines()[1], Span::styled("coloured", green).into());
+        assert_eq!(
+            wrapped.lines()[2],
+            vec![Span::styled("words", green), Span::raw(" end")].into()
+        );
+    }
+
+    #[test]
+    fn wide_symbols_take_two_columns() {
+        let line = TextLine::from("日本 ok");
+        let theme = Theme::default();
+        assert_eq!(line.width(), 7);
+        assert_eq!(line.cell_at(1, &theme), Some(('\0', None)));
+        assert_eq!(line.cell_at(2, &theme), Some(('本', None)));
+        assert_eq!(line.cell_at(5, &theme), Some(('o', None)));
+        assert_eq!(line.columns(1..6).to_string(), "本 o");
+        let wrapped = Text::from("日本語のテキスト").wrapped(6);
+        assert_eq!(wrapped.to_string(), "日本語\nのテキ\nスト");
+    }
+
+    #[test]
+    fn themed_spans_follow_the_theme() {
+        let line = TextLine::new()
+            .with_span(Span::themed("fn", Role::SyntaxKeyword))
+            .with_span(" main");
+        let wrapped = Text::from(line).wrapped(3);
+        assert_eq!(
+            wrapped.lines()[0].spans()[0].role(),
+            Some(Role::SyntaxKeyword)
+        );
+        let theme = Theme::default().with_style(Role::SyntaxKeyword, Colour::Red.bold());
+        let cell = wrapped.lines()[0].cell_at(0, &theme);
+        assert_eq!(cell, Some(('f', Some(Colour::Red.bold()))));
+        assert_eq!(wrapped.lines()[1].cell_at(0, &theme), Some(('m', None)));
+    }
+}
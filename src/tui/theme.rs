@@ -0,0 +1,534 @@
+//! Semantic style roles shared by every widget.
+//!
+//! A theme file is TOML with an optional `base` theme and a `[styles]` table
+//! keyed by role name. Styles are written either as a short spec string or as
+//! an inline table:
+//!
+//! ```toml
+//! base = "dark"
+//!
+//! [styles]
+//! border = "bright-black"
+//! "border.focused" = "bold cyan"
+//! selection = { fg = "black", bg = "#87afff", bold = true }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    path::Path,
+    str::FromStr,
+};
+
+use ansi_term::{Colour, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    Border,
+    BorderFocused,
+    Title,
+    Text,
+    TextMuted,
+    Selection,
+    Error,
+    Warning,
+    Scrollbar,
+    Progress,
+    SyntaxKeyword,
+    SyntaxType,
+    SyntaxFunction,
+    SyntaxString,
+    SyntaxNumber,
+    SyntaxComment,
+    DiffAdded,
+    DiffRemoved,
+    LogInfo,
+    LogDebug,
+    LogTrace,
+}
+
+impl Role {
+    pub const ALL: [Self; 21] = [
+        Self::Border,
+        Self::BorderFocused,
+        Self::Title,
+        Self::Text,
+        Self::TextMuted,
+        Self::Selection,
+        Self::Error,
+        Self::Warning,
+        Self::Scrollbar,
+        Self::Progress,
+        Self::SyntaxKeyword,
+        Self::SyntaxType,
+        Self::SyntaxFunction,
+        Self::SyntaxString,
+        Self::SyntaxNumber,
+        Self::SyntaxComment,
+        Self::DiffAdded,
+        Self::DiffRemoved,
+        Self::LogInfo,
+        Self::LogDebug,
+        Self::LogTrace,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Border => "border",
+            Self::BorderFocused => "border.focused",
+            Self::Title => "title",
+            Self::Text => "text",
+            Self::TextMuted => "text.muted",
+            Self::Selection => "selection",
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Scrollbar => "scrollbar",
+            Self::Progress => "progress",
+            Self::SyntaxKeyword => "syntax.keyword",
+            Self::SyntaxType => "syntax.type",
+            Self::SyntaxFunction => "syntax.function",
+            Self::SyntaxString => "syntax.string",
+            Self::SyntaxNumber => "syntax.number",
+            Self::SyntaxComment => "syntax.comment",
+            Self::DiffAdded => "diff.added",
+            Self::DiffRemoved => "diff.removed",
+            Self::LogInfo => "log.info",
+            Self::LogDebug => "log.debug",
+            Self::LogTrace => "log.trace",
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Role {
+    type Err = ThemeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.name() == s)
+            .ok_or_else(|| ThemeError::UnknownRole(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    styles: HashMap<Role, Style>,
+}
+
+impl Theme {
+    #[must_use]
+    pub fn dark() -> Self {
+        Self::from_styles([
+            (Role::Border, Colour::Fixed(244).normal()),
+            (Role::BorderFocused, Colour::Cyan.bold()),
+            (Role::Title, Colour::White.bold()),
+            (Role::Text, Style::new()),
+            (Role::TextMuted, Colour::Fixed(245).normal()),
+            (Role::Selection, Colour::Black.on(Colour::Cyan)),
+            (Role::Error, Colour::Red.bold()),
+            (Role::Warning, Colour::Yellow.normal()),
+            (Role::Scrollbar, Colour::Fixed(240).normal()),
+            (Role::Progress, Colour::Cyan.normal()),
+            (Role::SyntaxKeyword, Colour::Purple.bold()),
+            (Role::SyntaxType, Colour::Yellow.normal()),
+            (Role::SyntaxFunction, Colour::Blue.normal()),
+            (Role::SyntaxString, Colour::Green.normal()),
+            (Role::SyntaxNumber, Colour::Cyan.normal()),
+            (Role::SyntaxComment, Colour::Fixed(245).italic()),
+            (Role::DiffAdded, Colour::Green.normal()),
+            (Role::DiffRemoved, Colour::Red.normal()),
+            (Role::LogInfo, Colour::Green.normal()),
+            (Role::LogDebug, Colour::Blue.normal()),
+            (Role::LogTrace, Colour::Purple.dimmed()),
+        ])
+    }
+
+    #[must_use]
+    pub fn light() -> Self {
+        Self::from_styles([
+            (Role::Border, Colour::Fixed(248).normal()),
+            (Role::BorderFocused, Colour::Blue.bold()),
+            (Role::Title, Colour::Black.bold()),
+            (Role::Text, Colour::Black.normal()),
+            (Role::TextMuted, Colour::Fixed(243).normal()),
+            (Role::Selection, Colour::White.on(Colour::Blue)),
+            (Role::Error, Colour::Red.bold()),
+            (Role::Warning, Colour::Fixed(130).normal()),
+            (Role::Scrollbar, Colour::Fixed(250).normal()),
+            (Role::Progress, Colour::Blue.normal()),
+            (Role::SyntaxKeyword, Colour::Purple.bold()),
+            (Role::SyntaxType, Colour::Fixed(130).normal()),
+            (Role::SyntaxFunction, Colour::Blue.normal()),
+            (Role::SyntaxString, Colour::Fixed(28).normal()),
+            (Role::SyntaxNumber, Colour::Fixed(30).normal()),
+            (Role::SyntaxComment, Colour::Fixed(243).italic()),
+            (Role::DiffAdded, Colour::Fixed(28).normal()),
+            (Role::DiffRemoved, Colour::Fixed(124).normal()),
+            (Role::LogInfo, Colour::Fixed(28).normal()),
+            (Role::LogDebug, Colour::Blue.normal()),
+            (Role::LogTrace, Colour::Purple.normal()),
+        ])
+    }
+
+    #[must_use]
+    pub fn high_contrast() -> Self {
+        Self::from_styles([
+            (Role::Border, Colour::White.bold()),
+            (Role::BorderFocused, Colour::Yellow.bold()),
+            (Role::Title, Colour::White.bold().underline()),
+            (Role::Text, Colour::White.normal()),
+            (Role::TextMuted, Colour::White.normal()),
+            (Role::Selection, Colour::Black.on(Colour::Yellow).bold()),
+            (Role::Error, Colour::White.on(Colour::Red).bold()),
+            (Role::Warning, Colour::Black.on(Colour::Yellow)),
+            (Role::Scrollbar, Colour::White.bold()),
+            (Role::Progress, Colour::Yellow.bold()),
+            (Role::SyntaxKeyword, Colour::Yellow.bold()),
+            (Role::SyntaxType, Colour::Cyan.bold()),
+            (Role::SyntaxFunction, Colour::White.bold()),
+            (Role::SyntaxString, Colour::Green.bold()),
+            (Role::SyntaxNumber, Colour::Cyan.normal()),
+            (Role::SyntaxComment, Colour::White.italic()),
+            (Role::DiffAdded, Colour::Green.bold()),
+            (Role::DiffRemoved, Colour::Red.bold()),
+            (Role::LogInfo, Colour::Green.bold()),
+            (Role::LogDebug, Colour::Cyan.bold()),
+            (Role::LogTrace, Colour::White.normal()),
+        ])
+    }
+
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" | "high_contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    fn from_styles(styles: impl IntoIterator<Item = (Role, Style)>) -> Self {
+        Self {
+            styles: styles.into_iter().collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn style(&self, role: Role) -> Style {
+        self.styles.get(&role).copied().unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn with_style(mut self, role: Role, style: Style) -> Self {
+        self.set_style(role, style);
+        self
+    }
+
+    pub fn set_style(&mut self, role: Role, style: Style) {
+        self.styles.insert(role, style);
+    }
+
+    /// Reads a theme file, see [`Theme::from_toml`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ThemeError::Io`] if the file can't be read, or any error of
+    /// [`Theme::from_toml`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let source = std::fs::read_to_string(path).map_err(ThemeError::Io)?;
+        Self::from_toml(&source)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error for invalid TOML, an unknown base theme or role, or a
+    /// style that doesn't parse.
+    pub fn from_toml(source: &str) -> Result<Self, ThemeError> {
+        Self::from_table(&source.parse().map_err(ThemeError::Toml)?)
+    }
+
+    /// A theme from an already parsed theme file.
+    pub(crate) fn from_table(table: &toml::Table) -> Result<Self, ThemeError> {
+        let mut theme = match table.get("base") {
+            Some(toml::Value::String(name)) => {
+                Self::named(name).ok_or_else(|| ThemeError::UnknownTheme(name.clone()))?
+            }
+            Some(other) => return Err(ThemeError::InvalidStyle(other.to_string())),
+            None => Self::default(),
+        };
+        if let Some(styles) = table.get("styles") {
+            let styles = styles
+                .as_table()
+                .ok_or_else(|| ThemeError::InvalidStyle(styles.to_string()))?;
+            for (role, value) in styles {
+                theme.set_style(role.parse()?, style_from_toml(value)?);
+            }
+        }
+        Ok(theme)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+fn style_from_toml(value: &toml::Value) -> Result<Style, ThemeError> {
+    let invalid = || ThemeError::InvalidStyle(value.to_string());
+    match value {
+        toml::Value::String(spec) => parse_style(spec),
+        toml::Value::Table(table) => {
+            let mut style = Style::new();
+            for (key, value) in table {
+                match (key.as_str(), value) {
+                    ("fg", toml::Value::String(colour)) => {
+                        style.foreground = Some(parse_colour(colour)?);
+                    }
+                    ("bg", toml::Value::String(colour)) => {
+                        style.background = Some(parse_colour(colour)?);
+                    }
+                    (modifier, toml::Value::Boolean(enabled)) => {
+                        if *enabled {
+                            style = apply_modifier(style, modifier).ok_or_else(invalid)?;
+                        }
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            Ok(style)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses specs like `"bold red on black"`, `"italic 244"` or `"#ffaf00 on blue"`.
+///
+/// # Errors
+///
+/// Returns [`ThemeError::InvalidStyle`] for unknown words, or
+/// [`ThemeError::InvalidColour`] for a colour that doesn't parse.
+pub fn parse_style(spec: &str) -> Result<Style, ThemeError> {
+    let mut style = Style::new();
+    let mut words = spec.split_whitespace();
+    while let Some(word) = words.next() {
+        if word == "on" {
+            let colour = words
+                .next()
+                .ok_or_else(|| ThemeError::InvalidStyle(spec.to_string()))?;
+            style.background = Some(parse_colour(colour)?);
+        } else if let Some(modified) = apply_modifier(style, word) {
+            style = modified;
+        } else {
+            style.foreground = Some(parse_colour(word)?);
+        }
+    }
+    Ok(style)
+}
+
+/// The spec [`parse_style`] reads back as `style`, e.g. `"bold red on 236"`.
+#[must_use]
+pub fn style_spec(style: Style) -> String {
+    let modifiers = [
+        (style.is_bold, "bold"),
+        (style.is_dimmed, "dim"),
+        (style.is_italic, "italic"),
+        (style.is_underline, "underline"),
+        (style.is_blink, "blink"),
+        (style.is_reverse, "reverse"),
+        (style.is_hidden, "hidden"),
+        (style.is_strikethrough, "strikethrough"),
+    ];
+    let mut words: Vec<String> = modifiers
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, word)| word.to_string())
+        .collect();
+    words.extend(style.foreground.map(colour_name));
+    if let Some(background) = style.background {
+        words.push(format!("on {}", colour_name(background)));
+    }
+    words.join(" ")
+}
+
+fn colour_name(colour: Colour) -> String {
+    match colour {
+        Colour::Black => "black".to_string(),
+        Colour::Red => "red".to_string(),
+        Colour::Green => "green".to_string(),
+        Colour::Yellow => "yellow".to_string(),
+        Colour::Blue => "blue".to_string(),
+        Colour::Purple => "purple".to_string(),
+        Colour::Cyan => "cyan".to_string(),
+        Colour::White => "white".to_string(),
+        Colour::Fixed(index) => index.to_string(),
+        Colour::RGB(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+    }
+}
+
+fn apply_modifier(style: Style, modifier: &str) -> Option<Style> {
+    Some(match modifier {
+        "bold" => style.bold(),
+        "dim" | "dimmed" => style.dimmed(),
+        "italic" => style.italic(),
+        "underline" => style.underline(),
+        "blink" => style.blink(),
+        "reverse" => style.reverse(),
+        "hidden" => style.hidden(),
+        "strikethrough" => style.strikethrough(),
+        _ => return None,
+    })
+}
+
+/// Parses a colour name, an `#rrggbb` hex colour or a 256-colour index.
+///
+/// # Errors
+///
+/// Returns [`ThemeError::InvalidColour`] if `name` is none of these.
+pub fn parse_colour(name: &str) -> Result<Colour, ThemeError> {
+    let invalid = || ThemeError::InvalidColour(name.to_string());
+    if let Some(hex) = name.strip_prefix('#') {
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+        return Ok(Colour::RGB(channel(0)?, channel(2)?, channel(4)?));
+    }
+    if let Ok(index) = name.parse::<u8>() {
+        return Ok(Colour::Fixed(index));
+    }
+    let (bright, base) = name
+        .strip_prefix("bright-")
+        .map_or((false, name), |base| (true, base));
+    let index = match base {
+        "black" => 0,
+        "red" => 1,
+        "green" => 2,
+        "yellow" => 3,
+        "blue" => 4,
+        "purple" | "magenta" => 5,
+        "cyan" => 6,
+        "white" => 7,
+        _ => return Err(invalid()),
+    };
+    Ok(match (bright, index) {
+        (true, index) => Colour::Fixed(index + 8),
+        (false, 0) => Colour::Black,
+        (false, 1) => Colour::Red,
+        (false, 2) => Colour::Green,
+        (false, 3) => Colour::Yellow,
+        (false, 4) => Colour::Blue,
+        (false, 5) => Colour::Purple,
+        (false, 6) => Colour::Cyan,
+        (false, _) => Colour::White,
+    })
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    UnknownTheme(String),
+    UnknownRole(String),
+    InvalidStyle(String),
+    InvalidColour(String),
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read theme: {err}"),
+            Self::Toml(err) => write!(f, "could not parse theme: {err}"),
+            Self::UnknownTheme(name) => write!(f, "unknown base theme `{name}`"),
+            Self::UnknownRole(name) => write!(f, "unknown style role `{name}`"),
+            Self::InvalidStyle(style) => write!(f, "invalid style `{style}`"),
+            Self::InvalidColour(colour) => write!(f, "invalid colour `{colour}`"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Toml(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn role_names_round_trip() {
+        for role in Role::ALL {
+            assert_eq!(role.name().parse::<Role>().unwrap(), role);
+        }
+        assert!("border.hovered".parse::<Role>().is_err());
+    }
+
+    #[test]
+    fn parse_style_spec() {
+        let style = parse_style("bold red on 236").unwrap();
+        assert_eq!(style, Colour::Red.bold().on(Colour::Fixed(236)));
+        let style = parse_style("#ff8000 underline").unwrap();
+        assert_eq!(style, Colour::RGB(255, 128, 0).underline());
+        assert_eq!(
+            parse_style("bright-blue").unwrap(),
+            Colour::Fixed(12).normal()
+        );
+        assert!(parse_style("red on").is_err());
+        assert!(parse_style("reddish").is_err());
+        for colour in ["#aééb", "#+1+2+3", "#12345", "#1234567"] {
+            assert!(parse_colour(colour).is_err(), "{colour}");
+        }
+    }
+
+    #[test]
+    fn theme_from_toml() {
+        let theme = Theme::from_toml(
+            r##"
+            base = "light"
+
+            [styles]
+            "border.focused" = "bold green"
+            selection = { fg = "black", bg = "#87afff", italic = true }
+            "##,
+        )
+        .unwrap();
+        assert_eq!(theme.style(Role::BorderFocused), Colour::Green.bold());
+        assert_eq!(
+            theme.style(Role::Selection),
+            Colour::Black.on(Colour::RGB(0x87, 0xaf, 0xff)).italic()
+        );
+        assert_eq!(
+            theme.style(Role::Border),
+            Theme::light().style(Role::Border)
+        );
+    }
+
+    #[test]
+    fn theme_from_toml_errors() {
+        assert!(matches!(
+            Theme::from_toml("base = \"solarized\""),
+            Err(ThemeError::UnknownTheme(_))
+        ));
+        assert!(matches!(
+            Theme::from_toml("[styles]\nborders = \"red\""),
+            Err(ThemeError::UnknownRole(_))
+        ));
+        assert!(matches!(
+            Theme::from_toml("[styles]\ntext = { fg = 3 }"),
+            Err(ThemeError::InvalidStyle(_))
+        ));
+    }
+}
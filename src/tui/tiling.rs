@@ -0,0 +1,615 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use glam::Vec2;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    event::{Key, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    split::{Pane, Split, SplitState},
+    theme::Theme,
+    widget::{Direction, Widget},
+};
+
+pub type PaneId = usize;
+
+/// The arrangement of a [`Tiling`], for saving and restoring it.
+///
+/// Written as pane ids and `(direction state first second)` groups, where
+/// the direction is `h` for side by side or `v` for stacked and the state
+/// is a [`SplitState`], e.g. `(h 0.5 0 (v 0.3 1 2))`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Layout {
+    Pane(PaneId),
+    Split {
+        direction: Direction,
+        state: SplitState,
+        first: Box<Self>,
+        second: Box<Self>,
+    },
+}
+
+impl Layout {
+    fn panes(&self, ids: &mut Vec<PaneId>) {
+        match self {
+            Self::Pane(id) => ids.push(*id),
+            Self::Split { first, second, .. } => {
+                first.panes(ids);
+                second.panes(ids);
+            }
+        }
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pane(id) => write!(f, "{id}"),
+            Self::Split {
+                direction,
+                state,
+                first,
+                second,
+            } => {
+                let direction = match direction {
+                    Direction::Horizontal => 'h',
+                    Direction::Vertical => 'v',
+                };
+                write!(f, "({direction} {state} {first} {second})")
+            }
+        }
+    }
+}
+
+impl FromStr for Layout {
+    type Err = InvalidLayout;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spaced = s.replace('(', " ( ").replace(')', " ) ");
+        let mut tokens = spaced.split_whitespace().peekable();
+        let layout = parse_layout(&mut tokens).ok_or_else(|| InvalidLayout(s.to_string()))?;
+        let mut ids = Vec::new();
+        layout.panes(&mut ids);
+        ids.sort_unstable();
+        let unique = ids.windows(2).all(|pair| pair[0] != pair[1]);
+        if tokens.next().is_some() || !unique {
+            return Err(InvalidLayout(s.to_string()));
+        }
+        Ok(layout)
+    }
+}
+
+fn parse_layout<'a>(
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+) -> Option<Layout> {
+    let token = tokens.next()?;
+    if token != "(" {
+        return token.parse().ok().map(Layout::Pane);
+    }
+    let direction = match tokens.next()? {
+        "h" => Direction::Horizontal,
+        "v" => Direction::Vertical,
+        _ => return None,
+    };
+    let mut state = tokens.next()?.to_string();
+    if let Some(pane) = tokens.next_if(|t| matches!(*t, "first" | "second")) {
+        state = format!("{state} {pane}");
+    }
+    let state = state.parse().ok()?;
+    let first = Box::new(parse_layout(tokens)?);
+    let second = Box::new(parse_layout(tokens)?);
+    tokens.next_if_eq(&")")?;
+    Some(Layout::Split {
+        direction,
+        state,
+        first,
+        second,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidLayout(pub String);
+
+impl Display for InvalidLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid layout `{}`", self.0)
+    }
+}
+
+impl std::error::Error for InvalidLayout {}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Pane(PaneId),
+    Split(Split, Box<[Self; 2]>),
+}
+
+impl Node {
+    fn from_layout(layout: Layout) -> Self {
+        match layout {
+            Layout::Pane(id) => Self::Pane(id),
+            Layout::Split {
+                direction,
+                state,
+                first,
+                second,
+            } => {
+                let mut split = Split::new(Rect::new(0.0, 0.0), direction);
+                split.restore(state);
+                let children = [Self::from_layout(*first), Self::from_layout(*second)];
+                Self::Split(split, Box::new(children))
+            }
+        }
+    }
+
+    fn layout(&self) -> Layout {
+        match self {
+            Self::Pane(id) => Layout::Pane(*id),
+            Self::Split(split, children) => Layout::Split {
+                direction: split.direction(),
+                state: split.state(),
+                first: Box::new(children[0].layout()),
+                second: Box::new(children[1].layout()),
+            },
+        }
+    }
+
+    /// Child indices leading to the pane.
+    fn path_to(&self, id: PaneId) -> Option<Vec<usize>> {
+        match self {
+            Self::Pane(pane) => (*pane == id).then(Vec::new),
+            Self::Split(_, children) => children.iter().enumerate().find_map(|(i, child)| {
+                let mut path = child.path_to(id)?;
+                path.insert(0, i);
+                Some(path)
+            }),
+        }
+    }
+
+    fn at_mut(&mut self, path: &[usize]) -> &mut Self {
+        match (path.split_first(), self) {
+            (Some((&i, rest)), Self::Split(_, children)) => children[i].at_mut(rest),
+            (_, node) => node,
+        }
+    }
+
+    fn first_pane(&self) -> PaneId {
+        match self {
+            Self::Pane(id) => *id,
+            Self::Split(_, children) => children[0].first_pane(),
+        }
+    }
+
+    fn layout_in(&mut self, area: Rect, panes: &mut Vec<(PaneId, Rect)>) {
+        match self {
+            Self::Pane(id) => panes.push((*id, area)),
+            Self::Split(split, children) => {
+                split.set_area(area);
+                let (first, second) = split.panes();
+                children[0].layout_in(first, panes);
+                children[1].layout_in(second, panes);
+            }
+        }
+    }
+
+    fn splits<'a>(&'a self, splits: &mut Vec<&'a Split>) {
+        if let Self::Split(split, children) = self {
+            splits.push(split);
+            children[0].splits(splits);
+            children[1].splits(splits);
+        }
+    }
+
+    fn splits_mut<'a>(&'a mut self, splits: &mut Vec<&'a mut Split>) {
+        if let Self::Split(split, children) = self {
+            splits.push(split);
+            let [first, second] = children.as_mut();
+            first.splits_mut(splits);
+            second.splits_mut(splits);
+        }
+    }
+}
+
+/// A workspace of tiled panes, like the panes of a tmux window.
+///
+/// The layout is a binary tree of [`Split`]s with panes at the leaves. The
+/// tiling only draws the dividers; place a widget or container in each of
+/// [`Tiling::panes`] after every change. Alt and the arrow keys move the
+/// focus between panes, and with Shift added they move the nearest divider.
+#[derive(Debug, Clone)]
+pub struct Tiling {
+    area: Rect,
+    root: Node,
+    panes: Vec<(PaneId, Rect)>,
+    focused: PaneId,
+    zoomed: bool,
+    next_id: PaneId,
+}
+
+impl Tiling {
+    /// A workspace with a single pane, numbered 0.
+    #[must_use]
+    pub fn new(area: Rect) -> Self {
+        let mut tiling = Self {
+            area,
+            root: Node::Pane(0),
+            panes: Vec::new(),
+            focused: 0,
+            zoomed: false,
+            next_id: 1,
+        };
+        tiling.relayout();
+        tiling
+    }
+
+    #[must_use]
+    pub fn from_layout(area: Rect, layout: Layout) -> Self {
+        let root = Node::from_layout(layout);
+        let mut ids = Vec::new();
+        root.layout().panes(&mut ids);
+        let mut tiling = Self {
+            area,
+            focused: root.first_pane(),
+            root,
+            panes: Vec::new(),
+            zoomed: false,
+            next_id: ids.iter().max().map_or(0, |id| id + 1),
+        };
+        tiling.relayout();
+        tiling
+    }
+
+    #[must_use]
+    pub fn layout(&self) -> Layout {
+        self.root.layout()
+    }
+
+    #[must_use]
+    pub const fn area(&self) -> &Rect {
+        &self.area
+    }
+
+    pub fn set_area(&mut self, area: Rect) {
+        self.area = area;
+        self.relayout();
+    }
+
+    fn relayout(&mut self) {
+        self.panes.clear();
+        self.root.layout_in(self.area.clone(), &mut self.panes);
+    }
+
+    /// Every pane with its area. While zoomed, only the focused pane is
+    /// listed and it takes the whole area.
+    #[must_use]
+    pub fn panes(&self) -> Vec<(PaneId, Rect)> {
+        if self.zoomed {
+            return vec![(self.focused, self.area.clone())];
+        }
+        self.panes.clone()
+    }
+
+    #[must_use]
+    pub fn pane_area(&self, id: PaneId) -> Option<Rect> {
+        self.panes()
+            .into_iter()
+            .find_map(|(pane, area)| (pane == id).then_some(area))
+    }
+
+    #[must_use]
+    pub const fn focused(&self) -> PaneId {
+        self.focused
+    }
+
+    pub fn focus(&mut self, id: PaneId) {
+        if self.root.path_to(id).is_some() {
+            self.focused = id;
+        }
+    }
+
+    #[must_use]
+    pub const fn is_zoomed(&self) -> bool {
+        self.zoomed
+    }
+
+    /// Shows the focused pane alone, or the whole layout again.
+    pub const fn toggle_zoom(&mut self) {
+        self.zoomed = !self.zoomed;
+    }
+
+    /// Splits the focused pane, putting a new pane after it, and focuses
+    /// the new pane. Returns its id.
+    pub fn split(&mut self, direction: Direction) -> PaneId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.insert(id, self.focused, direction, Pane::Second);
+        self.focused = id;
+        self.zoomed = false;
+        id
+    }
+
+    fn insert(&mut self, id: PaneId, beside: PaneId, direction: Direction, side: Pane) {
+        let Some(path) = self.root.path_to(beside) else {
+            return;
+        };
+        let node = self.root.at_mut(&path);
+        let children = match side {
+            Pane::First => [Node::Pane(id), Node::Pane(beside)],
+            Pane::Second => [Node::Pane(beside), Node::Pane(id)],
+        };
+        let split = Split::new(Rect::new(0.0, 0.0), direction);
+        *node = Node::Split(split, Box::new(children));
+        self.relayout();
+    }
+
+    /// Removes a pane, giving its room to its neighbour. The last pane can't
+    /// be closed.
+    pub fn close(&mut self, id: PaneId) -> bool {
+        let Some(mut path) = self.root.path_to(id) else {
+            return false;
+        };
+        let Some(index) = path.pop() else {
+            return false;
+        };
+        let parent = self.root.at_mut(&path);
+        let Node::Split(_, children) = parent else {
+            return false;
+        };
+        let [first, second] = std::mem::replace(children.as_mut(), [Node::Pane(0), Node::Pane(0)]);
+        let sibling = if index == 0 { second } else { first };
+        if self.focused == id {
+            self.focused = sibling.first_pane();
+        }
+        *parent = sibling;
+        self.zoomed = false;
+        self.relayout();
+        true
+    }
+
+    /// Exchanges the places of two panes.
+    pub fn swap(&mut self, a: PaneId, b: PaneId) {
+        let (Some(first), Some(second)) = (self.root.path_to(a), self.root.path_to(b)) else {
+            return;
+        };
+        *self.root.at_mut(&first) = Node::Pane(b);
+        *self.root.at_mut(&second) = Node::Pane(a);
+        self.relayout();
+    }
+
+    /// Moves pane `id` next to `target`, splitting the target's place in
+    /// `direction` with `id` on the given side.
+    pub fn move_pane(&mut self, id: PaneId, target: PaneId, direction: Direction, side: Pane) {
+        if id == target || self.root.path_to(target).is_none() {
+            return;
+        }
+        let focused = self.focused;
+        if self.close(id) {
+            self.insert(id, target, direction, side);
+            self.focused = focused;
+        }
+    }
+
+    /// Focuses the nearest pane in the direction of an arrow key.
+    pub fn focus_towards(&mut self, key: Key) {
+        let Some(from) = self.pane_area(self.focused) else {
+            return;
+        };
+        let edges = |rect: &Rect| {
+            let (position, width, height) = rect.pos_width_height();
+            let (x, y) = (f64::from(position.x), f64::from(position.y));
+            [x, y, x + width, y + height]
+        };
+        let [left, top, right, bottom] = edges(&from);
+        let overlap = |a: f64, b: f64, c: f64, d: f64| b.min(d) - a.max(c);
+        let nearest = self
+            .panes
+            .iter()
+            .filter(|(id, _)| *id != self.focused)
+            .filter_map(|(id, rect)| {
+                let [l, t, r, b] = edges(rect);
+                let (gap, shared) = match key {
+                    Key::Left => (left - r, overlap(top, bottom, t, b)),
+                    Key::Right => (l - right, overlap(top, bottom, t, b)),
+                    Key::Up => (top - b, overlap(left, right, l, r)),
+                    Key::Down => (t - bottom, overlap(left, right, l, r)),
+                    _ => return None,
+                };
+                (gap >= 0.0 && shared > 0.0).then_some((gap, -shared, *id))
+            })
+            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some((_, _, id)) = nearest {
+            self.focused = id;
+        }
+    }
+
+    /// Moves the divider of the closest split around the focused pane that
+    /// runs across `key`'s direction.
+    pub fn resize_towards(&mut self, key: Key) {
+        let (direction, cells) = match key {
+            Key::Left => (Direction::Horizontal, -1.0),
+            Key::Right => (Direction::Horizontal, 1.0),
+            Key::Up => (Direction::Vertical, -1.0),
+            Key::Down => (Direction::Vertical, 1.0),
+            _ => return,
+        };
+        let Some(mut path) = self.root.path_to(self.focused) else {
+            return;
+        };
+        while path.pop().is_some() {
+            if let Node::Split(split, _) = self.root.at_mut(&path) {
+                if split.direction() == direction {
+                    split.resize_by(cells);
+                    break;
+                }
+            }
+        }
+        self.relayout();
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        if !event.modifiers.alt
+            || !matches!(event.key, Key::Left | Key::Right | Key::Up | Key::Down)
+        {
+            return false;
+        }
+        if event.modifiers.shift {
+            self.resize_towards(event.key);
+        } else {
+            self.focus_towards(event.key);
+        }
+        true
+    }
+}
+
+impl Widget for Tiling {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        if self.zoomed {
+            return None;
+        }
+        let mut splits = Vec::new();
+        self.root.splits(&mut splits);
+        splits
+            .iter()
+            .find_map(|split| split.contents_of(x, y, theme))
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+
+    /// Drags dividers, and clicking a pane focuses it.
+    fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        if self.zoomed {
+            return false;
+        }
+        let mut splits = Vec::new();
+        self.root.splits_mut(&mut splits);
+        let mut handled = false;
+        for split in splits {
+            handled |= split.handle_mouse(event);
+        }
+        if handled {
+            self.relayout();
+            return true;
+        }
+        if event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return false;
+        }
+        let point = Vec2::new(event.x as f32, event.y as f32);
+        let clicked = self.panes.iter().find(|(_, rect)| rect.contains(point));
+        match clicked {
+            Some(&(id, _)) => {
+                self.focused = id;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::event::Modifiers;
+
+    fn areas(tiling: &Tiling) -> Vec<(PaneId, (f32, f32, f64, f64))> {
+        tiling
+            .panes()
+            .into_iter()
+            .map(|(id, rect)| {
+                let (position, width, height) = rect.pos_width_height();
+                (id, (position.x, position.y, width, height))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_and_closes_panes() {
+        let mut tiling = Tiling::new(Rect::new(41.0, 11.0));
+        assert_eq!(tiling.split(Direction::Horizontal), 1);
+        assert_eq!(tiling.split(Direction::Vertical), 2);
+        assert_eq!(
+            areas(&tiling),
+            [
+                (0, (0.0, 0.0, 20.0, 11.0)),
+                (1, (21.0, 0.0, 20.0, 5.0)),
+                (2, (21.0, 6.0, 20.0, 5.0)),
+            ]
+        );
+        assert!(tiling.close(1));
+        assert_eq!(tiling.focused(), 2);
+        assert_eq!(
+            areas(&tiling),
+            [(0, (0.0, 0.0, 20.0, 11.0)), (2, (21.0, 0.0, 20.0, 11.0))]
+        );
+        assert!(tiling.close(2));
+        assert!(!tiling.close(0), "the last pane stays");
+        assert_eq!(areas(&tiling), [(0, (0.0, 0.0, 41.0, 11.0))]);
+    }
+
+    #[test]
+    fn swaps_moves_zooms_and_focuses() {
+        let mut tiling = Tiling::new(Rect::new(41.0, 11.0));
+        tiling.split(Direction::Horizontal);
+        tiling.split(Direction::Vertical);
+        tiling.swap(0, 2);
+        assert_eq!(tiling.layout().to_string(), "(h 0.5 2 (v 0.5 1 0))");
+        tiling.move_pane(2, 0, Direction::Horizontal, Pane::First);
+        assert_eq!(tiling.layout().to_string(), "(v 0.5 1 (h 0.5 2 0))");
+
+        tiling.focus(1);
+        tiling.handle_key(KeyEvent::new(Key::Down, Modifiers::ALT));
+        assert_eq!(tiling.focused(), 0);
+        tiling.handle_key(KeyEvent::new(Key::Left, Modifiers::ALT));
+        assert_eq!(tiling.focused(), 2);
+        tiling.handle_key(KeyEvent::new(Key::Up, Modifiers::ALT));
+        assert_eq!(tiling.focused(), 1);
+        tiling.focus(0);
+        tiling.toggle_zoom();
+        assert_eq!(areas(&tiling), [(0, (0.0, 0.0, 41.0, 11.0))]);
+        assert_eq!(tiling.contents_of(20, 8, &Theme::default()), None);
+    }
+
+    #[test]
+    fn resizes_with_keys_and_mouse() {
+        let mut tiling = Tiling::new(Rect::new(41.0, 11.0));
+        tiling.split(Direction::Horizontal);
+        let shift_alt = Modifiers {
+            alt: true,
+            shift: true,
+            ..Modifiers::NONE
+        };
+        tiling.handle_key(KeyEvent::new(Key::Right, shift_alt));
+        assert_eq!(tiling.pane_area(0).unwrap().pos_width_height().1, 21.0);
+        let event = |kind, x| MouseEvent::new(kind, x, 3);
+        tiling.handle_mouse(event(MouseEventKind::Down(MouseButton::Left), 21));
+        tiling.handle_mouse(event(MouseEventKind::Drag(MouseButton::Left), 10));
+        tiling.handle_mouse(event(MouseEventKind::Up(MouseButton::Left), 10));
+        assert_eq!(tiling.pane_area(0).unwrap().pos_width_height().1, 10.0);
+        tiling.handle_mouse(event(MouseEventKind::Down(MouseButton::Left), 2));
+        assert_eq!(tiling.focused(), 0);
+    }
+
+    #[test]
+    fn layouts_round_trip() {
+        let source = "(h 0.25 3 (v 0.5 second 1 7))";
+        let layout: Layout = source.parse().unwrap();
+        assert_eq!(layout.to_string(), source);
+        let mut tiling = Tiling::from_layout(Rect::new(41.0, 11.0), layout);
+        assert_eq!(tiling.focused(), 3);
+        assert_eq!(tiling.split(Direction::Vertical), 8);
+        for invalid in [
+            "(h 0.5 1)",
+            "(x 0.5 1 2)",
+            "(h 0.5 1 1)",
+            "1 2",
+            "(h 2 1 2)",
+        ] {
+            assert!(invalid.parse::<Layout>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,515 @@
+use std::fmt::{self, Debug};
+
+use super::{
+    cell::Cell,
+    container::{Container, TuiBorder},
+    event::{Key, KeyEvent},
+    theme::{Role, Theme},
+    widget::Widget,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeNode {
+    label: String,
+    children: Vec<Self>,
+    expanded: bool,
+    lazy: bool,
+}
+
+impl TreeNode {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            children: Vec::new(),
+            expanded: false,
+            lazy: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_children(self, children: Vec<Self>) -> Self {
+        Self {
+            children,
+            lazy: false,
+            ..self
+        }
+    }
+
+    /// Marks the node as having children that the tree's loader provides
+    /// the first time it is expanded.
+    #[must_use]
+    pub fn lazy(self) -> Self {
+        Self { lazy: true, ..self }
+    }
+
+    #[must_use]
+    pub fn with_expanded(self, expanded: bool) -> Self {
+        Self { expanded, ..self }
+    }
+
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    #[must_use]
+    pub fn children(&self) -> &[Self] {
+        &self.children
+    }
+
+    #[must_use]
+    pub const fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    #[must_use]
+    pub const fn is_expandable(&self) -> bool {
+        self.lazy || !self.children.is_empty()
+    }
+}
+
+/// A node visible on screen with its indentation already drawn.
+#[derive(Debug, Clone)]
+struct Row {
+    path: Vec<usize>,
+    /// The guides of the node's ancestors, without its own connector.
+    guides: String,
+    prefix: String,
+}
+
+type Loader = Box<dyn FnMut(&TreeNode) -> Vec<TreeNode>>;
+
+/// Only the rows of expanded nodes are laid out and only the ones in view
+/// are drawn.
+pub struct Tree {
+    frame: Container,
+    roots: Vec<TreeNode>,
+    guides: TuiBorder,
+    loader: Option<Loader>,
+    rows: Vec<Row>,
+    cursor: usize,
+    offset: usize,
+}
+
+impl Tree {
+    #[must_use]
+    pub fn new(frame: Container) -> Self {
+        Self {
+            frame,
+            roots: Vec::new(),
+            guides: TuiBorder::SmoothCorner,
+            loader: None,
+            rows: Vec::new(),
+            cursor: 0,
+            offset: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_roots(mut self, roots: Vec<TreeNode>) -> Self {
+        self.set_roots(roots);
+        self
+    }
+
+    /// Draws the indentation guides with the glyphs of `border`.
+    #[must_use]
+    pub fn with_guides(mut self, border: TuiBorder) -> Self {
+        self.guides = border;
+        self.layout();
+        self
+    }
+
+    #[must_use]
+    pub fn with_loader(self, loader: impl FnMut(&TreeNode) -> Vec<TreeNode> + 'static) -> Self {
+        Self {
+            loader: Some(Box::new(loader)),
+            ..self
+        }
+    }
+
+    pub fn set_roots(&mut self, roots: Vec<TreeNode>) {
+        self.roots = roots;
+        self.cursor = 0;
+        self.offset = 0;
+        self.layout();
+    }
+
+    #[must_use]
+    pub fn roots(&self) -> &[TreeNode] {
+        &self.roots
+    }
+
+    /// Number of rows currently visible when scrolling through the tree.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    #[must_use]
+    pub const fn frame(&self) -> &Container {
+        &self.frame
+    }
+
+    pub const fn frame_mut(&mut self) -> &mut Container {
+        &mut self.frame
+    }
+
+    #[must_use]
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Indices leading from a root to the node under the cursor.
+    #[must_use]
+    pub fn selected_path(&self) -> Option<&[usize]> {
+        self.rows.get(self.cursor).map(|row| row.path.as_slice())
+    }
+
+    #[must_use]
+    pub fn selected(&self) -> Option<&TreeNode> {
+        self.node(self.selected_path()?)
+    }
+
+    #[must_use]
+    pub fn node(&self, path: &[usize]) -> Option<&TreeNode> {
+        let (first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.roots.get(*first)?, |node, &i| node.children.get(i))
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode> {
+        let (first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.roots.get_mut(*first)?, |node, &i| {
+                node.children.get_mut(i)
+            })
+    }
+
+    /// Moves the cursor to the node at `path` if it is visible.
+    pub fn select(&mut self, path: &[usize]) {
+        if let Some(index) = self.rows.iter().position(|row| row.path == path) {
+            self.cursor = index;
+            self.scroll_to_cursor();
+        }
+    }
+
+    pub fn expand(&mut self) {
+        self.set_expanded(true);
+    }
+
+    pub fn collapse(&mut self) {
+        self.set_expanded(false);
+    }
+
+    pub fn toggle(&mut self) {
+        let expanded = self.selected().is_some_and(TreeNode::is_expanded);
+        self.set_expanded(!expanded);
+    }
+
+    /// Expands or collapses the node under the cursor, laying out again
+    /// only the rows of its subtree.
+    fn set_expanded(&mut self, expanded: bool) {
+        let Some(Row { path, guides, .. }) = self.rows.get(self.cursor).cloned() else {
+            return;
+        };
+        let mut loader = self.loader.take();
+        if let Some(node) = self.node_mut(&path) {
+            if expanded && node.lazy {
+                if let Some(loader) = &mut loader {
+                    node.children = loader(node);
+                    node.lazy = false;
+                }
+            }
+            node.expanded = expanded && node.is_expandable();
+        }
+        self.loader = loader;
+        let start = self.cursor;
+        let end = start
+            + 1
+            + self.rows[start + 1..]
+                .iter()
+                .take_while(|row| row.path.starts_with(&path))
+                .count();
+        let rows = self.subtree_rows(path, guides);
+        self.rows.splice(start..end, rows);
+        self.scroll_to_cursor();
+    }
+
+    pub fn select_next(&mut self) {
+        self.move_cursor_to(|cursor, len| (cursor + 1).min(len - 1));
+    }
+
+    pub fn select_previous(&mut self) {
+        self.move_cursor_to(|cursor, _| cursor.saturating_sub(1));
+    }
+
+    pub fn select_parent(&mut self) {
+        if let Some([parent @ .., _]) = self.selected_path().map(<[usize]>::to_vec).as_deref() {
+            if !parent.is_empty() {
+                self.select(parent);
+            }
+        }
+    }
+
+    fn move_cursor_to(&mut self, next: impl FnOnce(usize, usize) -> usize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        self.cursor = next(self.cursor, self.rows.len());
+        self.scroll_to_cursor();
+    }
+
+    /// Right expands the node or steps into it, left collapses it or steps
+    /// out to its parent.
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let page = self.frame.content_size().1.max(1);
+        let Some(node) = self.selected() else {
+            return false;
+        };
+        let (expandable, expanded) = (node.is_expandable(), node.is_expanded());
+        match event.key {
+            Key::Up => self.select_previous(),
+            Key::Down => self.select_next(),
+            Key::Home => self.move_cursor_to(|_, _| 0),
+            Key::End => self.move_cursor_to(|_, len| len - 1),
+            Key::PageUp => self.move_cursor_to(|cursor, _| cursor.saturating_sub(page)),
+            Key::PageDown => self.move_cursor_to(|cursor, len| (cursor + page).min(len - 1)),
+            Key::Right if expanded => self.select_next(),
+            Key::Right if expandable => self.expand(),
+            Key::Left if expanded => self.collapse(),
+            Key::Left => self.select_parent(),
+            Key::Enter | Key::Char(' ') => self.toggle(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let rows = self.frame.content_size().1.max(1);
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + rows {
+            self.offset = self.cursor + 1 - rows;
+        }
+    }
+
+    fn layout(&mut self) {
+        self.rows = (0..self.roots.len())
+            .flat_map(|i| self.subtree_rows(vec![i], String::new()))
+            .collect();
+        self.cursor = self.cursor.min(self.rows.len().saturating_sub(1));
+        self.scroll_to_cursor();
+    }
+
+    /// The rows of the node at `path` and its expanded descendants, given
+    /// the guides of its ancestors.
+    fn subtree_rows(&self, path: Vec<usize>, guides: String) -> Vec<Row> {
+        let symbols = self.guides.symbols();
+        let mut rows = Vec::new();
+        // Each entry holds the path of a node and the guides of its ancestors.
+        let mut stack = vec![(path, guides)];
+        while let Some((path, guides)) = stack.pop() {
+            let Some(node) = self.node(&path) else {
+                continue;
+            };
+            let marker = match (node.is_expandable(), node.expanded) {
+                (false, _) => ' ',
+                (true, false) => '▸',
+                (true, true) => '▾',
+            };
+            let connector = match path.split_last() {
+                Some((last, parent)) if !parent.is_empty() => {
+                    let siblings = self.node(parent).map_or(0, |p| p.children.len());
+                    let corner = if last + 1 == siblings {
+                        symbols.bottom_left
+                    } else {
+                        symbols.tee_right
+                    };
+                    format!("{corner}{}", symbols.horizontal)
+                }
+                _ => String::new(),
+            };
+            if node.expanded {
+                let child_guides = if connector.is_empty() {
+                    String::new()
+                } else if connector.starts_with(symbols.tee_right) {
+                    format!("{guides}{} ", symbols.vertical)
+                } else {
+                    format!("{guides}  ")
+                };
+                for i in (0..node.children.len()).rev() {
+                    let mut child = path.clone();
+                    child.push(i);
+                    stack.push((child, child_guides.clone()));
+                }
+            }
+            rows.push(Row {
+                prefix: format!("{guides}{connector}{marker} "),
+                guides,
+                path,
+            });
+        }
+        rows
+    }
+}
+
+impl Widget for Tree {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell> {
+        let Some((x, y)) = self.frame.local_position(x, y) else {
+            return self.frame.contents_of(x, y, theme);
+        };
+        let index = self.offset + y;
+        let blank = Cell::blank(theme.style(Role::Text));
+        let (Some(row), Some(node)) = (
+            self.rows.get(index),
+            self.rows.get(index).and_then(|row| self.node(&row.path)),
+        ) else {
+            return Some(blank);
+        };
+        let prefix = row.prefix.chars().count();
+        if x < prefix {
+            let symbol = row.prefix.chars().nth(x).unwrap_or(' ');
+            let role = if x + 2 >= prefix {
+                Role::Text
+            } else {
+                Role::Border
+            };
+            return Some(Cell::new(symbol, theme.style(role)));
+        }
+        let role = if index == self.cursor {
+            Role::Selection
+        } else {
+            Role::Text
+        };
+        let symbol = node.label.chars().nth(x - prefix).unwrap_or(' ');
+        Some(Cell::new(symbol, theme.style(role)))
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) -> bool {
+        self.handle_key(event)
+    }
+}
+
+impl Debug for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tree")
+            .field("frame", &self.frame)
+            .field("roots", &self.roots.len())
+            .field("guides", &self.guides)
+            .field("rows", &self.rows.len())
+            .field("cursor", &self.cursor)
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell as Counter, rc::Rc};
+
+    use super::*;
+    use crate::rect::Rect;
+
+    fn render(tree: &Tree, width: usize, height: usize) -> Vec<String> {
+        let theme = Theme::default();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| tree.contents_of(x, y, &theme).unwrap().symbol)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn sample() -> Vec<TreeNode> {
+        vec![TreeNode::new("src").with_expanded(true).with_children(vec![
+            TreeNode::new("tui")
+                .with_expanded(true)
+                .with_children(vec![TreeNode::new("tree.rs")]),
+            TreeNode::new("lib.rs"),
+        ])]
+    }
+
+    #[test]
+    fn draws_guides() {
+        let tree = Tree::new(Container::new(Rect::new(16.0, 4.0))).with_roots(sample());
+        assert_eq!(
+            render(&tree, 16, 4),
+            vec!["▾ src", "├─▾ tui", "│ ╰─  tree.rs", "╰─  lib.rs"]
+        );
+    }
+
+    #[test]
+    fn left_and_right_navigation() {
+        let mut tree = Tree::new(Container::new(Rect::new(16.0, 4.0))).with_roots(sample());
+        tree.handle_key(Key::Right.into());
+        assert_eq!(tree.selected_path(), Some(&[0, 0][..]));
+        tree.handle_key(Key::Left.into());
+        assert_eq!(tree.len(), 3);
+        tree.handle_key(Key::Left.into());
+        assert_eq!(tree.selected().map(TreeNode::label), Some("src"));
+        tree.handle_key(Key::Left.into());
+        assert_eq!(tree.len(), 1);
+        tree.handle_key(Key::Right.into());
+        assert_eq!(tree.len(), 3);
+    }
+
+    #[test]
+    fn expanding_lays_out_only_the_subtree() {
+        let mut tree = Tree::new(Container::new(Rect::new(20.0, 5.0))).with_roots(sample());
+        tree.select(&[0, 0]);
+        tree.collapse();
+        assert_eq!(
+            render(&tree, 20, 4),
+            vec!["▾ src", "├─▸ tui", "╰─  lib.rs", ""]
+        );
+        tree.expand();
+        let fresh = Tree::new(Container::new(Rect::new(20.0, 5.0))).with_roots(sample());
+        assert_eq!(render(&tree, 20, 5), render(&fresh, 20, 5));
+        assert_eq!(tree.selected_path(), Some(&[0, 0][..]));
+        tree.select(&[0]);
+        tree.collapse();
+        assert_eq!((tree.len(), tree.cursor()), (1, 0));
+    }
+
+    #[test]
+    fn loads_children_once() {
+        let calls = Rc::new(Counter::new(0));
+        let counter = Rc::clone(&calls);
+        let mut tree = Tree::new(Container::new(Rect::new(16.0, 4.0)))
+            .with_roots(vec![TreeNode::new("dir").lazy()])
+            .with_loader(move |node| {
+                counter.set(counter.get() + 1);
+                vec![TreeNode::new(format!("{}/a", node.label()))]
+            });
+        tree.expand();
+        tree.collapse();
+        tree.expand();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(render(&tree, 16, 2), vec!["▾ dir", "╰─  dir/a"]);
+    }
+
+    #[test]
+    fn scrolls_large_trees() {
+        let children = (0..100_000).map(|i| TreeNode::new(i.to_string())).collect();
+        let mut tree =
+            Tree::new(Container::new(Rect::new(16.0, 3.0))).with_roots(vec![TreeNode::new("root")
+                .with_expanded(true)
+                .with_children(children)]);
+        tree.handle_key(Key::End.into());
+        assert_eq!((tree.cursor(), tree.offset()), (100_000, 99_998));
+        assert_eq!(render(&tree, 16, 3)[2], "╰─  99999");
+    }
+}
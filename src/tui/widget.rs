@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+
+use glam::Vec2;
+
+use crate::rect::Rect;
+
+use super::{
+    cell::Cell,
+    event::{KeyEvent, MouseEvent},
+    theme::Theme,
+};
+
+pub trait Widget: Debug {
+    fn contents_of(&self, x: usize, y: usize, theme: &Theme) -> Option<Cell>;
+
+    /// Screen position the terminal cursor should be placed at, if any.
+    fn cursor_position(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Returns whether the event was used.
+    fn handle_key(&mut self, _event: KeyEvent) -> bool {
+        false
+    }
+
+    /// Returns whether the event was used.
+    fn handle_mouse(&mut self, _event: MouseEvent) -> bool {
+        false
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+pub fn local_position(area: &Rect, x: usize, y: usize) -> Option<(usize, usize)> {
+    let point = Vec2::new(x as f32, y as f32);
+    if !area.contains(point) {
+        return None;
+    }
+    let (position, ..) = area.pos_width_height();
+    let local = point - position.floor();
+    Some((local.x as usize, local.y as usize))
+}
+
+/// The axis along which splits, tiling layouts and bar charts line up
+/// their parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Placement {
+    /// Under the anchor, or above it when there is no room below.
+    #[default]
+    Below,
+    /// Beside the anchor, or on its left when there is no room on the right.
+    Right,
+}
+
+/// Places a `width` by `height` popup next to `anchor`, moving it as needed
+/// to keep it inside `screen`.
+#[must_use]
+#[allow(clippy::cast_sign_loss)]
+pub fn popup_area(
+    anchor: &Rect,
+    width: usize,
+    height: usize,
+    screen: &Rect,
+    placement: Placement,
+) -> Rect {
+    let span = |rect: &Rect| {
+        let (position, width, height) = rect.pos_width_height();
+        let (x, y) = (position.x.max(0.0) as usize, position.y.max(0.0) as usize);
+        (x, y, x + width as usize, y + height as usize)
+    };
+    let (left, top, right, bottom) = span(anchor);
+    let (min_x, min_y, max_x, max_y) = span(screen);
+    let (width, height) = (width.min(max_x - min_x), height.min(max_y - min_y));
+    let fit = |start: usize, flipped: Option<usize>, min: usize, max: usize, size: usize| {
+        if start + size <= max {
+            start
+        } else {
+            flipped
+                .filter(|&flipped| flipped >= min)
+                .unwrap_or(max - size)
+        }
+    };
+    let (x, y) = match placement {
+        Placement::Below => (
+            left.clamp(min_x, max_x - width),
+            fit(bottom, top.checked_sub(height), min_y, max_y, height),
+        ),
+        Placement::Right => (
+            fit(right, left.checked_sub(width), min_x, max_x, width),
+            top.clamp(min_y, max_y - height),
+        ),
+    };
+    Rect::new(width as f64, height as f64).with_position(Vec2::new(x as f32, y as f32))
+}